@@ -61,5 +61,25 @@ pub fn linear_render(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, random_render, linear_render);
+pub fn wide_render(c: &mut Criterion) {
+    c.bench_function("[wide] render 51 Nodes - 50 Edges in 2 Levels", |b| {
+        let graph = {
+            let mut tmp = DirectedGraph::new();
+
+            tmp.add_nodes((0..51).map(|idx| (idx, format!("test-{idx}"))));
+            tmp.add_edges((1..51).map(|idx| (0, idx)));
+
+            tmp
+        };
+
+        let conf = termgraph::Config::new(termgraph::IDFormatter::new(), 50);
+
+        b.iter(|| {
+            let mut tmp = Vec::new();
+            termgraph::fdisplay(black_box(&graph), &conf, &mut tmp);
+        });
+    });
+}
+
+criterion_group!(benches, random_render, linear_render, wide_render);
 criterion_main!(benches);
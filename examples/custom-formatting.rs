@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use termgraph::{Config, DirectedGraph, IDFormatter, NodeFormat, ValueFormatter};
+use termgraph::{Config, DefaultFormatter, DirectedGraph, IDFormatter, NodeFormat, ValueFormatter};
 
 struct BareFormatter {}
 
@@ -15,8 +15,8 @@ where
 
 fn main() {
     let mut graph = DirectedGraph::new();
-    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
-    graph.add_edges([(0, 1), (1, 2)]);
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "")]);
+    graph.add_edges([(0, 1), (1, 2), (1, 3)]);
 
     println!("ID Formatter:");
     let id_config = Config::new(IDFormatter::new(), 3).default_colors();
@@ -26,6 +26,10 @@ fn main() {
     let value_config = Config::new(ValueFormatter::new(), 3).default_colors();
     termgraph::display(&graph, &value_config);
 
+    println!("Default Formatter:");
+    let default_config = Config::new(DefaultFormatter::new(), 3).default_colors();
+    termgraph::display(&graph, &default_config);
+
     println!("Bare Formatter:");
     let bare_config = Config::new(BareFormatter {}, 3).default_colors();
     termgraph::display(&graph, &bare_config);
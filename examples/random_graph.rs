@@ -26,7 +26,6 @@ fn main() {
 
             (nodes[src], nodes[target])
         })
-        .into_iter()
         .collect();
 
     let graph = {
@@ -4,6 +4,8 @@ use std::{
     hash::Hash,
 };
 
+use crate::{config::OrderFn, DirectedGraph};
+
 #[derive(Debug)]
 pub struct AcyclicDirectedGraph<'g, ID, T> {
     pub(crate) nodes: HashMap<&'g ID, &'g T>,
@@ -18,6 +20,38 @@ where
         Self { nodes, edges }
     }
 
+    /// The same Graph, where the Values of the Nodes are taken from the given Graph instead, while
+    /// the Nodes missing from it keep their current Value
+    pub fn with_values<'v>(
+        &self,
+        graph: &'v DirectedGraph<ID, T>,
+    ) -> AcyclicDirectedGraph<'v, ID, T>
+    where
+        'g: 'v,
+    {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(id, value)| (*id, graph.get(id).unwrap_or(value)))
+            .collect();
+        AcyclicDirectedGraph {
+            nodes,
+            edges: self.edges.clone(),
+        }
+    }
+
+    /// The same Graph without performing a transitive reduction, see
+    /// [`transitive_reduction`](Self::transitive_reduction), which is a lot faster for large
+    /// Graphs
+    pub fn unreduced(&self) -> MinimalAcyclicDirectedGraph<'g, ID, T> {
+        MinimalAcyclicDirectedGraph {
+            inner: AcyclicDirectedGraph {
+                nodes: self.nodes.clone(),
+                edges: self.edges.clone(),
+            },
+        }
+    }
+
     /// Performs a transitive reduction on the current acyclic graph. This means that all of the
     /// Edges `a -> c` are removed if the Edges `a -> b` and `b -> c` exist.
     pub fn transitive_reduction(&self) -> MinimalAcyclicDirectedGraph<'g, ID, T> {
@@ -126,6 +160,42 @@ where
     pub fn successors(&self, node: &ID) -> Option<&HashSet<&'g ID>> {
         self.edges.get(node)
     }
+
+    /// All the Edges of the Graph
+    pub fn edges(&self) -> impl Iterator<Item = (&'g ID, &'g ID)> + '_ {
+        self.edges
+            .iter()
+            .flat_map(|(src, targets)| targets.iter().map(move |target| (*src, *target)))
+    }
+
+    /// Adds the Edge, which must not close a Cycle
+    pub fn insert_edge(&mut self, src: &'g ID, target: &'g ID) {
+        self.edges.entry(src).or_default().insert(target);
+    }
+
+    /// Checks if there is a Path from `from` to `to`
+    pub fn reaches(&self, from: &'g ID, to: &ID) -> bool {
+        let mut visited: HashSet<&ID> = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(current) = stack.pop() {
+            if current == to {
+                return true;
+            }
+            if visited.insert(current) {
+                stack.extend(self.edges.get(current).into_iter().flatten());
+            }
+        }
+        false
+    }
+
+    /// Counts the Edges leading to every Node
+    pub fn in_degrees(&self) -> HashMap<&'g ID, usize> {
+        let mut result: HashMap<&'g ID, usize> = self.nodes.keys().map(|n| (*n, 0)).collect();
+        for target in self.edges.values().flatten() {
+            *result.entry(*target).or_default() += 1;
+        }
+        result
+    }
 }
 
 impl<'g, ID, T> PartialEq for AcyclicDirectedGraph<'g, ID, T>
@@ -193,6 +263,15 @@ where
     }
 
     pub fn topological_sort(&self) -> Vec<&'g ID>
+    where
+        ID: Hash + Eq,
+    {
+        self.topological_sort_by(None)
+    }
+
+    /// The same as [`topological_sort`](Self::topological_sort), but the Nodes are considered in
+    /// the given Order, so the result does not depend on the Order the Nodes are stored in
+    pub fn topological_sort_by(&self, order: Option<&OrderFn<'_, ID>>) -> Vec<&'g ID>
     where
         ID: Hash + Eq,
     {
@@ -201,6 +280,9 @@ where
         let mut ordering: Vec<&ID> = Vec::new();
 
         let mut nodes: Vec<_> = self.inner.nodes.keys().copied().collect();
+        if let Some(order) = order {
+            nodes.sort_by(|a, b| order(a, b));
+        }
 
         while !nodes.is_empty() {
             let mut potential: Vec<(usize, &ID)> = nodes
@@ -0,0 +1,145 @@
+//! Analyses of a Graph, whose Results can be used to decide what to display, like the Path to
+//! [highlight](crate::DisplayOptions::highlight_path).
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::DirectedGraph;
+
+/// Finds a Path with the fewest Edges from `from` to `to`, including both of them, or returns
+/// `None` if there is no such Path.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{analysis, Config, DirectedGraph, DisplayOptions, IDFormatter};
+///
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+/// graph.add_edges([(0, 1), (1, 2), (2, 3), (0, 2)]);
+///
+/// let path = analysis::shortest_path(&graph, &0, &3).unwrap();
+/// assert_eq!(vec![&0, &2, &3], path);
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let options = DisplayOptions::new().highlight_path(path.into_iter().copied());
+/// termgraph::fdisplay_with(&graph, &config, &options, std::io::stdout().lock());
+/// ```
+pub fn shortest_path<'g, ID, T>(
+    graph: &'g DirectedGraph<ID, T>,
+    from: &ID,
+    to: &ID,
+) -> Option<Vec<&'g ID>>
+where
+    ID: Hash + Eq,
+{
+    let from = graph.get_key(from)?;
+
+    let mut previous: HashMap<&ID, Option<&ID>> = HashMap::from([(from, None)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            return Some(backtrack(&previous, current));
+        }
+        for target in graph.successors(current) {
+            if graph.get(target).is_some() && !previous.contains_key(target) {
+                previous.insert(target, Some(current));
+                queue.push_back(target);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the Path with the lowest total Weight of its Edges from `from` to `to`, including both
+/// of them, or returns `None` if there is no such Path.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{analysis, DirectedGraph};
+///
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([("a", ()), ("b", ()), ("c", ())]);
+/// graph.add_edges([("a", "b"), ("b", "c"), ("a", "c")]);
+///
+/// // The direct Edge is more expensive than the Detour
+/// let weight = |src: &&str, target: &&str| match (*src, *target) {
+///     ("a", "c") => 10,
+///     _ => 1,
+/// };
+/// let path = analysis::shortest_weighted_path(&graph, &"a", &"c", weight);
+/// assert_eq!(Some(vec![&"a", &"b", &"c"]), path);
+/// ```
+pub fn shortest_weighted_path<'g, ID, T, W>(
+    graph: &'g DirectedGraph<ID, T>,
+    from: &ID,
+    to: &ID,
+    weight: W,
+) -> Option<Vec<&'g ID>>
+where
+    ID: Hash + Eq,
+    W: Fn(&ID, &ID) -> u64,
+{
+    let from = graph.get_key(from)?;
+
+    // The Heap only stores the Index of the Node, as the IDs can not be ordered
+    let mut nodes: Vec<&ID> = vec![from];
+    let mut distances: HashMap<&ID, u64> = HashMap::from([(from, 0)]);
+    let mut previous: HashMap<&ID, Option<&ID>> = HashMap::from([(from, None)]);
+    let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+    while let Some(Reverse((distance, index))) = heap.pop() {
+        let current = nodes[index];
+        if distance > distances[current] {
+            continue;
+        }
+        if current == to {
+            return Some(backtrack(&previous, current));
+        }
+
+        for target in graph.successors(current) {
+            if graph.get(target).is_none() {
+                continue;
+            }
+            let candidate = distance.saturating_add(weight(current, target));
+            if distances.get(target).is_none_or(|known| candidate < *known) {
+                distances.insert(target, candidate);
+                previous.insert(target, Some(current));
+                nodes.push(target);
+                heap.push(Reverse((candidate, nodes.len() - 1)));
+            }
+        }
+    }
+    None
+}
+
+/// Follows the Predecessors from the given Node back to the start of the Path
+fn backtrack<'g, ID>(previous: &HashMap<&'g ID, Option<&'g ID>>, end: &'g ID) -> Vec<&'g ID>
+where
+    ID: Hash + Eq,
+{
+    let mut path = vec![end];
+    while let Some(Some(node)) = previous.get(path[path.len() - 1]) {
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreachable() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1), (2, 1)]);
+
+        assert_eq!(None, shortest_path(&graph, &0, &2));
+        assert_eq!(None, shortest_path(&graph, &5, &1));
+        assert_eq!(Some(vec![&1]), shortest_path(&graph, &1, &1));
+        assert_eq!(None, shortest_weighted_path(&graph, &0, &2, |_, _| 1));
+    }
+}
@@ -0,0 +1,106 @@
+use std::{collections::HashSet, hash::Hash, time::Duration};
+
+use crate::{watch::CLEAR_SCREEN, Color, Config, DirectedGraph, DisplayOptions, Layout};
+
+/// Displays the Graph once for every Node of the `order`, to visualize a Traversal of the Graph
+/// step by step.
+///
+/// Every Frame clears the Terminal and highlights the current Node of the Traversal. The Nodes
+/// that were already visited are colored green and the Frontier, which are the Successors of the
+/// visited Nodes that were not visited yet, is colored yellow. After every Frame this waits for
+/// the `delay`.
+///
+/// The `order` can be any Sequence of Nodes, like the ones returned by
+/// [`DirectedGraph::bfs`] or [`DirectedGraph::dfs`], or the order in which your own Algorithm
+/// processes the Nodes. The Layout is only computed once, so the Nodes stay in place between the
+/// Frames.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+/// graph.add_edges([(0, 1), (0, 2), (1, 3), (2, 3)]);
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let order: Vec<usize> = graph.bfs(&0).into_iter().copied().collect();
+/// termgraph::animate(&graph, &config, order, Duration::from_millis(10));
+/// ```
+pub fn animate<ID, T, I>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    order: I,
+    delay: Duration,
+) where
+    ID: Hash + Eq + Clone,
+    I: IntoIterator<Item = ID>,
+{
+    fanimate(graph, config, order, delay, std::io::stdout().lock());
+}
+
+/// The same as [`animate`], but allows you to specify the Output Target
+pub fn fanimate<ID, T, I, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    order: I,
+    delay: Duration,
+    mut dest: W,
+) where
+    ID: Hash + Eq + Clone,
+    I: IntoIterator<Item = ID>,
+    W: std::io::Write,
+{
+    let layout = Layout::compute(graph, config);
+
+    let mut visited: HashSet<ID> = HashSet::new();
+    for current in order {
+        let frontier: HashSet<&ID> = visited
+            .iter()
+            .chain(std::iter::once(&current))
+            .flat_map(|id| graph.successors(id))
+            .filter(|id| !visited.contains(*id) && **id != current)
+            .collect();
+
+        let colors = visited
+            .iter()
+            .map(|id| (id.clone(), Color::Green))
+            .chain(frontier.into_iter().map(|id| (id.clone(), Color::Yellow)));
+        let options = DisplayOptions::new()
+            .node_colors(colors)
+            .highlight([current.clone()]);
+
+        let _ = dest.write_all(CLEAR_SCREEN.as_bytes());
+        layout.fdisplay_with(config, &options, &mut dest);
+        let _ = dest.flush();
+
+        visited.insert(current);
+        std::thread::sleep(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn frames() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1), (1, 2)]);
+
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut output = Vec::new();
+        fanimate(&graph, &config, [0, 1, 2], Duration::ZERO, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        let frames: Vec<&str> = output.split(CLEAR_SCREEN).skip(1).collect();
+        assert_eq!(3, frames.len());
+        // The second Frame has the first Node visited, the second current and the third upcoming
+        assert!(frames[1].contains("\x1b[32m(0)"));
+        assert!(frames[1].contains("\x1b[33m(2)"));
+        assert!(!frames[1].contains("\x1b[32m(1)"));
+    }
+}
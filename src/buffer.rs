@@ -0,0 +1,251 @@
+use std::hash::Hash;
+
+use crate::{
+    grid::{Canvas, Paint},
+    Color, Config, DirectedGraph, DisplayOptions, Layout,
+};
+
+/// How the Glyph of a [`Cell`] is emphasized, besides its Color
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CellStyle {
+    /// The Glyph is drawn as usual
+    #[default]
+    Plain,
+    /// The Glyph is highlighted, like a Node highlighted through the [`DisplayOptions`] or the
+    /// Edges of their Path, in which case the Color of the Cell is the
+    /// [Highlight-Color](DisplayOptions::highlight_color)
+    Highlighted,
+    /// The Glyph is dimmed, like everything that is not part of the Path of the
+    /// [`DisplayOptions`] or the Rules between the Levels
+    Dimmed,
+}
+
+/// A single Glyph of a [`RenderBuffer`]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cell<'g, ID> {
+    /// The Character displayed in the Cell
+    pub glyph: char,
+    /// The Color of the Cell, like the Color of an Edge or the Highlight of a Node, which is `None`
+    /// if the Cell is not colored
+    pub color: Option<Color>,
+    /// How the Cell is emphasized
+    pub style: CellStyle,
+    /// The Node whose Label the Cell is part of
+    pub node: Option<&'g ID>,
+    /// The URL the Label of the Node links to, see [`Config::hyperlinks`]
+    pub link: Option<String>,
+}
+
+impl<ID> Clone for Cell<'_, ID> {
+    fn clone(&self) -> Self {
+        Self {
+            glyph: self.glyph,
+            color: self.color.clone(),
+            style: self.style,
+            node: self.node,
+            link: self.link.clone(),
+        }
+    }
+}
+
+/// The Output of a Graph as Rows of [`Cell`]s instead of Text, which allows drawing the Graph in
+/// a TUI without having to parse the Escape-Sequences of the Output.
+///
+/// The Rows are the Lines of the Output, so the Coordinates are the same as the ones of
+/// [`Layout::node_positions`] and the Lines of the Edges can be looked up using
+/// [`Layout::edge_cells`]. Every Row only contains the Cells up to its last Glyph, so the Rows can
+/// have different Lengths.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3).default_colors();
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+///
+/// let buffer = termgraph::render_buffer(&graph, &config);
+/// let label: String = buffer
+///     .rows()
+///     .iter()
+///     .flatten()
+///     .filter(|cell| cell.node == Some(&1))
+///     .map(|cell| cell.glyph)
+///     .collect();
+/// assert_eq!("(1)", label);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderBuffer<'g, ID> {
+    rows: Vec<Vec<Cell<'g, ID>>>,
+}
+
+impl<'g, ID> RenderBuffer<'g, ID> {
+    /// All the Rows of the Output, from top to bottom
+    pub fn rows(&self) -> &[Vec<Cell<'g, ID>>] {
+        &self.rows
+    }
+
+    /// The Cell at the given Column and Row, if the Row extends that far
+    pub fn get(&self, column: usize, row: usize) -> Option<&Cell<'g, ID>> {
+        self.rows.get(row)?.get(column)
+    }
+
+    /// The number of Columns of the longest Row
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// The number of Rows
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+impl<'g, ID, T> Layout<'g, ID, T>
+where
+    ID: Hash + Eq,
+{
+    /// Renders the Layout into a [`RenderBuffer`] instead of writing its Output.
+    ///
+    /// The Cells are created from the Layout directly, so the Colors are the ones the Config would
+    /// display regardless of its [Color-Markup](Config::color_markup), and Escape-Sequences in the
+    /// Labels are kept as Glyphs.
+    pub fn render_buffer(&self, config: &Config<ID, T>) -> RenderBuffer<'g, ID> {
+        self.render_buffer_with(config, &DisplayOptions::new())
+    }
+
+    /// Renders the Layout into a [`RenderBuffer`], like [`Layout::render_buffer`], but with the
+    /// Colors and Highlights of the Options applied on top of the Config
+    pub fn render_buffer_with(
+        &self,
+        config: &Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+    ) -> RenderBuffer<'g, ID> {
+        let mut canvas = BufferCanvas {
+            rows: Vec::new(),
+            current: Vec::new(),
+        };
+        self.draw(config, options, &mut canvas);
+        if !canvas.current.is_empty() {
+            canvas.rows.push(canvas.current);
+        }
+        RenderBuffer { rows: canvas.rows }
+    }
+}
+
+/// Renders the Graph into a [`RenderBuffer`], see [`Layout::render_buffer`]
+pub fn render_buffer<'g, ID, T>(
+    graph: &'g DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+) -> RenderBuffer<'g, ID>
+where
+    ID: Hash + Eq,
+{
+    Layout::compute(graph, config).render_buffer(config)
+}
+
+/// Collects the drawn Glyphs into the Rows of Cells
+struct BufferCanvas<'g, ID> {
+    rows: Vec<Vec<Cell<'g, ID>>>,
+    current: Vec<Cell<'g, ID>>,
+}
+
+impl<'g, ID> Canvas<'g, ID> for BufferCanvas<'g, ID> {
+    fn draw(
+        &mut self,
+        text: &str,
+        paint: Option<&Paint>,
+        node: Option<&'g ID>,
+        link: Option<&str>,
+    ) {
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                self.newline();
+            }
+            self.current.extend(line.chars().map(|glyph| Cell {
+                glyph,
+                color: paint.and_then(|paint| paint.color.clone()),
+                style: paint.map_or(CellStyle::Plain, |paint| paint.style),
+                node,
+                link: link.map(str::to_string),
+            }));
+        }
+    }
+
+    fn newline(&mut self) {
+        self.rows.push(std::mem::take(&mut self.current));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IDFormatter, ValueFormatter};
+
+    #[test]
+    fn cells() {
+        let config = Config::new(IDFormatter::new(), 3)
+            .default_colors()
+            .color_markup(|_| "<color>".to_string(), "</color>")
+            .hyperlinks(|id: &usize, _| Some(format!("https://example.com/{id}")));
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, ()), (1, ())]);
+        graph.add_edges([(0, 1)]);
+
+        let layout = Layout::compute(&graph, &config);
+        let buffer = layout.render_buffer(&config);
+
+        let text: Vec<String> = buffer
+            .rows()
+            .iter()
+            .map(|row| row.iter().map(|c| c.glyph).collect())
+            .collect();
+        let mut plain = Vec::new();
+        layout.fdisplay_with(&config, &DisplayOptions::new().colors(false), &mut plain);
+        let plain = crate::formatter::strip_ansi(&String::from_utf8(plain).unwrap());
+        assert_eq!(plain.lines().collect::<Vec<_>>(), text);
+
+        let positions = layout.node_positions(&config);
+        let first = positions[&0];
+        assert_eq!(
+            Some(&0),
+            buffer.get(first.column, first.row).and_then(|c| c.node)
+        );
+        let edge = layout.edge_cells(&config)[0];
+        let cell = buffer.get(edge.column, edge.row).unwrap();
+        assert_eq!(None, cell.node);
+        assert!(cell.color.is_some());
+        assert_eq!(CellStyle::Plain, cell.style);
+
+        let label = buffer.get(first.column, first.row).unwrap();
+        assert_eq!(Some("https://example.com/0"), label.link.as_deref());
+        assert_eq!(None, label.color);
+    }
+
+    #[test]
+    fn styles() {
+        let config = Config::new(ValueFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "\x1b[31mred"), (1, "plain")]);
+        graph.add_edges([(0, 1)]);
+
+        let layout = Layout::compute(&graph, &config);
+        let options = DisplayOptions::new().highlight([1]);
+        let buffer = layout.render_buffer_with(&config, &options);
+        let node_cells = |id| {
+            buffer
+                .rows()
+                .iter()
+                .flatten()
+                .filter(move |c| c.node == Some(id))
+        };
+
+        // The Escape-Sequence within the Label is only made up of Glyphs
+        assert!(node_cells(&0).any(|c| c.glyph == '\x1b'));
+        assert!(node_cells(&0).all(|c| c.color.is_none() && c.style == CellStyle::Plain));
+
+        assert!(node_cells(&1)
+            .all(|c| c.style == CellStyle::Highlighted && c.color == Some(Color::Custom(7))));
+    }
+}
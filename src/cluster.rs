@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+/// Assigns a Node to the Cluster with the returned Name, or to no Cluster at all
+type ClusterKey<ID, T> = Box<dyn Fn(&ID, &T) -> Option<String>>;
+
+/// Groups the Nodes of a Graph into named Clusters, which can be collapsed into a single summary
+/// Node each, like `[parser: 14 nodes]`.
+///
+/// The Edges from and to the Nodes of a collapsed Cluster are combined into Edges from and to the
+/// summary Node, while Edges within the Cluster are not displayed.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Clusters, Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "main"), (1, "parser::expr"), (2, "parser::stmt"), (3, "codegen")]);
+/// graph.add_edges([(0, 1), (0, 2), (1, 2), (2, 3)]);
+///
+/// let mut clusters = Clusters::new(|_, path: &&str| {
+///     path.split_once("::").map(|(module, _)| module.to_string())
+/// });
+/// clusters.collapse("parser");
+///
+/// termgraph::display_clustered(&graph, &config, &clusters);
+/// ```
+pub struct Clusters<ID, T> {
+    key: ClusterKey<ID, T>,
+    collapsed: HashSet<String>,
+}
+
+impl<ID, T> Clusters<ID, T> {
+    /// Creates the Clusters, where the Cluster of every Node is determined using the given `key`.
+    /// Initially all the Clusters are expanded.
+    pub fn new<F>(key: F) -> Self
+    where
+        F: Fn(&ID, &T) -> Option<String> + 'static,
+    {
+        Self {
+            key: Box::new(key),
+            collapsed: HashSet::new(),
+        }
+    }
+
+    /// Returns the Name of the Cluster the given Node belongs to
+    pub fn cluster_of(&self, id: &ID, value: &T) -> Option<String> {
+        (self.key)(id, value)
+    }
+
+    /// Displays the Cluster with the given Name as a single summary Node
+    pub fn collapse<N>(&mut self, name: N)
+    where
+        N: Into<String>,
+    {
+        self.collapsed.insert(name.into());
+    }
+
+    /// Displays all the Nodes of the Cluster with the given Name again
+    pub fn expand(&mut self, name: &str) {
+        self.collapsed.remove(name);
+    }
+
+    /// Collapses the Cluster if it is expanded and expands it otherwise
+    pub fn toggle<N>(&mut self, name: N)
+    where
+        N: Into<String>,
+    {
+        let name = name.into();
+        if !self.collapsed.remove(&name) {
+            self.collapsed.insert(name);
+        }
+    }
+
+    /// Whether the Cluster with the given Name is displayed as a single summary Node
+    pub fn is_collapsed(&self, name: &str) -> bool {
+        self.collapsed.contains(name)
+    }
+}
@@ -1,4 +1,15 @@
-use crate::NodeFormat;
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt::Display,
+    io::IsTerminal,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    formatter::{ContextNodeFormat, Formatter, LabelIssue},
+    NodeFormat,
+};
 
 /// The Colors that can be displayed in the console
 #[allow(missing_docs)]
@@ -33,12 +44,113 @@ impl From<Color> for usize {
     }
 }
 
+/// How Edges, that need to be reversed to break the Cycles in a Graph, are displayed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BackEdgeStyle {
+    /// The Edges are routed back up to their original Target
+    #[default]
+    Reverse,
+    /// The Edges are drawn downwards like every other Edge, but they end in an upward pointing
+    /// Arrow at their original Source, next to the Arrows of the other Edges ending there
+    Marked,
+    /// The Edges are not drawn, but instead listed below the Graph
+    Omit,
+}
+
+/// An Adjustment the Layout made on its own, which is reported to the Hook set using
+/// [`Config::on_warning`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum LayoutWarning<'a, ID> {
+    /// The Label of the Node was cut off, as it was longer than the
+    /// [max label width](Config::max_label_width)
+    TruncatedLabel {
+        /// The Node whose Label was cut off
+        node: &'a ID,
+        /// The Width the Label was cut off at
+        width: usize,
+    },
+    /// The Edge passing through a Level did not fit into the
+    /// [available Width](Config::max_glyphs_per_layer) and was moved to its last Column
+    ClampedEdge {
+        /// The Source of the Edge
+        src: &'a ID,
+        /// The Target of the Edge
+        target: &'a ID,
+        /// The Column the Edge would have been placed at
+        x: usize,
+        /// The Column the Edge was moved to
+        max_x: usize,
+    },
+    /// The Edge from the Node to itself was removed, as it can not be displayed
+    DroppedSelfLoop {
+        /// The Node with the Edge to itself
+        node: &'a ID,
+    },
+    /// The Edge was reversed or omitted, depending on the [`BackEdgeStyle`], to break a Cycle
+    BrokenCycle {
+        /// The Source of the Edge
+        src: &'a ID,
+        /// The Target of the Edge
+        target: &'a ID,
+    },
+    /// The Label of the Edge was not drawn, as there was no free Spot next to its Line
+    DroppedEdgeLabel {
+        /// The Source of the Edge, in the Orientation it is drawn in
+        src: &'a ID,
+        /// The Target of the Edge, in the Orientation it is drawn in
+        target: &'a ID,
+    },
+    /// The Label of the Node had a Problem, that was fixed by the [`Validate`](crate::Validate)
+    /// Formatter
+    InvalidLabel {
+        /// The Node with the invalid Label
+        node: &'a ID,
+        /// The Problem that was found
+        issue: LabelIssue,
+    },
+}
+
+/// How the Colors of the Palette are assigned to the Edges in the Graph
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorBy {
+    /// All Edges leaving the same Node share a Color
+    #[default]
+    SourceNode,
+    /// Every Edge gets its own Color
+    Edge,
+    /// All Edges entering the same Node share a Color
+    TargetNode,
+    /// All Edges between the same two Levels share a Color
+    Level,
+}
+
+/// How the Nodes of a Level are distributed across the available width
+///
+/// The available width is the width of the widest Level, limited by the
+/// [max glyphs per layer](Config::max_glyphs_per_layer).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Justify {
+    /// The Nodes are packed to the left
+    #[default]
+    Left,
+    /// The Nodes are packed in the center
+    Center,
+    /// The first Node is placed on the left, the last one on the right and the remaining space is
+    /// distributed evenly between the Nodes. A single Node is placed in the center.
+    SpaceBetween,
+}
+
 /// This builder is used to construct a [`LineGlyphs`] instance
 pub struct LineGlyphBuilder {
     vertical: char,
     horizontal: char,
     crossing: char,
     arrow_down: char,
+    arrow_up: char,
+    arrow_both: char,
+    arrow_left: char,
+    arrow_right: char,
+    passthrough: char,
 }
 
 impl LineGlyphBuilder {
@@ -50,6 +162,107 @@ impl LineGlyphBuilder {
             horizontal: '-',
             crossing: '+',
             arrow_down: 'V',
+            arrow_up: '^',
+            arrow_both: 'X',
+            arrow_left: '<',
+            arrow_right: '>',
+            passthrough: '|',
+        }
+    }
+
+    /// Creates the base Builder using the Unicode Box-Drawing symbols
+    #[must_use]
+    pub const fn unicode() -> Self {
+        Self {
+            vertical: '│',
+            horizontal: '─',
+            crossing: '┼',
+            arrow_down: '▼',
+            arrow_up: '▲',
+            arrow_both: '◆',
+            arrow_left: '◀',
+            arrow_right: '▶',
+            passthrough: '│',
+        }
+    }
+
+    /// Creates the base Builder using the default ASCII symbols for the Lines, but Unicode
+    /// symbols for the Arrows pointing in every direction (`▼ ▲ ◀ ▶`)
+    #[must_use]
+    pub const fn unicode_arrows() -> Self {
+        Self {
+            arrow_down: '▼',
+            arrow_up: '▲',
+            arrow_both: '◆',
+            arrow_left: '◀',
+            arrow_right: '▶',
+            ..Self::ascii()
+        }
+    }
+
+    /// Creates the base Builder using the heavy Unicode Box-Drawing symbols
+    #[must_use]
+    pub const fn heavy() -> Self {
+        Self {
+            vertical: '┃',
+            horizontal: '━',
+            crossing: '╋',
+            arrow_down: '▼',
+            arrow_up: '▲',
+            arrow_both: '◆',
+            arrow_left: '◀',
+            arrow_right: '▶',
+            passthrough: '┃',
+        }
+    }
+
+    /// Creates the base Builder using the double Unicode Box-Drawing symbols
+    #[must_use]
+    pub const fn double() -> Self {
+        Self {
+            vertical: '║',
+            horizontal: '═',
+            crossing: '╬',
+            arrow_down: '▼',
+            arrow_up: '▲',
+            arrow_both: '◆',
+            arrow_left: '◀',
+            arrow_right: '▶',
+            passthrough: '║',
+        }
+    }
+
+    /// Creates the base Builder using dashed Unicode Box-Drawing symbols, which are used for
+    /// [`LineStyle::Dashed`] by default
+    #[must_use]
+    pub const fn dashed() -> Self {
+        Self {
+            vertical: '┆',
+            horizontal: '┄',
+            crossing: '┼',
+            arrow_down: '▼',
+            arrow_up: '▲',
+            arrow_both: '◆',
+            arrow_left: '◀',
+            arrow_right: '▶',
+            passthrough: '┆',
+        }
+    }
+
+    /// Creates the base Builder using dotted Unicode Box-Drawing symbols, which are used for
+    /// [`LineStyle::Dotted`] by default
+    #[must_use]
+    pub const fn dotted() -> Self {
+        Self {
+            vertical: '┊',
+            horizontal: '┈',
+            crossing: '┼',
+            arrow_down: '▼',
+            arrow_up: '▲',
+            arrow_both: '◆',
+            arrow_left: '◀',
+            arrow_right: '▶',
+            passthrough: '┊',
         }
     }
 
@@ -78,6 +291,47 @@ impl LineGlyphBuilder {
         self
     }
 
+    /// Set the Glyph for arrow heads pointing up, used by [`BackEdgeStyle::Marked`]
+    #[must_use]
+    pub const fn arrow_up(mut self, glyph: char) -> Self {
+        self.arrow_up = glyph;
+        self
+    }
+
+    /// Set the Glyph for Edges in both directions ending at the same Column of a Node, where neither
+    /// arrow head alone would show both of them. A marked Back-Edge only shares the Column with
+    /// another Edge, if the Node is too narrow to give it a Column of its own.
+    #[must_use]
+    pub const fn arrow_both(mut self, glyph: char) -> Self {
+        self.arrow_both = glyph;
+        self
+    }
+
+    /// Set the Glyph for arrow heads pointing to the left, used for Edges ending on the side of a
+    /// Node
+    #[must_use]
+    pub const fn arrow_left(mut self, glyph: char) -> Self {
+        self.arrow_left = glyph;
+        self
+    }
+
+    /// Set the Glyph for arrow heads pointing to the right, used for Edges ending on the side of a
+    /// Node
+    #[must_use]
+    pub const fn arrow_right(mut self, glyph: char) -> Self {
+        self.arrow_right = glyph;
+        self
+    }
+
+    /// Set the Glyph for Edges passing through a Level, which is the same as the vertical Glyph
+    /// by default. A distinct Glyph, like `┆`, makes it easier to tell Edges that only pass by a
+    /// Level apart from the Edges leaving its Nodes.
+    #[must_use]
+    pub const fn passthrough(mut self, glyph: char) -> Self {
+        self.passthrough = glyph;
+        self
+    }
+
     /// Should be called, once the configuration is done to obtain the final [`LineGlyphs`] instance
     pub const fn finish(self) -> LineGlyphs {
         LineGlyphs {
@@ -85,6 +339,11 @@ impl LineGlyphBuilder {
             horizontal: self.horizontal,
             crossing: self.crossing,
             arrow_down: self.arrow_down,
+            arrow_up: self.arrow_up,
+            arrow_both: self.arrow_both,
+            arrow_left: self.arrow_left,
+            arrow_right: self.arrow_right,
+            passthrough: self.passthrough,
         }
     }
 }
@@ -92,11 +351,18 @@ impl LineGlyphBuilder {
 /// Describes the Glyphs that should be used to display the lines in the Graph.
 ///
 /// This can't be constructed directly, but instead is constructed using [`LineGlyphBuilder`]
+#[derive(Debug, Clone, Copy)]
 pub struct LineGlyphs {
     pub(crate) vertical: char,
     pub(crate) horizontal: char,
     pub(crate) crossing: char,
     pub(crate) arrow_down: char,
+    pub(crate) arrow_up: char,
+    pub(crate) arrow_both: char,
+    pub(crate) arrow_left: char,
+    pub(crate) arrow_right: char,
+    /// Used for Edges passing through a Level
+    pub(crate) passthrough: char,
 }
 
 impl From<LineGlyphBuilder> for LineGlyphs {
@@ -105,6 +371,88 @@ impl From<LineGlyphBuilder> for LineGlyphs {
     }
 }
 
+/// The Style of the Line of a single Edge, see [`Config::edge_style`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    /// The regular [Line Glyphs](Config::line_glyphs)
+    #[default]
+    Solid,
+    /// The [dashed](LineGlyphBuilder::dashed) Glyphs by default
+    Dashed,
+    /// The [dotted](LineGlyphBuilder::dotted) Glyphs by default
+    Dotted,
+}
+
+/// Maps the Weights of the Edges to the Glyphs and Colors used to draw them, see
+/// [`Config::edge_weights`]
+///
+/// Every Tier applies to the Edges with at least its minimum Weight, up to the minimum Weight of
+/// the next Tier. Edges below the lowest Tier are drawn using the regular Glyphs and Colors.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Color, LineGlyphBuilder, WeightTiers};
+///
+/// let tiers = WeightTiers::new()
+///     .tier(10, LineGlyphBuilder::heavy())
+///     .colored_tier(100, LineGlyphBuilder::double(), Color::Red);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WeightTiers {
+    /// Sorted by their minimum Weight
+    pub(crate) tiers: Vec<(u64, LineGlyphs, Option<Color>)>,
+}
+
+impl WeightTiers {
+    /// Creates an empty Mapping, which draws all Edges the same
+    #[must_use]
+    pub fn new() -> Self {
+        Self { tiers: Vec::new() }
+    }
+
+    /// The Edges with a Weight of at least `heavy` are drawn using the
+    /// [heavy](LineGlyphBuilder::heavy) and the ones with at least `double` using the
+    /// [double](LineGlyphBuilder::double) Glyphs
+    #[must_use]
+    pub fn thickness(heavy: u64, double: u64) -> Self {
+        Self::new()
+            .tier(heavy, LineGlyphBuilder::heavy())
+            .tier(double, LineGlyphBuilder::double())
+    }
+
+    /// Draws the Edges with at least the given Weight using the Glyphs
+    #[must_use]
+    pub fn tier<G>(self, min_weight: u64, glyphs: G) -> Self
+    where
+        G: Into<LineGlyphs>,
+    {
+        self.insert(min_weight, glyphs.into(), None)
+    }
+
+    /// Draws the Edges with at least the given Weight using the Glyphs and Color
+    #[must_use]
+    pub fn colored_tier<G>(self, min_weight: u64, glyphs: G, color: Color) -> Self
+    where
+        G: Into<LineGlyphs>,
+    {
+        self.insert(min_weight, glyphs.into(), Some(color))
+    }
+
+    fn insert(mut self, min_weight: u64, glyphs: LineGlyphs, color: Option<Color>) -> Self {
+        self.tiers.retain(|(w, _, _)| *w != min_weight);
+        let index = self.tiers.partition_point(|(w, _, _)| *w < min_weight);
+        self.tiers.insert(index, (min_weight, glyphs, color));
+        self
+    }
+
+    /// Returns the Index of the Tier for the given Weight
+    pub(crate) fn find(&self, weight: u64) -> Option<usize> {
+        self.tiers
+            .partition_point(|(w, _, _)| *w <= weight)
+            .checked_sub(1)
+    }
+}
+
 /// The Configuration to use for displaying a Graph
 ///
 /// # Example
@@ -114,12 +462,116 @@ impl From<LineGlyphBuilder> for LineGlyphs {
 /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3).default_colors();
 /// ```
 pub struct Config<ID, T> {
-    pub(crate) formatter: Box<dyn NodeFormat<ID, T>>,
+    pub(crate) formatter: Formatter<ID, T>,
+    pub(crate) level_hook: Option<LevelHook<ID, T>>,
+    pub(crate) swimlanes: Option<LaneKey<ID, T>>,
+    pub(crate) rank: Option<NodeRank<ID, T>>,
+    pub(crate) hyperlinks: Option<NodeLink<ID, T>>,
     pub(crate) color_palette: Option<Vec<Color>>,
+    pub(crate) color_strategy: ColorBy,
+    pub(crate) color_start: ColorStart,
+    pub(crate) color_end: String,
     pub(crate) max_per_layer: usize,
     max_glyphs_per_layer: usize,
+    /// Limits the Width further while a Graph is laid out in only a part of the Width, see
+    /// [`Config::with_glyph_width`]
+    glyph_width_cap: Cell<Option<usize>>,
     pub(crate) vertical_edge_spacing: usize,
+    pub(crate) node_padding: usize,
+    pub(crate) edge_gap: usize,
+    pub(crate) layer_spacing: usize,
+    pub(crate) justify: Justify,
+    pub(crate) line_prefix: String,
+    pub(crate) gutter: Option<Box<dyn Fn(usize) -> String>>,
+    pub(crate) level_separator: Option<char>,
+    pub(crate) ruler: bool,
     pub(crate) line_glyphs: LineGlyphs,
+    pub(crate) back_edge_style: BackEdgeStyle,
+    pub(crate) edge_weight: Option<EdgeWeight<ID>>,
+    pub(crate) reversal_cost: Option<EdgeWeight<ID>>,
+    pub(crate) layout_weight: Option<EdgeWeight<ID>>,
+    pub(crate) edge_style: Option<EdgeStyle<ID>>,
+    dashed_glyphs: LineGlyphs,
+    pub(crate) patterns: Vec<LineGlyphs>,
+    dotted_glyphs: LineGlyphs,
+    pub(crate) weight_tiers: WeightTiers,
+    pub(crate) debug_layout: bool,
+    pub(crate) max_label_width: Option<usize>,
+    pub(crate) wrap_labels: Option<usize>,
+    pub(crate) max_cells: Option<usize>,
+    pub(crate) max_levels: Option<usize>,
+    pub(crate) chain_length: Option<usize>,
+    pub(crate) max_render_time: Option<Duration>,
+    pub(crate) layout_attempts: usize,
+    pub(crate) layout_budget: Option<Duration>,
+    pub(crate) layout_seed: u64,
+    pub(crate) refine_ordering: bool,
+    pub(crate) shorten_labels: bool,
+    pub(crate) id_label: Option<IdLabel<ID>>,
+    pub(crate) on_warning: Option<WarningHook<ID>>,
+    pub(crate) sibling_order: Option<SiblingOrder<ID>>,
+}
+
+/// Adjusts the Label of a Node based on the Level it was placed on
+pub(crate) type LevelHook<ID, T> = Box<dyn Fn(usize, &ID, &T, String) -> String>;
+
+/// Returns the Name of the Swimlane a Node belongs to
+pub(crate) type LaneKey<ID, T> = Box<dyn Fn(&ID, &T) -> String>;
+
+/// Returns the Rank of a Node, which determines its Level
+pub(crate) type NodeRank<ID, T> = Box<RankFn<'static, ID, T>>;
+
+/// Returns the Rank of a Node, while borrowing from its surroundings
+pub(crate) type RankFn<'a, ID, T> = dyn Fn(&ID, &T) -> usize + 'a;
+
+/// Orders the Nodes on the same Level
+pub(crate) type SiblingOrder<ID> = Box<dyn Fn(&ID, &ID) -> Ordering>;
+
+/// Orders the Nodes, while borrowing from its surroundings
+pub(crate) type OrderFn<'a, ID> = dyn Fn(&ID, &ID) -> Ordering + 'a;
+
+/// Labels a Node only by its ID, which is only available for IDs that implement `Display`
+pub(crate) type IdLabel<ID> = Box<dyn Fn(&ID) -> String>;
+
+/// Receives the Adjustments made by the Layout
+pub(crate) type WarningHook<ID> = Box<WarningFn<ID>>;
+
+/// Receives the Adjustments made by the Layout, see [`LayoutWarning`]
+pub(crate) type WarningFn<ID> = dyn Fn(LayoutWarning<'_, ID>);
+
+/// Returns the URL a Node links to, if it links to anything
+pub(crate) type NodeLink<ID, T> = Box<dyn Fn(&ID, &T) -> Option<String>>;
+
+/// Returns the Weight of the Edge between the two Nodes
+pub(crate) type EdgeWeight<ID> = Box<WeightFn<ID>>;
+
+/// Returns the Weight of the Edge between the two Nodes
+pub(crate) type WeightFn<ID> = dyn Fn(&ID, &ID) -> u64;
+
+/// Returns the Style of the Edge between the two Nodes
+pub(crate) type EdgeStyle<ID> = Box<dyn Fn(&ID, &ID) -> LineStyle>;
+
+/// Renders the Sequence, that starts a Span of the given Color
+pub(crate) type ColorStart = Box<dyn Fn(&Color) -> String>;
+
+/// The ANSI Escape-Sequence, that ends a colored Span
+pub(crate) const ANSI_RESET: &str = "\x1b[0m";
+
+/// The ANSI Escape-Sequence, that starts a Span of the given Color
+pub(crate) fn ansi_color_start(color: &Color) -> String {
+    format!("\x1b[{}m", usize::from(color.clone()))
+}
+
+/// The Colors used by [`Config::default_colors`]
+pub(crate) fn default_palette() -> Vec<Color> {
+    vec![
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ]
 }
 
 impl<ID, T> Config<ID, T> {
@@ -128,19 +580,196 @@ impl<ID, T> Config<ID, T> {
     /// # Default Values
     /// * Colors: disabled
     /// * Vertical-Edge-Spacing: 1
+    /// * Node-Padding: 2
+    /// * Edge-Gap: 0
+    /// * Layer-Spacing: 1
     #[must_use]
     pub fn new<F>(nfmt: F, max_per_layer: usize) -> Self
     where
         F: NodeFormat<ID, T> + 'static,
     {
         Self {
-            formatter: Box::new(nfmt),
+            formatter: Formatter::Plain(Box::new(nfmt)),
+            level_hook: None,
+            swimlanes: None,
+            rank: None,
+            hyperlinks: None,
             color_palette: None,
+            color_strategy: ColorBy::SourceNode,
+            color_start: Box::new(ansi_color_start),
+            color_end: ANSI_RESET.to_string(),
             max_per_layer,
             max_glyphs_per_layer: usize::MAX,
+            glyph_width_cap: Cell::new(None),
             vertical_edge_spacing: 1,
+            node_padding: 2,
+            edge_gap: 0,
+            layer_spacing: 1,
+            justify: Justify::Left,
+            line_prefix: String::new(),
+            gutter: None,
+            level_separator: None,
+            ruler: false,
             line_glyphs: LineGlyphBuilder::ascii().finish(),
+            back_edge_style: BackEdgeStyle::Reverse,
+            edge_weight: None,
+            layout_weight: None,
+            reversal_cost: None,
+            edge_style: None,
+            dashed_glyphs: LineGlyphBuilder::dashed().finish(),
+            patterns: Vec::new(),
+            dotted_glyphs: LineGlyphBuilder::dotted().finish(),
+            weight_tiers: WeightTiers::new(),
+            debug_layout: false,
+            max_label_width: None,
+            wrap_labels: None,
+            max_cells: None,
+            max_levels: None,
+            chain_length: None,
+            max_render_time: None,
+            layout_attempts: 1,
+            layout_budget: None,
+            layout_seed: 0,
+            refine_ordering: false,
+            shorten_labels: false,
+            id_label: None,
+            on_warning: None,
+            sibling_order: None,
+        }
+    }
+
+    /// Creates a new Config with the given Formatter, where the limits for each Layer are chosen
+    /// based on the width of the Terminal.
+    ///
+    /// The width is queried from the Terminal connected to Stdout on Unix, otherwise the `COLUMNS`
+    /// environment variable is used, falling back to 80 columns if it is not set either. If the
+    /// output is not a Terminal, a width of 120 columns is assumed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> = Config::for_terminal(IDFormatter::new());
+    /// ```
+    #[must_use]
+    pub fn for_terminal<F>(nfmt: F) -> Self
+    where
+        F: NodeFormat<ID, T> + 'static,
+    {
+        let width = terminal_width();
+
+        // Every Node needs at least a couple of glyphs for its name and the spacing around it
+        Self::new(nfmt, (width / 8).max(1)).max_glyphs_per_layer(width)
+    }
+
+    /// Creates a new Config, that uses as little space as possible
+    ///
+    /// # Values
+    /// * Colors: disabled
+    /// * Node-Padding: 1
+    /// * Layer-Spacing: 0
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> = Config::compact(IDFormatter::new(), 5).layer_spacing(1);
+    /// ```
+    #[must_use]
+    pub fn compact<F>(nfmt: F, max_per_layer: usize) -> Self
+    where
+        F: NodeFormat<ID, T> + 'static,
+    {
+        Self::new(nfmt, max_per_layer)
+            .node_padding(1)
+            .layer_spacing(0)
+    }
+
+    /// Creates a new Config, that spreads out the Graph to make it easier to follow the Edges
+    ///
+    /// # Values
+    /// * Colors: default
+    /// * Vertical-Edge-Spacing: 2
+    /// * Node-Padding: 4
+    /// * Layer-Spacing: 2
+    #[must_use]
+    pub fn spacious<F>(nfmt: F, max_per_layer: usize) -> Self
+    where
+        F: NodeFormat<ID, T> + 'static,
+    {
+        Self::new(nfmt, max_per_layer)
+            .vertical_edge_spacing(2)
+            .node_padding(4)
+            .layer_spacing(2)
+            .default_colors()
+    }
+
+    /// Creates a new Config, that draws the Edges using the Unicode Box-Drawing symbols
+    ///
+    /// # Values
+    /// * Colors: default
+    /// * Line-Glyphs: [`LineGlyphBuilder::unicode`]
+    #[must_use]
+    pub fn unicode<F>(nfmt: F, max_per_layer: usize) -> Self
+    where
+        F: NodeFormat<ID, T> + 'static,
+    {
+        Self::new(nfmt, max_per_layer)
+            .line_glyphs(LineGlyphBuilder::unicode())
+            .default_colors()
+    }
+
+    /// Creates a new Config, like [`Config::new`], but allows for parts of it to be overridden
+    /// using Environment-Variables.
+    ///
+    /// # Variables
+    /// * `TERMGRAPH_COLORS`: Enables (`1`, `true`, `on`, `always`) or disables (`0`, `false`,
+    ///   `off`, `never`) the default Colors
+    /// * `TERMGRAPH_GLYPHS`: Selects the Line-Glyphs (`ascii` or `unicode`)
+    /// * `TERMGRAPH_MAX_WIDTH`: Sets the maximum number of Glyphs per Layer, which has to be at
+    ///   least 1
+    ///
+    /// Variables that are not set or contain an unknown value are ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> = Config::from_env(IDFormatter::new(), 3);
+    /// ```
+    #[must_use]
+    pub fn from_env<F>(nfmt: F, max_per_layer: usize) -> Self
+    where
+        F: NodeFormat<ID, T> + 'static,
+    {
+        Self::new(nfmt, max_per_layer).env_overrides(|name| std::env::var(name).ok())
+    }
+
+    /// Applies the overrides, using the given function to look up the Variables
+    fn env_overrides<V>(mut self, var: V) -> Self
+    where
+        V: Fn(&str) -> Option<String>,
+    {
+        match var("TERMGRAPH_COLORS").as_deref().map(str::trim) {
+            Some("1" | "true" | "on" | "always") => self = self.default_colors(),
+            Some("0" | "false" | "off" | "never") => self = self.disable_colors(),
+            _ => {}
+        };
+
+        match var("TERMGRAPH_GLYPHS").as_deref().map(str::trim) {
+            Some("ascii") => self = self.line_glyphs(LineGlyphBuilder::ascii()),
+            Some("unicode") => self = self.line_glyphs(LineGlyphBuilder::unicode()),
+            _ => {}
+        };
+
+        if let Some(width) = var("TERMGRAPH_MAX_WIDTH")
+            .and_then(|w| w.trim().parse().ok())
+            .filter(|w| *w > 0)
+        {
+            self = self.max_glyphs_per_layer(width);
         }
+
+        self
     }
 
     /// Sets the vertical spacing between the horizontal connecting edges
@@ -164,70 +793,1033 @@ impl<ID, T> Config<ID, T> {
         self
     }
 
-    /// Sets the Formatter of this Configuration to the provided one
+    /// Sets the minimum number of blank columns between two adjacent Nodes on the same Layer
+    ///
+    /// # Example
+    /// ```ignore
+    /// Padding = 2     Padding = 4
+    ///
+    /// (a)  (b)        (a)    (b)
+    /// ```
     #[must_use]
-    pub fn formatter<F>(mut self, nfmt: F) -> Self
-    where
-        F: NodeFormat<ID, T> + 'static,
-    {
-        self.formatter = Box::new(nfmt);
+    pub fn node_padding(mut self, padding: usize) -> Self {
+        self.node_padding = padding;
         self
     }
 
-    /// Updates the Number of Nodes that should be placed on a single horizontal Layer at most
+    /// Sets the minimum number of blank columns between two parallel vertical Edges, so that
+    /// adjacent Edges don't visually fuse together in dense parts of the Graph.
+    ///
+    /// Where possible, Edges are moved to a different column of their Target-Node to keep the
+    /// distance to the other Edges.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Gap = 0         Gap = 1
+    ///
+    ///  (a)   (b)       (a)   (b)
+    ///   |     |         |     |
+    ///   |  +--+         | +---+
+    ///   |  |            | |
+    ///   V  V            V V
+    ///  (c)(d)          (c)(d)
+    /// ```
     #[must_use]
-    pub fn max_per_layer(mut self, count: usize) -> Self {
-        self.max_per_layer = count;
+    pub fn edge_gap(mut self, gap: usize) -> Self {
+        self.edge_gap = gap;
         self
     }
 
-    /// Sets the Color-Palette to the default Color-Palette
+    /// Sets the number of rows between a row of Nodes and the horizontal edges below it
+    ///
+    /// # Example
+    /// ```ignore
+    /// Spacing = 1     Spacing = 0     Spacing = 2
+    ///
+    ///  (a)             (a)             (a)
+    ///   |               ----|           |
+    ///   ----|               |           |
+    ///       |               V           ----|
+    ///       V              (b)              |
+    ///      (b)                              V
+    ///                                      (b)
+    /// ```
     #[must_use]
-    pub fn default_colors(mut self) -> Self {
-        self.color_palette = Some(vec![
-            Color::Red,
-            Color::Green,
-            Color::Yellow,
-            Color::Blue,
-            Color::Magenta,
-            Color::Cyan,
-        ]);
+    pub fn layer_spacing(mut self, n_spacing: usize) -> Self {
+        self.layer_spacing = n_spacing;
         self
     }
 
-    /// Sets the Color-Palette to the given List of Colors
+    /// Sets how the Nodes of every Level are distributed across the available width
+    ///
+    /// # Example
+    /// ```ignore
+    /// Left              Center            SpaceBetween
+    ///
+    /// (a) (b) (c)       (a) (b) (c)       (a) (b) (c)
+    ///  |                     |                 |
+    ///  V                     V                 V
+    /// (d)                   (d)               (d)
+    /// ```
     #[must_use]
-    pub fn custom_colors(mut self, colors: Vec<Color>) -> Self {
-        self.color_palette = Some(colors);
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
         self
     }
 
-    /// Disables the colors for the output
+    /// Sets a Prefix that is written at the start of every line of the output, except for the empty
+    /// line at the end of the Graph.
+    ///
+    /// This allows for the Graph to be indented or nested in other output, like a Markdown
+    /// blockquote.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3).line_prefix("    ");
+    /// ```
     #[must_use]
-    pub fn disable_colors(mut self) -> Self {
-        self.color_palette = None;
+    pub fn line_prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.line_prefix = prefix.into();
         self
     }
 
-    /// Sets the Glyphs to use for the Lines in the Graph
+    /// Shows a Gutter on the left side of the Graph, containing the index of every Level in front
+    /// of its Nodes
+    ///
+    /// # Example
+    /// ```ignore
+    /// 0 (a)
+    ///    |
+    ///    V
+    /// 1 (b)
+    /// ```
     #[must_use]
-    pub fn line_glyphs<L>(mut self, glyphs: L) -> Self
+    pub fn level_gutter(self) -> Self {
+        self.gutter(|level| level.to_string())
+    }
+
+    /// Shows a Gutter on the left side of the Graph, containing the Label returned by the given
+    /// function for every Level in front of its Nodes
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// let stages = ["parse", "lower", "optimize"];
+    /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3)
+    ///     .gutter(move |level| stages.get(level).copied().unwrap_or_default().to_string());
+    /// ```
+    #[must_use]
+    pub fn gutter<F>(mut self, label: F) -> Self
     where
-        L: Into<LineGlyphs>,
+        F: Fn(usize) -> String + 'static,
     {
-        self.line_glyphs = glyphs.into();
+        self.gutter = Some(Box::new(label));
         self
     }
 
-    /// Set the max glyph width per layer
+    /// Draws a horizontal Rule using the given Glyph between every two Levels, spanning the full
+    /// Width of the Output. The Rule is drawn in the first row below the Nodes of a Level and is
+    /// dimmed, if Colors are enabled.
+    ///
+    /// # Example
+    /// ```ignore
+    /// (a)   (b)
+    /// ┈|┈┈┈┈┈|┈
+    ///  +--+--+
+    ///     |
+    ///     V
+    ///    (c)
+    /// ```
     #[must_use]
-    pub fn max_glyphs_per_layer(mut self, max: usize) -> Self {
-        self.max_glyphs_per_layer = max;
+    pub fn level_separator(mut self, glyph: Option<char>) -> Self {
+        self.level_separator = glyph;
         self
     }
 
-    /// Get the number of Glyphs that can be placed
-    pub(crate) fn glyph_width(&self) -> usize {
-        self.max_glyphs_per_layer
+    /// Enables or disables the Ruler, which shows the Column of every Glyph above the Graph and
+    /// the Row of every Line in front of it. This makes it easier to report and pinpoint Problems
+    /// with the Layout, as every Position can be referred to by its Coordinates.
+    ///
+    /// # Example
+    /// ```ignore
+    ///   0
+    ///   0123456789
+    /// 0  (a)
+    /// 1   |
+    /// 2   +----+
+    /// 3   |    |
+    /// 4   V    V
+    /// 5  (b)  (c)
+    /// ```
+    #[must_use]
+    pub fn ruler(mut self, enabled: bool) -> Self {
+        self.ruler = enabled;
+        self
+    }
+
+    /// Sets the Formatter of this Configuration to the provided one
+    #[must_use]
+    pub fn formatter<F>(mut self, nfmt: F) -> Self
+    where
+        F: NodeFormat<ID, T> + 'static,
+    {
+        self.formatter = Formatter::Plain(Box::new(nfmt));
+        self
+    }
+
+    /// Sets the Formatter of this Configuration to one, that also receives the
+    /// [`NodeContext`](crate::NodeContext) of every Node
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, ContextNodeFormat, IDFormatter, NodeContext};
+    ///
+    /// struct LevelFormatter {}
+    ///
+    /// impl ContextNodeFormat<usize, usize> for LevelFormatter {
+    ///     fn format_node(&self, id: &usize, _: &usize, context: &NodeContext) -> String {
+    ///         format!("({id} @ {})", context.level)
+    ///     }
+    /// }
+    ///
+    /// let config: Config<usize, usize> =
+    ///     Config::new(IDFormatter::new(), 3).context_formatter(LevelFormatter {});
+    /// ```
+    #[must_use]
+    pub fn context_formatter<F>(mut self, nfmt: F) -> Self
+    where
+        F: ContextNodeFormat<ID, T> + 'static,
+    {
+        self.formatter = Formatter::Context(Box::new(nfmt));
+        self
+    }
+
+    /// Sets a Hook, that adjusts the formatted Label of every Node based on the Index of the Level
+    /// it was placed on, starting with 0 at the top.
+    ///
+    /// The Nodes are distributed across the Levels using their unadjusted Labels. The
+    /// [max label width](Config::max_label_width) and [label wrapping](Config::wrap_labels) are
+    /// applied to the adjusted Labels.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, ValueFormatter};
+    ///
+    /// // Only the Labels of the first two Levels are shown in full
+    /// let config: Config<usize, &str> = Config::new(ValueFormatter::new(), 3)
+    ///     .level_hook(|level, id, _, label| match level {
+    ///         0 | 1 => label,
+    ///         _ => format!("({id})"),
+    ///     });
+    /// ```
+    #[must_use]
+    pub fn level_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(usize, &ID, &T, String) -> String + 'static,
+    {
+        self.level_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Partitions the Nodes of every Level into vertical Swimlanes, based on the Name returned by
+    /// the given Key for every Node.
+    ///
+    /// The Lanes are sorted by their Names and every Lane spans the same Columns on all Levels.
+    /// The Name of every Lane is shown above it and neighbouring Lanes are separated by a line of
+    /// `:`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// // The Spans of a Trace, with the Service that handled them
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("GET /", "gateway"), ("verify", "auth"), ("query", "db")]);
+    /// graph.add_edges([("GET /", "verify"), ("GET /", "query")]);
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3).swimlanes(|_, service: &&str| service.to_string());
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn swimlanes<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&ID, &T) -> String + 'static,
+    {
+        self.swimlanes = Some(Box::new(key));
+        self
+    }
+
+    /// Places the Nodes on Levels based on the Rank returned for every Node, instead of computing
+    /// the Levels from the Edges.
+    ///
+    /// Nodes with the same Rank are placed on the same Level and the Levels are ordered by their
+    /// Rank, where Ranks without any Nodes are skipped. Neither the
+    /// [maximum number of Nodes per Layer](Config::max_per_layer) nor the
+    /// [maximum Glyphs per Layer](Config::max_glyphs_per_layer) are enforced, so a Level is as
+    /// wide as its Nodes need.
+    ///
+    /// Edges leading to a lower Rank are handled like the Edges closing a Cycle, see
+    /// [`BackEdgeStyle`]. Edges between Nodes of the same Rank can not be drawn and are only
+    /// listed below the Graph, when the Back-Edges are omitted.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// // The Stage of the Pipeline every Job runs in
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("fetch", 0), ("lint", 1), ("build", 1), ("deploy", 3)]);
+    /// graph.add_edges([("fetch", "build"), ("fetch", "deploy"), ("build", "deploy")]);
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3).rank(|_, stage: &usize| *stage);
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn rank<F>(mut self, rank: F) -> Self
+    where
+        F: Fn(&ID, &T) -> usize + 'static,
+    {
+        self.rank = Some(Box::new(rank));
+        self
+    }
+
+    /// Turns the Labels of the Nodes into Hyperlinks to the URL returned for them, using the OSC 8
+    /// escape sequence, which most modern Terminals support. Nodes without a URL are displayed as
+    /// usual.
+    ///
+    /// Terminals that do not support Hyperlinks simply ignore the escape sequence, but it still
+    /// ends up in the output when writing to a File.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// // The Functions of a Call-Graph, with the File they are defined in
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("main", "src/main.rs"), ("parse", "src/parse.rs")]);
+    /// graph.add_edges([("main", "parse")]);
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3)
+    ///     .hyperlinks(|_, file: &&str| Some(format!("file:///project/{file}")));
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn hyperlinks<F>(mut self, link: F) -> Self
+    where
+        F: Fn(&ID, &T) -> Option<String> + 'static,
+    {
+        self.hyperlinks = Some(Box::new(link));
+        self
+    }
+
+    /// Updates the Number of Nodes that should be placed on a single horizontal Layer at most
+    #[must_use]
+    pub fn max_per_layer(mut self, count: usize) -> Self {
+        self.max_per_layer = count;
+        self
+    }
+
+    /// Sets the Color-Palette to the default Color-Palette
+    #[must_use]
+    pub fn default_colors(mut self) -> Self {
+        self.color_palette = Some(default_palette());
+        self
+    }
+
+    /// Sets the Color-Palette to the given List of Colors
+    #[must_use]
+    pub fn custom_colors(mut self, colors: Vec<Color>) -> Self {
+        self.color_palette = Some(colors);
+        self
+    }
+
+    /// Sets how the Colors of the Palette are assigned to the Edges
+    ///
+    /// Where Edges of different Colors share a Line, like a horizontal Line fanning out to multiple
+    /// Targets, the shared part is drawn without a Color.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{ColorBy, Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3)
+    ///     .default_colors()
+    ///     .color_strategy(ColorBy::TargetNode);
+    /// ```
+    #[must_use]
+    pub fn color_strategy(mut self, strategy: ColorBy) -> Self {
+        self.color_strategy = strategy;
+        self
+    }
+
+    /// Sets the Markup that is written around every colored Span of the output, instead of the
+    /// ANSI Escape-Sequences.
+    ///
+    /// The `start` is called with the Color of the Span and its result is written in front of it,
+    /// the `end` is written after it. This also applies to the Highlighting of Nodes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3)
+    ///     .default_colors()
+    ///     .color_markup(
+    ///         |color| format!("<span class=\"c{}\">", usize::from(color.clone())),
+    ///         "</span>",
+    ///     );
+    /// ```
+    #[must_use]
+    pub fn color_markup<F, E>(mut self, start: F, end: E) -> Self
+    where
+        F: Fn(&Color) -> String + 'static,
+        E: Into<String>,
+    {
+        self.color_start = Box::new(start);
+        self.color_end = end.into();
+        self
+    }
+
+    /// Disables the colors for the output
+    #[must_use]
+    pub fn disable_colors(mut self) -> Self {
+        self.color_palette = None;
+        self
+    }
+
+    /// Sets the Glyphs to use for the Lines in the Graph
+    #[must_use]
+    pub fn line_glyphs<L>(mut self, glyphs: L) -> Self
+    where
+        L: Into<LineGlyphs>,
+    {
+        self.line_glyphs = glyphs.into();
+        self
+    }
+
+    /// Sets how the Edges, that need to be reversed to break the Cycles in the Graph, are displayed
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{BackEdgeStyle, Config, IDFormatter};
+    ///
+    /// let config: Config<usize, usize> =
+    ///     Config::new(IDFormatter::new(), 3).back_edge_style(BackEdgeStyle::Omit);
+    /// ```
+    #[must_use]
+    pub fn back_edge_style(mut self, style: BackEdgeStyle) -> Self {
+        self.back_edge_style = style;
+        self
+    }
+
+    /// Draws every Edge using the Glyphs of the [`LineStyle`] returned for it, which can be
+    /// configured using [`Config::line_style_glyphs`]. The Style takes precedence over the Glyphs
+    /// of the [Weight Tiers](Config::edge_weights).
+    ///
+    /// Like for the Weights, the Parts of the Lines that are shared by multiple Edges are drawn
+    /// using the regular Glyphs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter, LineStyle};
+    ///
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("app", ()), ("serde", ()), ("tracing", ())]);
+    /// graph.add_edges([("app", "serde"), ("app", "tracing")]);
+    ///
+    /// // Optional Dependencies are drawn dashed
+    /// let config = Config::new(IDFormatter::new(), 3).edge_style(|_, target| match *target {
+    ///     "tracing" => LineStyle::Dashed,
+    ///     _ => LineStyle::Solid,
+    /// });
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn edge_style<F>(mut self, style: F) -> Self
+    where
+        F: Fn(&ID, &ID) -> LineStyle + 'static,
+    {
+        self.edge_style = Some(Box::new(style));
+        self
+    }
+
+    /// Sets the Glyphs used for the Edges with the given [`LineStyle`], where the Glyphs of
+    /// [`LineStyle::Solid`] are the regular [Line Glyphs](Config::line_glyphs)
+    #[must_use]
+    pub fn line_style_glyphs<L>(mut self, style: LineStyle, glyphs: L) -> Self
+    where
+        L: Into<LineGlyphs>,
+    {
+        match style {
+            LineStyle::Solid => self.line_glyphs = glyphs.into(),
+            LineStyle::Dashed => self.dashed_glyphs = glyphs.into(),
+            LineStyle::Dotted => self.dotted_glyphs = glyphs.into(),
+        }
+        self
+    }
+
+    /// Distinguishes the Edges by the Glyphs of their Lines, which works regardless of whether the
+    /// Colors can be told apart. The Patterns are assigned to the Edges like the Colors, see
+    /// [`Config::color_strategy`], and are repeated once all of them are used.
+    ///
+    /// Lines shared by Edges with different Patterns are drawn using the regular Glyphs. The
+    /// Glyphs of [Styles](Config::edge_style) and [Weight-Tiers](Config::weight_tiers) take
+    /// precedence over the Patterns.
+    ///
+    /// # Example
+    /// ```ignore
+    ///  (0)  (1)
+    ///   |    !
+    ///   +----+
+    ///   |    |
+    ///   |    +====+
+    ///   |    |    !
+    ///   V    V    V
+    ///  (2)  (3)  (4)
+    /// ```
+    #[must_use]
+    pub fn edge_patterns<I, L>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<LineGlyphs>,
+    {
+        self.patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Distinguishes the Edges using the Patterns `|`, `!`, `:` and `¦` for their vertical Lines
+    /// and `-`, `=`, `~` and `.` for their horizontal ones, see [`Config::edge_patterns`].
+    ///
+    /// The other Glyphs are taken from the current [Line Glyphs](Config::line_glyphs), so this
+    /// should be called after setting them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3).colorblind_patterns();
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn colorblind_patterns(self) -> Self {
+        let base = self.line_glyphs;
+        let patterns = [('|', '-'), ('!', '='), (':', '~'), ('¦', '.')].map(|(v, h)| LineGlyphs {
+            vertical: v,
+            horizontal: h,
+            ..base
+        });
+        self.edge_patterns(patterns)
+    }
+
+    /// The Glyphs used for the Edges with the given Style
+    pub(crate) fn style_glyphs(&self, style: LineStyle) -> LineGlyphs {
+        match style {
+            LineStyle::Solid => self.line_glyphs,
+            LineStyle::Dashed => self.dashed_glyphs,
+            LineStyle::Dotted => self.dotted_glyphs,
+        }
+    }
+
+    /// Sets the Cost of reversing or omitting an Edge to break the Cycles in the Graph. The Edges
+    /// to break the Cycles are chosen to keep their total Cost low, so the Edges that naturally
+    /// point backwards, like the Back-Edges of Loops in a Control-Flow-Graph, should have a low
+    /// Cost. By default every Edge costs `1`.
+    ///
+    /// The Cost is not used, if the Levels are determined by [Ranks](Config::rank).
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("entry", ()), ("header", ()), ("body", ()), ("exit", ())]);
+    /// graph.add_edges([
+    ///     ("entry", "header"),
+    ///     ("header", "body"),
+    ///     ("body", "header"),
+    ///     ("header", "exit"),
+    /// ]);
+    ///
+    /// // The Edge back to the Loop-Header is the one that should point upwards
+    /// let config = Config::new(IDFormatter::new(), 3)
+    ///     .reversal_cost(|src, target| if (*src, *target) == ("body", "header") { 1 } else { 10 });
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn reversal_cost<F>(mut self, cost: F) -> Self
+    where
+        F: Fn(&ID, &ID) -> u64 + 'static,
+    {
+        self.reversal_cost = Some(Box::new(cost));
+        self
+    }
+
+    /// Draws the Edges based on their Weight, which is determined by calling `weight` with the
+    /// Source and Target of an Edge, using the Glyphs and Colors of the matching [`WeightTiers`].
+    ///
+    /// Parts of the Lines that are shared by multiple Edges, like the horizontal Line fanning out
+    /// to multiple Targets, are drawn using the regular Glyphs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter, WeightTiers};
+    ///
+    /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3)
+    ///     .edge_weights(|src, target| (src * target) as u64, WeightTiers::thickness(10, 100));
+    /// ```
+    #[must_use]
+    pub fn edge_weights<F>(mut self, weight: F, tiers: WeightTiers) -> Self
+    where
+        F: Fn(&ID, &ID) -> u64 + 'static,
+    {
+        self.edge_weight = Some(Box::new(weight));
+        self.weight_tiers = tiers;
+        self
+    }
+
+    /// Keeps the Edges with a higher Weight shorter and straighter, like the main Flow through a
+    /// Pipeline, where the Weight is determined by calling `weight` with the Source and Target of
+    /// an Edge.
+    ///
+    /// A Node is moved up towards its Sources, if the Weights of its incoming Edges outweigh the
+    /// ones of its outgoing Edges. The Orderings tried by [`Config::best_of`] and
+    /// [`Config::refine_ordering`] also prefer to keep the heavier Edges from crossing others.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("parse", ()), ("check", ()), ("optimize", ()), ("emit", ()), ("log", ())]);
+    /// graph.add_edges([("parse", "check"), ("check", "optimize"), ("optimize", "emit")]);
+    /// graph.add_edges([("parse", "log")]);
+    ///
+    /// // The Log is placed right below the Parser, instead of at the Bottom
+    /// let config = Config::new(IDFormatter::new(), 3)
+    ///     .layout_weights(|src, target| match (*src, *target) {
+    ///         ("parse", "log") => 10,
+    ///         _ => 1,
+    ///     });
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn layout_weights<F>(mut self, weight: F) -> Self
+    where
+        F: Fn(&ID, &ID) -> u64 + 'static,
+    {
+        self.layout_weight = Some(Box::new(weight));
+        self
+    }
+
+    /// Enables or disables the Debug-Layout, which is useful to diagnose problems with the Layout
+    /// itself.
+    ///
+    /// In the Debug-Layout, the Dummy-Nodes used to route Edges through a Level are displayed as
+    /// `·`, the index of every Level is shown in front of it and all the Edges that were reversed
+    /// to break Cycles are listed below the Graph.
+    ///
+    /// # Example
+    /// ```ignore
+    /// 0  (a)
+    ///     |
+    ///     +---+
+    ///     |   |
+    ///     V   |
+    /// 1  (b)  ·
+    ///     |   |
+    ///     +---+
+    ///     |
+    ///     V
+    /// 2  (c)
+    /// ```
+    #[must_use]
+    pub fn debug_layout(mut self, enabled: bool) -> Self {
+        self.debug_layout = enabled;
+        self
+    }
+
+    /// Limits the Labels of the Nodes to the given number of Columns, where wide Characters like CJK
+    /// or Emoji take up two Columns.
+    ///
+    /// Labels that are longer, after being formatted, are cut off and end with `…` to show that
+    /// they were shortened.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Max-Width = 8
+    ///
+    /// (std::collections::HashMap) -> (std::c…
+    /// ```
+    #[must_use]
+    pub fn max_label_width(mut self, width: usize) -> Self {
+        self.max_label_width = Some(width);
+        self
+    }
+
+    /// Wraps the Labels of the Nodes onto multiple rows, so that every row of a Label is at most
+    /// the given number of Glyphs wide.
+    ///
+    /// Labels are preferably wrapped after separators, like spaces or the `/` in a Path. If a
+    /// [max label width](Config::max_label_width) is also configured, the Label is first truncated
+    /// and then wrapped.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Wrap = 8
+    ///
+    /// (/usr/local/bin)   ->   (/usr/
+    ///                         local/
+    ///                         bin)
+    /// ```
+    #[must_use]
+    pub fn wrap_labels(mut self, width: usize) -> Self {
+        self.wrap_labels = Some(width);
+        self
+    }
+
+    /// Set the max glyph width per layer
+    #[must_use]
+    pub fn max_glyphs_per_layer(mut self, max: usize) -> Self {
+        self.max_glyphs_per_layer = max;
+        self
+    }
+
+    /// Limits the number of cells that will be rendered.
+    ///
+    /// Once the limit is exceeded, no further levels are rendered and instead a summary of the
+    /// remaining Nodes is displayed (`… 12 more nodes`).
+    #[must_use]
+    pub fn max_cells(mut self, count: usize) -> Self {
+        self.max_cells = Some(count);
+        self
+    }
+
+    /// Limits the number of Levels that will be rendered, starting from the top.
+    ///
+    /// If the Graph has more Levels, the Edges leaving the last rendered Level are cut off and a
+    /// summary of the remaining Levels and Nodes is displayed (`… 12 more levels, 340 more nodes`).
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, IDFormatter};
+    ///
+    /// // Only shows the Roots and their direct Successors
+    /// let config: Config<usize, usize> = Config::new(IDFormatter::new(), 5).max_levels(2);
+    /// ```
+    #[must_use]
+    pub fn max_levels(mut self, count: usize) -> Self {
+        self.max_levels = Some(count);
+        self
+    }
+
+    /// Limits the time spent on rendering the Graph.
+    ///
+    /// Once the time is exceeded, no further levels are rendered and instead a summary of the
+    /// remaining Nodes is displayed (`… 12 more nodes`). This is checked between the rendering of
+    /// the individual levels, so the limit may be exceeded slightly.
+    ///
+    /// The time spent on breaking the Cycles and assigning the Levels counts towards the limit as
+    /// well. Once it is exceeded during these steps, they fall back to faster Heuristics, like
+    /// reversing more Edges than necessary, so that the summary can still be displayed.
+    #[must_use]
+    pub fn max_render_time(mut self, duration: Duration) -> Self {
+        self.max_render_time = Some(duration);
+        self
+    }
+
+    /// The Point in Time at which the [maximum render time](Config::max_render_time) is used up,
+    /// for a rendering that was started at `started`
+    pub(crate) fn deadline(&self, started: Instant) -> Option<Instant> {
+        self.max_render_time.map(|max| started + max)
+    }
+
+    /// Tries up to `attempts` different Orderings of the Nodes within their Levels and keeps the
+    /// one with the fewest Edges crossing each other. The first Attempts sort the Nodes by the
+    /// Positions of their Neighbours, the remaining ones start from a random Ordering, see
+    /// [`Config::layout_seed`].
+    ///
+    /// The Search stops early once the `budget` is used up or no Edges cross anymore. The `budget`
+    /// is separate from the [maximum render time](Config::max_render_time), which applies to
+    /// every tried Ordering on its own, and Orderings that could not display all the Nodes are
+    /// never chosen over complete ones. It is not used with [sorted Siblings](Config::sort_siblings)
+    /// or explicitly provided Levels.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3).best_of(20, Some(Duration::from_millis(50)));
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes((0..6).map(|i| (i, ())));
+    /// graph.add_edges([(0, 3), (0, 4), (1, 5), (2, 3), (1, 4), (2, 5)]);
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn best_of(mut self, attempts: usize, budget: Option<Duration>) -> Self {
+        self.layout_attempts = attempts.max(1);
+        self.layout_budget = budget;
+        self
+    }
+
+    /// The Seed for the random Orderings tried by [`Config::best_of`], which makes the chosen
+    /// Layout reproducible. Defaults to 0
+    #[must_use]
+    pub fn layout_seed(mut self, seed: u64) -> Self {
+        self.layout_seed = seed;
+        self
+    }
+
+    /// Refines the Ordering of the Nodes within their Levels, by swapping neighbouring Nodes as
+    /// long as this reduces the number of crossing Edges. This is also applied to every Ordering
+    /// tried by [`Config::best_of`].
+    ///
+    /// Like the Search, this is not used with [sorted Siblings](Config::sort_siblings) or
+    /// explicitly provided Levels.
+    #[must_use]
+    pub fn refine_ordering(mut self, refine: bool) -> Self {
+        self.refine_ordering = refine;
+        self
+    }
+
+    /// Calls the given Hook for every Adjustment the Layout makes on its own, like cutting off a
+    /// Label or reversing an Edge to break a Cycle, see [`LayoutWarning`] for all of them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter, LayoutWarning};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3).on_warning(|warning| match warning {
+    ///     LayoutWarning::BrokenCycle { src, target } => eprintln!("reversed {src} -> {target}"),
+    ///     other => eprintln!("{other:?}"),
+    /// });
+    ///
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second")]);
+    /// graph.add_edges([(0, 1), (1, 0)]);
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn on_warning<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(LayoutWarning<'_, ID>) + 'static,
+    {
+        self.on_warning = Some(Box::new(hook));
+        self
+    }
+
+    /// Reports the Adjustment to the Hook, if there is one
+    pub(crate) fn warn(&self, warning: LayoutWarning<'_, ID>) {
+        if let Some(hook) = self.on_warning.as_ref() {
+            hook(warning);
+        }
+    }
+
+    /// Get the number of Glyphs that can be placed
+    pub(crate) fn glyph_width(&self) -> usize {
+        match self.glyph_width_cap.get() {
+            Some(cap) => self.max_glyphs_per_layer.min(cap),
+            None => self.max_glyphs_per_layer,
+        }
+    }
+
+    /// Runs the Closure, while the number of Glyphs that can be placed is limited to the given
+    /// Width
+    pub(crate) fn with_glyph_width<R>(&self, width: usize, f: impl FnOnce() -> R) -> R {
+        let previous = self.glyph_width_cap.replace(Some(width));
+        let result = f();
+        self.glyph_width_cap.set(previous);
+        result
+    }
+
+    /// The Label of a Node consisting only of its ID, if that is available
+    pub(crate) fn id_label(&self, id: &ID) -> Option<String> {
+        self.id_label.as_ref().map(|label| label(id))
+    }
+}
+
+impl<ID, T> Config<ID, T>
+where
+    ID: Display,
+{
+    /// Collapses every Chain of at least `min_length` Nodes into a single Node, where a Chain is
+    /// a linear run of Nodes that are only connected to their neighbours in the Chain.
+    ///
+    /// The collapsed Chains are labeled with the IDs of their first and last Node and the number
+    /// of Nodes between them, like `(a → … 17 nodes … → b)`. Chains of fewer than 3 Nodes are never
+    /// collapsed, as this would not save any space.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes((0..20).map(|i| (i, i)));
+    /// graph.add_edges((0..19).map(|i| (i, i + 1)));
+    ///
+    /// // Displayed as a single Node "(0 → … 18 nodes … → 19)"
+    /// let config = Config::new(IDFormatter::new(), 3).collapse_chains(5);
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn collapse_chains(mut self, min_length: usize) -> Self {
+        self.chain_length = Some(min_length);
+        self.id_label = Some(Box::new(|id: &ID| id.to_string()));
+        self
+    }
+
+    /// Shortens the Labels of the Nodes, that do not fit onto their Level, before moving them to
+    /// another Level. The shorter Label of the [Formatter](crate::NodeFormat::format_short) is
+    /// tried first and then only the ID of the Node, like `(12)`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDValueFormatter};
+    ///
+    /// // One of the Children is displayed as `(1)` or `(2)`, to fit next to the other one
+    /// let config = Config::new(IDValueFormatter::new(), 3)
+    ///     .max_glyphs_per_layer(34)
+    ///     .shorten_labels(true);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "root"), (1, "a rather long label"), (2, "another long label")]);
+    /// graph.add_edges([(0, 1), (0, 2)]);
+    ///
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn shorten_labels(mut self, shorten: bool) -> Self {
+        self.shorten_labels = shorten;
+        self.id_label = Some(Box::new(|id: &ID| id.to_string()));
+        self
+    }
+}
+
+impl<ID, T> Config<ID, T>
+where
+    ID: Ord,
+{
+    /// Orders the Nodes on every Level by their IDs, instead of the arbitrary Order they are
+    /// placed in otherwise. This keeps the Output the same across multiple runs, which makes it
+    /// easier to compare them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 5).sort_siblings();
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes((0..5).map(|i| (i, i)));
+    /// graph.add_edges((1..5).map(|i| (0, i)));
+    ///
+    /// // The Children are displayed as (1) (2) (3) (4)
+    /// termgraph::display(&graph, &config);
+    /// ```
+    #[must_use]
+    pub fn sort_siblings(mut self) -> Self {
+        self.sibling_order = Some(Box::new(|a: &ID, b: &ID| a.cmp(b)));
+        self
+    }
+}
+
+/// Determines the number of columns available for the output
+fn terminal_width() -> usize {
+    if !std::io::stdout().is_terminal() {
+        return 120;
+    }
+
+    terminal_columns()
+        .or_else(|| {
+            std::env::var("COLUMNS")
+                .ok()
+                .and_then(|c| c.trim().parse().ok())
+        })
+        .filter(|c| *c > 0)
+        .unwrap_or(80)
+}
+
+/// Queries the number of columns of the Terminal connected to Stdout
+#[cfg(unix)]
+fn terminal_columns() -> Option<usize> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: `TIOCGWINSZ` only writes the Size of the Terminal into the provided struct
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    (result == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+/// Queries the number of columns of the Terminal connected to Stdout, which is only supported on
+/// Unix
+#[cfg(not(unix))]
+fn terminal_columns() -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn env_overrides() {
+        let vars = |name: &str| match name {
+            "TERMGRAPH_COLORS" => Some("on".to_string()),
+            "TERMGRAPH_GLYPHS" => Some("unicode".to_string()),
+            "TERMGRAPH_MAX_WIDTH" => Some("60".to_string()),
+            _ => None,
+        };
+
+        let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3).env_overrides(vars);
+
+        assert_eq!(Some(default_palette()), config.color_palette);
+        assert_eq!('│', config.line_glyphs.vertical);
+        assert_eq!(60, config.glyph_width());
+    }
+
+    #[test]
+    fn env_overrides_invalid() {
+        let vars = |name: &str| match name {
+            "TERMGRAPH_COLORS" => Some("maybe".to_string()),
+            "TERMGRAPH_MAX_WIDTH" => Some("wide".to_string()),
+            _ => None,
+        };
+
+        let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3).env_overrides(vars);
+
+        assert_eq!(None, config.color_palette);
+        assert_eq!('|', config.line_glyphs.vertical);
+        assert_eq!(usize::MAX, config.glyph_width());
+    }
+
+    #[test]
+    fn env_overrides_zero_width() {
+        let vars = |name: &str| match name {
+            "TERMGRAPH_MAX_WIDTH" => Some("0".to_string()),
+            _ => None,
+        };
+
+        let config: Config<usize, usize> = Config::new(IDFormatter::new(), 3).env_overrides(vars);
+
+        assert_eq!(usize::MAX, config.glyph_width());
     }
 }
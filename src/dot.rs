@@ -0,0 +1,500 @@
+//! Reads Graphs written in the DOT Language of Graphviz, which is available with the `dot`
+//! Feature.
+//!
+//! Only the Parts of the Language, that matter for the Structure of the Graph, are supported:
+//! * `graph` and `digraph` with an optional Name and `strict`
+//! * Node- and Edge-Statements, including Chains like `a -> b -> c`
+//! * Subgraphs and Clusters, whose Nodes are added to the Graph directly, and Subgraphs as the
+//!   Ends of an Edge, like `a -> { b c }`
+//! * The `label` Attribute of Nodes and Edges
+//! * Comments and quoted or HTML IDs
+//!
+//! All other Attributes, like Colors or Shapes, as well as the Ports of Nodes are ignored. Every
+//! Node is mapped to its Label, which defaults to the ID of the Node, and the Edges of undirected
+//! Graphs point in the Direction they were written in.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{dot, Config, ValueFormatter};
+//!
+//! let graph = dot::parse(r#"
+//!     digraph {
+//!         start [label="Start"];
+//!         start -> parse -> check;
+//!         check -> parse [label="retry"];
+//!     }
+//! "#).unwrap();
+//! assert_eq!(Some("retry"), graph.edge_label(&"check".to_string(), &"parse".to_string()));
+//!
+//! termgraph::display(&graph, &Config::new(ValueFormatter::new(), 3));
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::{graphviz::unescape_label, DirectedGraph};
+
+/// The Error returned when parsing malformed DOT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotError {
+    /// The Line of the Input, starting at 1
+    pub line: usize,
+    /// What is wrong with the Input
+    pub message: String,
+}
+
+impl Display for DotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DotError {}
+
+/// Parses the first Graph in the given DOT, see the [module level docs](self) for the supported
+/// Parts of the Language
+pub fn parse(input: &str) -> Result<DirectedGraph<String, String>, DotError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+        nodes: Vec::new(),
+        known: HashSet::new(),
+        labels: HashMap::new(),
+        edges: Vec::new(),
+    };
+    parser.graph()?;
+
+    let mut graph = DirectedGraph::with_capacity(parser.nodes.len(), parser.edges.len());
+    let labels = parser.labels;
+    graph.add_nodes(parser.nodes.into_iter().map(|id| {
+        // Without a Label, Graphviz displays the Name with its Escape-Sequences replaced
+        let label = labels
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| unescape_label(&id, "\\N"));
+        (id, label)
+    }));
+    let (labeled, unlabeled): (Vec<_>, Vec<_>) =
+        parser.edges.into_iter().partition(|(_, _, l)| l.is_some());
+    graph.add_edges(unlabeled.into_iter().map(|(from, to, _)| (from, to)));
+    graph.add_labeled_edges(
+        labeled
+            .into_iter()
+            .filter_map(|(from, to, label)| Some((from, to, label?))),
+    );
+    Ok(graph)
+}
+
+/// A single Token of the Input
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    text: String,
+    /// Quoted and HTML IDs are never Keywords or Punctuation
+    quoted: bool,
+    line: usize,
+}
+
+/// Splits the Input into its Tokens, while skipping Whitespace and Comments
+fn tokenize(input: &str) -> Result<Vec<Token>, DotError> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = input.chars().peekable();
+    // Lines starting with a `#` are the Output of the C-Preprocessor and are skipped
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        let start = line;
+        let error = |message: &str| DotError {
+            line: start,
+            message: message.to_string(),
+        };
+        let mut token = |text: String, quoted: bool| {
+            tokens.push(Token {
+                text,
+                quoted,
+                line: start,
+            })
+        };
+
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '#' if line_start => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => {
+                            line += usize::from(c == '\n');
+                            previous = c;
+                        }
+                        None => return Err(error("unterminated comment")),
+                    }
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            text.push('"');
+                        }
+                        // An escaped Newline continues the String on the next Line
+                        Some('\\') if chars.peek() == Some(&'\n') => {
+                            chars.next();
+                            line += 1;
+                        }
+                        Some(c) => {
+                            line += usize::from(c == '\n');
+                            text.push(c);
+                        }
+                        None => return Err(error("unterminated quoted string")),
+                    }
+                }
+                token(text, true);
+            }
+            '<' => {
+                let mut text = String::new();
+                let mut depth = 1;
+                loop {
+                    match chars.next() {
+                        Some('>') if depth == 1 => break,
+                        Some(c) => {
+                            match c {
+                                '<' => depth += 1,
+                                '>' => depth -= 1,
+                                '\n' => line += 1,
+                                _ => {}
+                            }
+                            text.push(c);
+                        }
+                        None => return Err(error("unterminated HTML string")),
+                    }
+                }
+                token(text, true);
+            }
+            '-' if matches!(chars.peek(), Some('>' | '-')) => {
+                let next = chars.next().unwrap_or_default();
+                token(format!("-{next}"), false);
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' => token(c.to_string(), false),
+            c if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') => {
+                let mut text = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '.'))
+                {
+                    text.push(c);
+                }
+                token(text, false);
+            }
+            c => return Err(error(&format!("unexpected character {c:?}"))),
+        }
+        line_start = false;
+    }
+    Ok(tokens)
+}
+
+/// The State while parsing the Tokens
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// The Nodes in the Order they first appeared in
+    nodes: Vec<String>,
+    known: HashSet<String>,
+    labels: HashMap<String, String>,
+    edges: Vec<(String, String, Option<String>)>,
+}
+
+impl Parser {
+    /// The Token at the current Position, if it is not quoted
+    fn peek(&self) -> Option<&str> {
+        self.tokens
+            .get(self.position)
+            .filter(|t| !t.quoted)
+            .map(|t| t.text.as_str())
+    }
+
+    /// Whether the current Token is the given Keyword, which are case-insensitive
+    fn keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|t| t.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Skips the current Token if it matches
+    fn accept(&mut self, text: &str) -> bool {
+        let matches = self.peek() == Some(text);
+        self.position += usize::from(matches);
+        matches
+    }
+
+    fn error(&self, message: String) -> DotError {
+        let line = self
+            .tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |t| t.line);
+        DotError { line, message }
+    }
+
+    /// Describes the current Token for an Error-Message
+    fn found(&self) -> String {
+        match self.tokens.get(self.position) {
+            Some(token) => format!("found {:?}", token.text),
+            None => "found the end of the input".to_string(),
+        }
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), DotError> {
+        if self.accept(text) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {:?}, {}", text, self.found())))
+        }
+    }
+
+    /// Parses an ID, which is any Token except for Punctuation
+    fn id(&mut self) -> Result<String, DotError> {
+        match self.tokens.get(self.position) {
+            Some(token)
+                if token.quoted
+                    || !matches!(
+                        token.text.as_str(),
+                        "{" | "}" | "[" | "]" | ";" | "," | "=" | ":" | "->" | "--"
+                    ) =>
+            {
+                self.position += 1;
+                Ok(token.text.clone())
+            }
+            _ => Err(self.error(format!("expected an ID, {}", self.found()))),
+        }
+    }
+
+    fn graph(&mut self) -> Result<(), DotError> {
+        if self.keyword("strict") {
+            self.position += 1;
+        }
+        if !self.keyword("graph") && !self.keyword("digraph") {
+            return Err(self.error(format!(
+                "expected \"graph\" or \"digraph\", {}",
+                self.found()
+            )));
+        }
+        self.position += 1;
+        if self.peek() != Some("{") {
+            self.id()?;
+        }
+        self.expect("{")?;
+        self.statements()?;
+        Ok(())
+    }
+
+    /// Parses the Statements up to and including the closing Brace, and returns all the Nodes
+    /// that appear in them
+    fn statements(&mut self) -> Result<Vec<String>, DotError> {
+        let mut nodes = Vec::new();
+        while !self.accept("}") {
+            if self.position >= self.tokens.len() {
+                return Err(self.error("expected \"}\", found the end of the input".to_string()));
+            }
+            self.statement(&mut nodes)?;
+            self.accept(";");
+        }
+        Ok(nodes)
+    }
+
+    fn statement(&mut self, nodes: &mut Vec<String>) -> Result<(), DotError> {
+        // The default Attributes for the following Statements
+        if ["graph", "node", "edge"].iter().any(|k| self.keyword(k))
+            && self.tokens.get(self.position + 1).map(|t| t.text.as_str()) == Some("[")
+        {
+            self.position += 1;
+            self.attributes()?;
+            return Ok(());
+        }
+        // An Attribute of the Graph itself
+        if self
+            .tokens
+            .get(self.position + 1)
+            .map(|t| (t.text.as_str(), t.quoted))
+            == Some(("=", false))
+        {
+            self.id()?;
+            self.position += 1;
+            self.id()?;
+            return Ok(());
+        }
+
+        let mut from = self.operand(nodes)?;
+        let mut edges = Vec::new();
+        while self.accept("->") || self.accept("--") {
+            let to = self.operand(nodes)?;
+            edges.extend(
+                from.iter()
+                    .flat_map(|f| to.iter().map(move |t| (f.clone(), t.clone()))),
+            );
+            from = to;
+        }
+
+        let attributes = self.attributes()?;
+        let label = attributes
+            .into_iter()
+            .rev()
+            .find(|(key, _)| key == "label")
+            .map(|(_, value)| value);
+        match label {
+            Some(label) if edges.is_empty() => {
+                for node in from {
+                    self.labels
+                        .insert(node.clone(), unescape_label(&node, &label));
+                }
+            }
+            label => {
+                // Labels of Edges are displayed on a single Line
+                let label = label.map(|l| unescape_label("", &l).replace('\n', " "));
+                self.edges
+                    .extend(edges.into_iter().map(|(f, t)| (f, t, label.clone())));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a single End of an Edge, which is either a Node or a Subgraph, and returns the Nodes
+    /// it contains
+    fn operand(&mut self, nodes: &mut Vec<String>) -> Result<Vec<String>, DotError> {
+        if self.keyword("subgraph") || self.peek() == Some("{") {
+            if self.keyword("subgraph") {
+                self.position += 1;
+                if self.peek() != Some("{") {
+                    self.id()?;
+                }
+            }
+            self.expect("{")?;
+            let contained = self.statements()?;
+            nodes.extend(contained.iter().cloned());
+            return Ok(contained);
+        }
+
+        let id = self.id()?;
+        // The Port and Compass-Point of the Node are not needed
+        while self.accept(":") {
+            self.id()?;
+        }
+        if self.known.insert(id.clone()) {
+            self.nodes.push(id.clone());
+        }
+        nodes.push(id.clone());
+        Ok(vec![id])
+    }
+
+    /// Parses any number of Attribute-Lists, like `[label="a", color=red][shape=box]`
+    fn attributes(&mut self) -> Result<Vec<(String, String)>, DotError> {
+        let mut attributes = Vec::new();
+        while self.accept("[") {
+            while !self.accept("]") {
+                let key = self.id()?;
+                let value = if self.accept("=") {
+                    self.id()?
+                } else {
+                    "true".to_string()
+                };
+                attributes.push((key, value));
+                if !self.accept(",") {
+                    self.accept(";");
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(graph: &DirectedGraph<String, String>) -> Vec<(&str, &str)> {
+        let mut edges: Vec<_> = graph
+            .edges()
+            .map(|(f, t)| (f.as_str(), t.as_str()))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn statements() {
+        let graph = parse(
+            r#"
+            /* A Comment */
+            strict digraph "name" {
+                rankdir = LR; // Another Comment
+                node [shape=box]
+                a [label="First\nNode"];
+                a -> b -> c:port:n [color=red, label="chain"]
+                subgraph cluster_x { d; e [label=<<b>E</b>>] }
+                c -> { d e }
+                "quoted id" -> a
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("a", "b"),
+                ("b", "c"),
+                ("c", "d"),
+                ("c", "e"),
+                ("quoted id", "a")
+            ],
+            edges(&graph)
+        );
+        assert_eq!(
+            Some(&"First\nNode".to_string()),
+            graph.get(&"a".to_string())
+        );
+        assert_eq!(Some(&"<b>E</b>".to_string()), graph.get(&"e".to_string()));
+        assert_eq!(Some(&"d".to_string()), graph.get(&"d".to_string()));
+        assert_eq!(
+            Some("chain"),
+            graph.edge_label(&"b".to_string(), &"c".to_string())
+        );
+        assert_eq!(None, graph.edge_label(&"c".to_string(), &"d".to_string()));
+    }
+
+    #[test]
+    fn escaped_ids() {
+        let graph = parse(r#"digraph { "a\"q" -> "x\ny"; "c\\d"; e [label="\N\l"] }"#).unwrap();
+
+        let label = |id: &str| graph.get(&id.to_string()).cloned();
+        assert_eq!(Some("a\"q".to_string()), label("a\"q"));
+        assert_eq!(Some("x\ny".to_string()), label("x\\ny"));
+        assert_eq!(Some("c\\d".to_string()), label("c\\\\d"));
+        assert_eq!(Some("e".to_string()), label("e"));
+    }
+
+    #[test]
+    fn undirected() {
+        let graph = parse("graph { 1 -- 2 -- -3.5 }").unwrap();
+        assert_eq!(vec![("1", "2"), ("2", "-3.5")], edges(&graph));
+    }
+
+    #[test]
+    fn errors() {
+        let error = parse("digraph {\n a -> \n}").unwrap_err();
+        assert_eq!(3, error.line);
+
+        let error = parse("digraph {\n a [label=\"x]\n}").unwrap_err();
+        assert_eq!(2, error.line);
+
+        assert!(parse("tree { a }").is_err());
+        assert!(parse("digraph { a ").is_err());
+    }
+}
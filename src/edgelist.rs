@@ -0,0 +1,132 @@
+use std::io::{BufRead, BufReader, Read};
+
+use crate::DirectedGraph;
+
+impl DirectedGraph<String, ()> {
+    /// Loads a Graph from a plain Edge-List with one `src,dst` Pair per Line, like a CSV-File
+    /// exported from a Database or Spreadsheet.
+    ///
+    /// The Nodes are created from the Edges, where a Line with only a single Field adds a Node
+    /// without any Edges. The Fields are trimmed and may be quoted like in CSV, so a quoted Field
+    /// can contain Commas and `""` stands for a single Quote. Any further Columns, like Weights,
+    /// are ignored. Empty Lines and Lines starting with `#` are skipped, as well as a UTF-8 BOM at
+    /// the Start. Use [`DirectedGraph::from_edge_list_with_header`] if the first Row contains the
+    /// Names of the Columns.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let csv = "\
+    /// ## src,dst
+    /// users,orders
+    /// orders,items
+    /// \"items\", \"products, archived\", 3
+    /// archive
+    /// ";
+    /// let graph = DirectedGraph::from_edge_list(csv.as_bytes()).unwrap();
+    /// assert_eq!(5, graph.nodes().count());
+    /// assert_eq!(3, graph.edges().count());
+    ///
+    /// termgraph::display(&graph, &Config::new(IDFormatter::new(), 3));
+    /// ```
+    pub fn from_edge_list<R>(reader: R) -> std::io::Result<Self>
+    where
+        R: Read,
+    {
+        Self::parse_edge_list(reader, false)
+    }
+
+    /// Loads a Graph from an Edge-List like [`DirectedGraph::from_edge_list`], where the first
+    /// Row, which is not empty or a Comment, is the Header of the Columns and is skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::DirectedGraph;
+    ///
+    /// let csv = "src,dst\nusers,orders\n";
+    /// let graph = DirectedGraph::from_edge_list_with_header(csv.as_bytes()).unwrap();
+    /// assert_eq!(2, graph.nodes().count());
+    /// assert!(graph.get(&"src".to_string()).is_none());
+    /// ```
+    pub fn from_edge_list_with_header<R>(reader: R) -> std::io::Result<Self>
+    where
+        R: Read,
+    {
+        Self::parse_edge_list(reader, true)
+    }
+
+    fn parse_edge_list<R>(reader: R, mut header: bool) -> std::io::Result<Self>
+    where
+        R: Read,
+    {
+        let invalid = |index: usize, message: String| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("line {}: {message}", index + 1),
+            )
+        };
+
+        let mut graph = Self::new();
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = match index {
+                0 => line.strip_prefix('\u{feff}').unwrap_or(&line),
+                _ => &line,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if header {
+                header = false;
+                continue;
+            }
+
+            let mut fields = split_fields(line)
+                .ok_or_else(|| invalid(index, format!("unterminated quote in {line:?}")))?
+                .into_iter();
+            let src = fields.next().unwrap_or_default();
+            let dst = fields.next();
+            if src.is_empty() || dst.as_ref().is_some_and(String::is_empty) {
+                return Err(invalid(index, format!("empty node in {line:?}")));
+            }
+
+            graph.add_nodes([(src.clone(), ())]);
+            if let Some(dst) = dst {
+                graph.add_nodes([(dst.clone(), ())]);
+                graph.add_edges([(src, dst)]);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// Splits the Line into its trimmed Fields, where Commas within Quotes do not separate the
+/// Fields, or returns `None` if a Quote is not closed
+fn split_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field.trim().to_string());
+    Some(fields)
+}
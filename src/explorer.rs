@@ -0,0 +1,470 @@
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashSet,
+    hash::Hash,
+    time::Instant,
+};
+
+use crate::{Config, DirectedGraph, DisplayOptions, Layout};
+
+/// An Action the User can perform in an [`Explorer`], usually bound to a Key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplorerAction {
+    /// Selects the next visible Node
+    Next,
+    /// Selects the previous visible Node
+    Previous,
+    /// Shows the Successors of the selected Node
+    Expand,
+    /// Hides the Successors of the selected Node
+    Collapse,
+    /// Expands the selected Node if it is collapsed and collapses it otherwise
+    Toggle,
+}
+
+/// Explores a large Graph, starting from a single Root, by expanding and collapsing the Successors
+/// of the Nodes.
+///
+/// Initially only the Root is visible and every [expanded](Explorer::expand) Node also shows its
+/// Successors. The selected Node is highlighted when the Explorer is displayed and the collapsed
+/// Nodes hiding some of their Successors are marked with a Stub, like `(node) → …`.
+///
+/// The Layout of the visible Nodes is only computed again once Nodes are expanded or collapsed, so
+/// moving the Selection only displays the existing Layout with a different Highlight.
+///
+/// The Explorer can be driven by any Terminal library by mapping the Keys to
+/// [`ExplorerAction`]s, or by [`Explorer::run`] with the `crossterm` Feature.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, Explorer, ExplorerAction, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// graph.add_edges([(0, 1), (1, 2)]);
+///
+/// let mut explorer = Explorer::new(&graph, 0);
+/// explorer.handle(ExplorerAction::Expand);
+/// explorer.handle(ExplorerAction::Next);
+///
+/// assert_eq!(&1, explorer.selected());
+/// explorer.fdisplay(&config, std::io::stdout().lock());
+/// ```
+pub struct Explorer<'g, ID, T>
+where
+    ID: Hash + Eq,
+{
+    graph: &'g DirectedGraph<ID, T>,
+    root: ID,
+    expanded: HashSet<ID>,
+    selected: ID,
+    /// The visible Nodes, which are only updated when Nodes are expanded or collapsed
+    visible: Vec<&'g ID>,
+    /// The Layout of the visible Nodes, which is computed once it is displayed
+    layout: RefCell<Option<Layout<'g, ID, T>>>,
+}
+
+impl<'g, ID, T> Explorer<'g, ID, T>
+where
+    ID: Hash + Eq + Clone,
+{
+    /// Creates a new Explorer, where only the Root is visible and selected
+    pub fn new(graph: &'g DirectedGraph<ID, T>, root: ID) -> Self {
+        let mut explorer = Self {
+            graph,
+            selected: root.clone(),
+            root,
+            expanded: HashSet::new(),
+            visible: Vec::new(),
+            layout: RefCell::new(None),
+        };
+        explorer.update_visible();
+        explorer
+    }
+
+    /// The currently selected Node
+    pub fn selected(&self) -> &ID {
+        &self.selected
+    }
+
+    /// Selects the given Node, if it is visible
+    pub fn select(&mut self, id: &ID) -> bool {
+        match self.visible.iter().find(|v| **v == id) {
+            Some(id) => {
+                self.selected = (*id).clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the Successors of the given Node are shown
+    pub fn is_expanded(&self, id: &ID) -> bool {
+        self.expanded.contains(id)
+    }
+
+    /// Whether the given Node has Successors, which are not shown because it is collapsed
+    pub fn has_hidden_successors(&self, id: &ID) -> bool {
+        !self.is_expanded(id) && self.graph.successors(id).next().is_some()
+    }
+
+    /// Shows the Successors of the given Node
+    pub fn expand(&mut self, id: ID) {
+        if self.expanded.insert(id) {
+            self.update_visible();
+        }
+    }
+
+    /// Hides the Successors of the given Node.
+    ///
+    /// If the selected Node is hidden as a result, the collapsed Node is selected instead.
+    pub fn collapse(&mut self, id: &ID) {
+        if self.expanded.remove(id) {
+            self.update_visible();
+        }
+        if !self.visible.contains(&&self.selected) {
+            self.selected = id.clone();
+        }
+    }
+
+    /// All the visible Nodes, in the order in which they are reached from the Root
+    pub fn visible(&self) -> &[&'g ID] {
+        &self.visible
+    }
+
+    /// Collects the visible Nodes again and discards the Layout of the previously visible ones
+    fn update_visible(&mut self) {
+        self.layout.get_mut().take();
+        self.visible.clear();
+        let Some(root) = self.graph.get_key(&self.root) else {
+            return;
+        };
+
+        let mut seen: HashSet<&'g ID> = HashSet::from([root]);
+        self.visible.push(root);
+        let mut index = 0;
+        while let Some(current) = self.visible.get(index).copied() {
+            index += 1;
+            if !self.expanded.contains(current) {
+                continue;
+            }
+
+            for succ in self.graph.successors(current) {
+                if seen.insert(succ) {
+                    self.visible.push(succ);
+                }
+            }
+        }
+    }
+
+    /// Performs the given Action and returns whether anything changed
+    pub fn handle(&mut self, action: ExplorerAction) -> bool {
+        let position = self.visible.iter().position(|v| **v == self.selected);
+
+        match action {
+            ExplorerAction::Next | ExplorerAction::Previous => {
+                let target = match (action, position) {
+                    (ExplorerAction::Next, Some(p)) => self.visible.get(p + 1),
+                    (_, Some(p)) => p.checked_sub(1).and_then(|p| self.visible.get(p)),
+                    (_, None) => self.visible.first(),
+                };
+                match target {
+                    Some(target) => {
+                        self.selected = (*target).clone();
+                        true
+                    }
+                    None => false,
+                }
+            }
+            ExplorerAction::Expand => {
+                let changed = !self.is_expanded(&self.selected);
+                self.expand(self.selected.clone());
+                changed
+            }
+            ExplorerAction::Collapse => {
+                let changed = self.is_expanded(&self.selected);
+                let selected = self.selected.clone();
+                self.collapse(&selected);
+                changed
+            }
+            ExplorerAction::Toggle => match self.is_expanded(&self.selected) {
+                true => self.handle(ExplorerAction::Collapse),
+                false => self.handle(ExplorerAction::Expand),
+            },
+        }
+    }
+}
+
+impl<'g, ID, T> Explorer<'g, ID, T>
+where
+    ID: Hash + Eq + Clone + 'static,
+{
+    /// Writes the visible Part of the Graph to the given Output Target, highlighting the selected
+    /// Node.
+    ///
+    /// The Layout is reused until Nodes are expanded or collapsed, so the same Config should be
+    /// used every time.
+    pub fn fdisplay<W>(&self, config: &Config<ID, T>, dest: W)
+    where
+        W: std::io::Write,
+    {
+        let options = DisplayOptions::new().highlight([self.selected.clone()]);
+        self.layout(config).fdisplay_with(config, &options, dest);
+    }
+
+    /// The Layout of the visible Nodes, which is computed if it is not known yet
+    fn layout(&self, config: &Config<ID, T>) -> Ref<'_, Layout<'g, ID, T>> {
+        if self.layout.borrow().is_none() {
+            // Only the collapsed Nodes have Successors, that are not visible
+            let visible = self.visible.iter().copied().collect();
+            let layout = Layout::from_reached(self.graph, visible, config, Instant::now());
+            *self.layout.borrow_mut() = Some(layout);
+        }
+        Ref::map(self.layout.borrow(), |layout| {
+            layout.as_ref().expect("The Layout was computed above")
+        })
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl<'g, ID, T> Explorer<'g, ID, T>
+where
+    ID: Hash + Eq + Clone + 'static,
+{
+    /// Explores the Graph interactively in the Terminal, until `q`, `Esc` or `Ctrl-C` is pressed.
+    ///
+    /// The Graph is displayed on the alternate Screen, which is scrolled to keep the selected Node
+    /// visible, and the Keys are mapped to the [`ExplorerAction`]s:
+    /// * `↓` or `j`: [`Next`](ExplorerAction::Next)
+    /// * `↑` or `k`: [`Previous`](ExplorerAction::Previous)
+    /// * `→`, `l` or `+`: [`Expand`](ExplorerAction::Expand)
+    /// * `←`, `h` or `-`: [`Collapse`](ExplorerAction::Collapse)
+    /// * `Enter` or `Space`: [`Toggle`](ExplorerAction::Toggle)
+    ///
+    /// # Example
+    /// ```no_run
+    /// use termgraph::{Config, DirectedGraph, Explorer, IDFormatter};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2)]);
+    ///
+    /// Explorer::new(&graph, 0).run(&config).unwrap();
+    /// ```
+    pub fn run(&mut self, config: &Config<ID, T>) -> std::io::Result<()> {
+        use crossterm::{cursor, event, execute, terminal};
+        use std::io::Write;
+
+        let mut stdout = std::io::stdout();
+        terminal::enable_raw_mode()?;
+        let _restore = RawTerminal;
+        execute!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            terminal::DisableLineWrap,
+            cursor::Hide
+        )?;
+
+        loop {
+            let mut output = Vec::new();
+            self.fdisplay(config, &mut output);
+            let output = String::from_utf8_lossy(&output);
+            let lines: Vec<&str> = output.lines().collect();
+
+            // The last Row of the Screen is used for the Help
+            let (_, height) = terminal::size()?;
+            let rows = usize::from(height.saturating_sub(1)).max(1);
+            let selected = self
+                .layout(config)
+                .node_positions(config)
+                .get(&self.selected)
+                .map_or(0, |position| position.row);
+            let offset = selected
+                .saturating_sub(rows / 2)
+                .min(lines.len().saturating_sub(rows));
+
+            execute!(
+                stdout,
+                terminal::Clear(terminal::ClearType::All),
+                cursor::MoveTo(0, 0)
+            )?;
+            // The Lines have to be ended explicitly, as the Raw Mode does not return to the start
+            // of the Line on its own
+            for line in lines.iter().skip(offset).take(rows) {
+                write!(stdout, "{line}\r\n")?;
+            }
+            execute!(stdout, cursor::MoveTo(0, height.saturating_sub(1)))?;
+            write!(
+                stdout,
+                "↑↓ select  → expand  ← collapse  enter toggle  q quit"
+            )?;
+            stdout.flush()?;
+
+            loop {
+                match event::read()? {
+                    event::Event::Key(key) if key.kind != event::KeyEventKind::Release => {
+                        match key_input(key) {
+                            Some(KeyInput::Quit) => return Ok(()),
+                            Some(KeyInput::Action(action)) if self.handle(action) => break,
+                            _ => {}
+                        }
+                    }
+                    event::Event::Resize(_, _) => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// What a Key does in [`Explorer::run`]
+#[cfg(feature = "crossterm")]
+#[derive(Debug, PartialEq, Eq)]
+enum KeyInput {
+    Action(ExplorerAction),
+    Quit,
+}
+
+/// Maps the Key to its Input in [`Explorer::run`]
+#[cfg(feature = "crossterm")]
+fn key_input(key: crossterm::event::KeyEvent) -> Option<KeyInput> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    // The Raw Mode no longer turns Ctrl-C into a Signal, so it has to quit explicitly
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return Some(KeyInput::Quit);
+    }
+
+    let action = match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => return Some(KeyInput::Quit),
+        KeyCode::Down | KeyCode::Char('j') => ExplorerAction::Next,
+        KeyCode::Up | KeyCode::Char('k') => ExplorerAction::Previous,
+        KeyCode::Right | KeyCode::Char('l' | '+') => ExplorerAction::Expand,
+        KeyCode::Left | KeyCode::Char('h' | '-') => ExplorerAction::Collapse,
+        KeyCode::Enter | KeyCode::Char(' ') => ExplorerAction::Toggle,
+        _ => return None,
+    };
+    Some(KeyInput::Action(action))
+}
+
+/// Restores the Terminal once [`Explorer::run`] returns, even if it failed
+#[cfg(feature = "crossterm")]
+struct RawTerminal;
+
+#[cfg(feature = "crossterm")]
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        use crossterm::{cursor, execute, terminal};
+
+        let _ = execute!(
+            std::io::stdout(),
+            cursor::Show,
+            terminal::EnableLineWrap,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    fn graph() -> DirectedGraph<usize, &'static str> {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "root"), (1, "left"), (2, "right"), (3, "leaf")]);
+        graph.add_edges([(0, 1), (0, 2), (1, 3)]);
+        graph
+    }
+
+    #[test]
+    fn expand_and_collapse() {
+        let graph = graph();
+        let mut explorer = Explorer::new(&graph, 0);
+        assert_eq!(vec![&0], explorer.visible());
+        assert!(explorer.has_hidden_successors(&0));
+
+        assert!(explorer.handle(ExplorerAction::Expand));
+        assert_eq!(3, explorer.visible().len());
+
+        assert!(explorer.select(&1));
+        assert!(explorer.handle(ExplorerAction::Toggle));
+        assert!(explorer.visible().contains(&&3));
+
+        // Collapsing the Root hides the selected Node, so the Root is selected instead
+        assert!(explorer.select(&3));
+        explorer.collapse(&0);
+        assert_eq!(&0, explorer.selected());
+        assert_eq!(vec![&0], explorer.visible());
+    }
+
+    #[test]
+    fn navigate() {
+        let graph = graph();
+        let mut explorer = Explorer::new(&graph, 0);
+        assert!(!explorer.handle(ExplorerAction::Next));
+
+        explorer.handle(ExplorerAction::Expand);
+        assert!(explorer.handle(ExplorerAction::Next));
+        assert_ne!(&0, explorer.selected());
+        assert!(explorer.handle(ExplorerAction::Previous));
+        assert_eq!(&0, explorer.selected());
+        assert!(!explorer.handle(ExplorerAction::Previous));
+    }
+
+    #[test]
+    fn display_visible() {
+        let graph = graph();
+        let config = Config::new(IDFormatter::new(), 10);
+        let mut explorer = Explorer::new(&graph, 0);
+        explorer.handle(ExplorerAction::Expand);
+
+        let mut output = Vec::new();
+        explorer.fdisplay(&config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("\x1b[7m(0)\x1b[0m"));
+        // Only the collapsed Node hiding a Successor is marked
+        assert!(output.contains("(1) → …"));
+        assert!(output.contains("(2)"));
+        assert!(!output.contains("(2) → …"));
+        assert!(!output.contains("(3)"));
+
+        // Moving the Selection keeps the Layout and only moves the Highlight
+        explorer.handle(ExplorerAction::Next);
+        let mut moved = Vec::new();
+        explorer.fdisplay(&config, &mut moved);
+        let moved = String::from_utf8(moved).unwrap();
+        assert_eq!(
+            crate::formatter::strip_ansi(&output),
+            crate::formatter::strip_ansi(&moved)
+        );
+        assert!(!moved.contains("\x1b[7m(0)\x1b[0m"));
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn keys() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(Some(KeyInput::Quit), key_input(key(KeyCode::Char('q'))));
+        assert_eq!(
+            Some(KeyInput::Quit),
+            key_input(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(None, key_input(key(KeyCode::Char('c'))));
+        assert_eq!(
+            Some(KeyInput::Action(ExplorerAction::Next)),
+            key_input(key(KeyCode::Down))
+        );
+        assert_eq!(
+            Some(KeyInput::Action(ExplorerAction::Toggle)),
+            key_input(key(KeyCode::Enter))
+        );
+        assert_eq!(None, key_input(key(KeyCode::Char('x'))));
+    }
+}
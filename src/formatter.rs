@@ -1,9 +1,154 @@
-use std::fmt::Display;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+    rc::Rc,
+};
+
+use crate::names;
 
 /// Specifies how the Nodes of the Graph should be formatted
 pub trait NodeFormat<ID, T> {
     /// Formats the given Node, the returned Value will be displayed in the Graph itself
     fn format_node(&self, id: &ID, value: &T) -> String;
+
+    /// Formats the given Node as multiple Lines, which are displayed below each other in the
+    /// Graph. The Node is as wide as its longest Line.
+    ///
+    /// By default this returns the single Line produced by [`NodeFormat::format_node`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::NodeFormat;
+    ///
+    /// struct SnippetFormatter {}
+    ///
+    /// impl NodeFormat<usize, &str> for SnippetFormatter {
+    ///     fn format_node(&self, id: &usize, _: &&str) -> String {
+    ///         format!("({id})")
+    ///     }
+    ///
+    ///     fn format_node_lines(&self, id: &usize, code: &&str) -> Vec<String> {
+    ///         std::iter::once(format!("({id})"))
+    ///             .chain(code.lines().map(|l| format!("  {l}")))
+    ///             .collect()
+    ///     }
+    /// }
+    /// ```
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        vec![self.format_node(id, value)]
+    }
+
+    /// Formats a shorter Label for the given Node, which is displayed instead of the full one, if
+    /// the full Label does not fit onto the Level of the Node, see
+    /// [`Config::shorten_labels`](crate::Config::shorten_labels).
+    ///
+    /// By default there is no shorter Label, in which case only the ID of the Node is displayed.
+    fn format_short(&self, _: &ID, _: &T) -> Option<String> {
+        None
+    }
+
+    /// Takes the Problems found while formatting the last Label, which are reported as
+    /// [`LayoutWarning::InvalidLabel`](crate::LayoutWarning::InvalidLabel).
+    ///
+    /// By default no Problems are found, see the [`Validate`] Formatter.
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        Vec::new()
+    }
+}
+
+/// The Position of a Node in the displayed Graph, which is passed to a [`ContextNodeFormat`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeContext {
+    /// The Number of Edges leading to the Node
+    pub in_degree: usize,
+    /// The Number of Edges leaving the Node
+    pub out_degree: usize,
+    /// The Index of the Level the Node is placed on, starting with 0 at the top
+    pub level: usize,
+}
+
+/// Specifies how the Nodes of the Graph should be formatted, based on their Position in the Graph
+///
+/// The Degrees are counted after the Cycles in the Graph were broken, so they match the displayed
+/// Edges.
+///
+/// # Note
+/// The Nodes are distributed across the Levels using a preliminary Label, for which the Level is
+/// always 0. The final Label is then formatted with the actual Level of the Node.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{ContextNodeFormat, NodeContext};
+///
+/// struct SinkFormatter {}
+///
+/// impl ContextNodeFormat<usize, &str> for SinkFormatter {
+///     fn format_node(&self, id: &usize, _: &&str, context: &NodeContext) -> String {
+///         match context.out_degree {
+///             0 => format!("[{id}]"),
+///             _ => format!("({id})"),
+///         }
+///     }
+/// }
+/// ```
+pub trait ContextNodeFormat<ID, T> {
+    /// Formats the given Node, the returned Value will be displayed in the Graph itself
+    fn format_node(&self, id: &ID, value: &T, context: &NodeContext) -> String;
+
+    /// Formats the given Node as multiple Lines, see [`NodeFormat::format_node_lines`]
+    fn format_node_lines(&self, id: &ID, value: &T, context: &NodeContext) -> Vec<String> {
+        vec![self.format_node(id, value, context)]
+    }
+
+    /// Formats a shorter Label for the given Node, see [`NodeFormat::format_short`]
+    fn format_short(&self, _: &ID, _: &T, _: &NodeContext) -> Option<String> {
+        None
+    }
+}
+
+/// The Formatter stored in the [`Config`](crate::Config)
+pub(crate) enum Formatter<ID, T> {
+    Plain(Box<dyn NodeFormat<ID, T>>),
+    Context(Box<dyn ContextNodeFormat<ID, T>>),
+}
+
+impl<ID, T> Formatter<ID, T> {
+    /// Whether the Formatter needs the [`NodeContext`] of the Nodes
+    pub fn needs_context(&self) -> bool {
+        matches!(self, Self::Context(_))
+    }
+
+    /// Formats the Lines of the given Node, the Context is only computed if it is needed
+    pub fn format_node_lines<C>(&self, id: &ID, value: &T, context: C) -> Vec<String>
+    where
+        C: FnOnce() -> NodeContext,
+    {
+        match self {
+            Self::Plain(nfmt) => nfmt.format_node_lines(id, value),
+            Self::Context(nfmt) => nfmt.format_node_lines(id, value, &context()),
+        }
+    }
+
+    /// Formats the shorter Label of the given Node, the Context is only computed if it is needed
+    pub fn format_short<C>(&self, id: &ID, value: &T, context: C) -> Option<String>
+    where
+        C: FnOnce() -> NodeContext,
+    {
+        match self {
+            Self::Plain(nfmt) => nfmt.format_short(id, value),
+            Self::Context(nfmt) => nfmt.format_short(id, value, &context()),
+        }
+    }
+
+    /// Takes the Problems found while formatting the last Label
+    pub fn take_issues(&self) -> Vec<LabelIssue> {
+        match self {
+            Self::Plain(nfmt) => nfmt.take_issues(),
+            Self::Context(_) => Vec::new(),
+        }
+    }
 }
 
 /// Returns the ID for Formatting
@@ -55,3 +200,818 @@ where
         format!("({value})")
     }
 }
+
+/// Returns the Value for Formatting, falling back to the ID for Values that are displayed as an
+/// empty String
+///
+/// Nodes without any Value, like `()`, can be displayed using their ID with the [`IDFormatter`].
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DefaultFormatter, DirectedGraph};
+///
+/// // The unnamed Node is displayed using its ID
+/// let config = Config::new(DefaultFormatter::new(), 3);
+/// let mut graph: DirectedGraph<usize, &str> = DirectedGraph::new();
+/// graph.add_nodes([(0, "named"), (1, "")]);
+/// graph.add_edges([(0, 1)]);
+///
+/// termgraph::display(&graph, &config);
+/// ```
+pub struct DefaultFormatter {}
+
+impl DefaultFormatter {
+    /// Creates a new Instance of the Formatter
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DefaultFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ID, T> NodeFormat<ID, T> for DefaultFormatter
+where
+    ID: Display,
+    T: Display,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        let value = value.to_string();
+        match value.is_empty() {
+            true => format!("({id})"),
+            false => format!("({value})"),
+        }
+    }
+}
+
+/// Returns the [`Debug`] representation of the Value for Formatting
+///
+/// This is useful for Values that do not implement [`Display`], like most enums.
+pub struct DebugFormatter {}
+
+impl DebugFormatter {
+    /// Creates a new Instance of the Formatter
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DebugFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ID, T> NodeFormat<ID, T> for DebugFormatter
+where
+    T: Debug,
+{
+    fn format_node(&self, _: &ID, value: &T) -> String {
+        format!("({value:?})")
+    }
+}
+
+/// Returns the ID and the Value for Formatting, like `(id: value)`
+pub struct IDValueFormatter {
+    separator: String,
+}
+
+impl IDValueFormatter {
+    /// Creates a new Instance of the Formatter, which separates the ID and Value using `": "`
+    pub fn new() -> Self {
+        Self::with_separator(": ")
+    }
+
+    /// Creates a new Instance of the Formatter, which separates the ID and Value using the given
+    /// Separator
+    pub fn with_separator<S>(separator: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            separator: separator.into(),
+        }
+    }
+}
+
+impl Default for IDValueFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ID, T> NodeFormat<ID, T> for IDValueFormatter
+where
+    ID: Display,
+    T: Display,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        format!("({id}{}{value})", self.separator)
+    }
+
+    fn format_short(&self, id: &ID, _: &T) -> Option<String> {
+        Some(format!("({id})"))
+    }
+}
+
+/// Joins the Output of two Formatters using a Separator
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, IDFormatter, Join, ValueFormatter};
+///
+/// // Formats the Nodes like `(id) (value)`
+/// let config: Config<usize, &str> =
+///     Config::new(Join::new(IDFormatter::new(), ValueFormatter::new(), " "), 3);
+/// ```
+pub struct Join<A, B> {
+    first: A,
+    second: B,
+    separator: String,
+}
+
+impl<A, B> Join<A, B> {
+    /// Creates a new Instance of the Formatter, that writes the Output of `first` and then the
+    /// Output of `second` separated by the Separator
+    pub fn new<S>(first: A, second: B, separator: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            first,
+            second,
+            separator: separator.into(),
+        }
+    }
+}
+
+impl<ID, T, A, B> NodeFormat<ID, T> for Join<A, B>
+where
+    A: NodeFormat<ID, T>,
+    B: NodeFormat<ID, T>,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        format!(
+            "{}{}{}",
+            self.first.format_node(id, value),
+            self.separator,
+            self.second.format_node(id, value)
+        )
+    }
+
+    /// The last Line of the first Formatter and the first Line of the second one are joined using
+    /// the Separator
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        let mut lines = self.first.format_node_lines(id, value);
+        let mut second = self.second.format_node_lines(id, value).into_iter();
+        match (lines.last_mut(), second.next()) {
+            (Some(last), Some(first)) => {
+                last.push_str(&self.separator);
+                last.push_str(&first);
+            }
+            (None, Some(first)) => lines.push(first),
+            (_, None) => {}
+        }
+        lines.extend(second);
+        lines
+    }
+
+    /// Uses the shorter Label of either Formatter, if at least one of them has one
+    fn format_short(&self, id: &ID, value: &T) -> Option<String> {
+        let (first, second) = match (
+            self.first.format_short(id, value),
+            self.second.format_short(id, value),
+        ) {
+            (None, None) => return None,
+            (first, second) => (
+                first.unwrap_or_else(|| self.first.format_node(id, value)),
+                second.unwrap_or_else(|| self.second.format_node(id, value)),
+            ),
+        };
+        Some(format!("{first}{}{second}", self.separator))
+    }
+
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        let mut issues = self.first.take_issues();
+        issues.extend(self.second.take_issues());
+        issues
+    }
+}
+
+/// Limits the Labels of another Formatter to a maximum Width, replacing the end of longer Labels
+/// with `…`
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, Truncate, ValueFormatter};
+///
+/// let config: Config<usize, &str> = Config::new(Truncate::new(ValueFormatter::new(), 10), 3);
+/// ```
+pub struct Truncate<F> {
+    inner: F,
+    max_width: usize,
+}
+
+impl<F> Truncate<F> {
+    /// Creates a new Instance of the Formatter, which limits the Labels of `inner` to at most
+    /// `max_width` Glyphs
+    pub fn new(inner: F, max_width: usize) -> Self {
+        Self { inner, max_width }
+    }
+}
+
+impl<ID, T, F> NodeFormat<ID, T> for Truncate<F>
+where
+    F: NodeFormat<ID, T>,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        names::truncate(self.inner.format_node(id, value), self.max_width)
+    }
+
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        self.inner
+            .format_node_lines(id, value)
+            .into_iter()
+            .map(|line| names::truncate(line, self.max_width))
+            .collect()
+    }
+
+    fn format_short(&self, id: &ID, value: &T) -> Option<String> {
+        let short = self.inner.format_short(id, value)?;
+        Some(names::truncate(short, self.max_width))
+    }
+
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        self.inner.take_issues()
+    }
+}
+
+/// Pads or clips the Labels of another Formatter to a fixed Width, so that all the Nodes occupy
+/// the same number of Columns
+///
+/// Longer Labels are cut off like with [`Truncate`] and shorter ones are padded with spaces.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, IDFormatter, PadTo};
+///
+/// let config: Config<usize, &str> = Config::new(PadTo::new(IDFormatter::new(), 6), 3);
+/// ```
+pub struct PadTo<F> {
+    inner: F,
+    width: usize,
+}
+
+impl<F> PadTo<F> {
+    /// Creates a new Instance of the Formatter, which makes every Label of `inner` exactly `width`
+    /// Glyphs wide
+    pub fn new(inner: F, width: usize) -> Self {
+        Self { inner, width }
+    }
+
+    fn pad(&self, label: String) -> String {
+        let label = names::truncate(label, self.width);
+        let padding = self.width - names::width(&label);
+        format!("{label}{:padding$}", "")
+    }
+}
+
+impl<ID, T, F> NodeFormat<ID, T> for PadTo<F>
+where
+    F: NodeFormat<ID, T>,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        self.pad(self.inner.format_node(id, value))
+    }
+
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        self.inner
+            .format_node_lines(id, value)
+            .into_iter()
+            .map(|line| self.pad(line))
+            .collect()
+    }
+
+    fn format_short(&self, id: &ID, value: &T) -> Option<String> {
+        let short = self.inner.format_short(id, value)?;
+        Some(self.pad(short))
+    }
+
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        self.inner.take_issues()
+    }
+}
+
+/// Caches the Labels of another Formatter, so that every Node is only formatted once until its
+/// Label is invalidated using the [`LabelCache`]
+///
+/// This is useful when the same Graph is displayed repeatedly and formatting the Nodes is
+/// expensive.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Cached, Config, DirectedGraph, ValueFormatter};
+///
+/// let nfmt = Cached::new(ValueFormatter::new());
+/// let cache = nfmt.cache();
+/// let config = Config::new(nfmt, 3);
+///
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+///
+/// termgraph::display(&graph, &config);
+///
+/// // Only the Node 1 is formatted again
+/// cache.invalidate(&1);
+/// termgraph::display(&graph, &config);
+/// ```
+pub struct Cached<ID, F> {
+    inner: F,
+    cache: LabelCache<ID>,
+}
+
+/// A Handle to the Labels cached by a [`Cached`] Formatter
+pub struct LabelCache<ID> {
+    labels: Rc<RefCell<HashMap<ID, Vec<String>>>>,
+}
+
+impl<ID, F> Cached<ID, F> {
+    /// Creates a new Instance of the Formatter, which caches the Labels of `inner`
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            cache: LabelCache {
+                labels: Rc::new(RefCell::new(HashMap::new())),
+            },
+        }
+    }
+
+    /// Returns a Handle to the Cache, which can be used to invalidate Labels after the Formatter
+    /// was moved into a [`Config`](crate::Config)
+    pub fn cache(&self) -> LabelCache<ID> {
+        self.cache.clone()
+    }
+}
+
+impl<ID> LabelCache<ID>
+where
+    ID: Hash + Eq,
+{
+    /// Removes the cached Label of the given Node, so it is formatted again the next time
+    pub fn invalidate(&self, id: &ID) {
+        self.labels.borrow_mut().remove(id);
+    }
+
+    /// Removes all the cached Labels
+    pub fn clear(&self) {
+        self.labels.borrow_mut().clear();
+    }
+}
+
+impl<ID> Clone for LabelCache<ID> {
+    fn clone(&self) -> Self {
+        Self {
+            labels: self.labels.clone(),
+        }
+    }
+}
+
+impl<ID, T, F> NodeFormat<ID, T> for Cached<ID, F>
+where
+    ID: Hash + Eq + Clone,
+    F: NodeFormat<ID, T>,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        self.format_node_lines(id, value).join("\n")
+    }
+
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        if let Some(lines) = self.cache.labels.borrow().get(id) {
+            return lines.clone();
+        }
+
+        let lines = self.inner.format_node_lines(id, value);
+        self.cache
+            .labels
+            .borrow_mut()
+            .insert(id.clone(), lines.clone());
+        lines
+    }
+
+    fn format_short(&self, id: &ID, value: &T) -> Option<String> {
+        self.inner.format_short(id, value)
+    }
+
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        self.inner.take_issues()
+    }
+}
+
+/// Removes ANSI Escape-Sequences and basic Markdown emphasis from the Labels of another Formatter
+///
+/// This is useful for Labels taken from log lines, that already contain styling, which would
+/// otherwise corrupt the output or take up space.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, StripMarkup, ValueFormatter};
+///
+/// let config: Config<usize, &str> = Config::new(StripMarkup::new(ValueFormatter::new()), 3);
+/// ```
+pub struct StripMarkup<F> {
+    inner: F,
+}
+
+impl<F> StripMarkup<F> {
+    /// Creates a new Instance of the Formatter, which strips the Markup from the Labels of `inner`
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+}
+
+impl<ID, T, F> NodeFormat<ID, T> for StripMarkup<F>
+where
+    F: NodeFormat<ID, T>,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        strip_markdown(&strip_ansi(&self.inner.format_node(id, value)))
+    }
+
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        self.inner
+            .format_node_lines(id, value)
+            .into_iter()
+            .map(|line| strip_markdown(&strip_ansi(&line)))
+            .collect()
+    }
+
+    fn format_short(&self, id: &ID, value: &T) -> Option<String> {
+        let short = self.inner.format_short(id, value)?;
+        Some(strip_markdown(&strip_ansi(&short)))
+    }
+
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        self.inner.take_issues()
+    }
+}
+
+/// Removes the CSI (`\x1b[..m`) and OSC (`\x1b]..\x07`) Escape-Sequences
+pub(crate) fn strip_ansi(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // The Parameters are ended by a single Byte in the range `@` to `~`
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // Ended by either BEL or ST (`\x1b\\`)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Removes the Delimiters of Markdown emphasis, strikethrough and inline code.
+///
+/// A Run of `*` or `_` is only removed, if it is at the start or end of a Word, so that things
+/// like `snake_case` or `a * b` are kept as is.
+fn strip_markdown(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let is_delim = |c: char| matches!(c, '*' | '_' | '~' | '`');
+    let is_boundary = |c: Option<&char>| {
+        c.is_none_or(|c| c.is_whitespace() || (c.is_ascii_punctuation() && !is_delim(*c)))
+    };
+
+    let mut result = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if !is_delim(c) {
+            result.push(c);
+            i += 1;
+            continue;
+        }
+
+        let run = chars[i..].iter().take_while(|r| **r == c).count();
+        let before = i.checked_sub(1).and_then(|b| chars.get(b));
+        let after = chars.get(i + run);
+
+        let remove = match c {
+            '`' => true,
+            '~' => run == 2,
+            _ => is_boundary(before) != is_boundary(after),
+        };
+        if !remove {
+            result.extend(&chars[i..i + run]);
+        }
+        i += run;
+    }
+    result
+}
+
+/// A Problem with a Label, that was found and fixed by the [`Validate`] Formatter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelIssue {
+    /// The Label contained the given Control-Character, which was replaced with a visible
+    /// Placeholder
+    ControlCharacter(char),
+    /// A Line of the Label was wider than the allowed maximum and was cut off
+    TooWide {
+        /// The Width of the Line
+        width: usize,
+        /// The maximum allowed Width
+        max_width: usize,
+    },
+}
+
+/// Checks the Labels of another Formatter and fixes any problems that would corrupt the output
+///
+/// Control-Characters, like Escape-Sequences from third-party data, are replaced with visible
+/// Placeholders (`\x1b` becomes `␛`) and Lines wider than the [maximum
+/// width](Validate::max_width) are cut off. Every problem found is reported as a
+/// [`LayoutWarning::InvalidLabel`](crate::LayoutWarning::InvalidLabel) to the Hook set using
+/// [`Config::on_warning`](crate::Config::on_warning).
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, LayoutWarning, Validate, ValueFormatter};
+///
+/// let config: Config<usize, &str> = Config::new(Validate::new(ValueFormatter::new()).max_width(20), 3)
+///     .on_warning(|warning| {
+///         if let LayoutWarning::InvalidLabel { node, issue } = warning {
+///             eprintln!("Label of {node}: {issue:?}");
+///         }
+///     });
+/// ```
+pub struct Validate<F> {
+    inner: F,
+    max_width: Option<usize>,
+    issues: RefCell<Vec<LabelIssue>>,
+}
+
+impl<F> Validate<F> {
+    /// Creates a new Instance of the Formatter, which validates the Labels of `inner`
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            max_width: None,
+            issues: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Sets the maximum Width of every Line of a Label
+    #[must_use]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    fn validate(&self, line: String) -> String {
+        let warn = |issue| self.issues.borrow_mut().push(issue);
+
+        let line: String = line
+            .chars()
+            .map(|c| match placeholder(c) {
+                Some(p) => {
+                    warn(LabelIssue::ControlCharacter(c));
+                    p
+                }
+                None => c,
+            })
+            .collect();
+
+        match self.max_width {
+            Some(max_width) if names::width(&line) > max_width => {
+                warn(LabelIssue::TooWide {
+                    width: names::width(&line),
+                    max_width,
+                });
+                names::truncate(line, max_width)
+            }
+            _ => line,
+        }
+    }
+}
+
+/// The visible Placeholder for a Control-Character, Line-Breaks are not replaced as they split a
+/// Label into multiple Lines
+fn placeholder(c: char) -> Option<char> {
+    match c {
+        '\n' => None,
+        '\0'..='\x1f' => char::from_u32(0x2400 + c as u32),
+        '\x7f' => Some('␡'),
+        c if c.is_control() => Some('�'),
+        _ => None,
+    }
+}
+
+impl<ID, T, F> NodeFormat<ID, T> for Validate<F>
+where
+    F: NodeFormat<ID, T>,
+{
+    fn format_node(&self, id: &ID, value: &T) -> String {
+        self.issues.borrow_mut().clear();
+        self.validate(self.inner.format_node(id, value))
+    }
+
+    fn format_node_lines(&self, id: &ID, value: &T) -> Vec<String> {
+        self.issues.borrow_mut().clear();
+        self.inner
+            .format_node_lines(id, value)
+            .into_iter()
+            .map(|line| self.validate(line))
+            .collect()
+    }
+
+    fn format_short(&self, id: &ID, value: &T) -> Option<String> {
+        self.issues.borrow_mut().clear();
+        let short = self.inner.format_short(id, value)?;
+        Some(self.validate(short))
+    }
+
+    fn take_issues(&self) -> Vec<LabelIssue> {
+        self.issues.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id_value_formatter() {
+        assert_eq!(
+            "(0: first)",
+            IDValueFormatter::new().format_node(&0, &"first")
+        );
+        assert_eq!(
+            "(0 = first)",
+            IDValueFormatter::with_separator(" = ").format_node(&0, &"first")
+        );
+    }
+
+    #[test]
+    fn default_formatter() {
+        assert_eq!("(first)", DefaultFormatter::new().format_node(&0, &"first"));
+        assert_eq!("(0)", DefaultFormatter::new().format_node(&0, &""));
+
+        // Any Value implementing Display can be formatted
+        struct Task(&'static str);
+        impl Display for Task {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "task {}", self.0)
+            }
+        }
+        assert_eq!(
+            "(task a)",
+            DefaultFormatter::new().format_node(&0, &Task("a"))
+        );
+    }
+
+    #[test]
+    fn debug_formatter() {
+        #[derive(Debug)]
+        enum Op {
+            Add,
+        }
+
+        assert_eq!("(Add)", DebugFormatter::new().format_node(&0, &Op::Add));
+        assert_eq!(
+            "(\"first\")",
+            DebugFormatter::new().format_node(&0, &"first")
+        );
+    }
+
+    #[test]
+    fn pad_formatter() {
+        let nfmt = PadTo::new(ValueFormatter::new(), 5);
+        assert_eq!("(a)  ", nfmt.format_node(&0, &"a"));
+        assert_eq!("(abc…", nfmt.format_node(&0, &"abcdef"));
+        assert_eq!(None, nfmt.format_short(&0, &"abcdef"));
+
+        let nfmt = PadTo::new(IDValueFormatter::new(), 5);
+        assert_eq!(Some("(0)  ".to_string()), nfmt.format_short(&0, &"abcdef"));
+    }
+
+    #[test]
+    fn cached_formatter() {
+        struct Counting(std::cell::Cell<usize>);
+        impl NodeFormat<usize, &str> for Counting {
+            fn format_node(&self, _: &usize, value: &&str) -> String {
+                self.0.set(self.0.get() + 1);
+                value.to_string()
+            }
+        }
+
+        let nfmt = Cached::new(Counting(std::cell::Cell::new(0)));
+        let cache = nfmt.cache();
+
+        assert_eq!("first", nfmt.format_node(&0, &"first"));
+        assert_eq!("first", nfmt.format_node(&0, &"changed"));
+        assert_eq!(1, nfmt.inner.0.get());
+
+        cache.invalidate(&0);
+        assert_eq!("changed", nfmt.format_node(&0, &"changed"));
+        assert_eq!(2, nfmt.inner.0.get());
+    }
+
+    #[test]
+    fn validate_formatter() {
+        let nfmt = Validate::new(ValueFormatter::new()).max_width(6);
+        let issues = || NodeFormat::<usize, &str>::take_issues(&nfmt);
+
+        assert_eq!("(ok)", nfmt.format_node(&0, &"ok"));
+        assert_eq!(Vec::<LabelIssue>::new(), issues());
+
+        assert_eq!("(␛[1m…", nfmt.format_node(&1, &"\x1b[1mbold"));
+        assert_eq!(
+            vec![
+                LabelIssue::ControlCharacter('\x1b'),
+                LabelIssue::TooWide {
+                    width: 10,
+                    max_width: 6
+                }
+            ],
+            issues()
+        );
+        assert_eq!(Vec::<LabelIssue>::new(), issues());
+    }
+
+    #[test]
+    fn strip_markup() {
+        let nfmt = StripMarkup::new(ValueFormatter::new());
+        assert_eq!("(error)", nfmt.format_node(&0, &"\x1b[1;31merror\x1b[0m"));
+        assert_eq!(
+            "(link)",
+            nfmt.format_node(&0, &"\x1b]8;;https://a.b\x07link")
+        );
+        assert_eq!(
+            "(bold and code)",
+            nfmt.format_node(&0, &"**bold** and `code`")
+        );
+        assert_eq!(
+            "(snake_case a * b)",
+            nfmt.format_node(&0, &"snake_case a * b")
+        );
+        assert_eq!("(old new)", nfmt.format_node(&0, &"~~old~~ _new_"));
+    }
+
+    #[test]
+    fn join_formatters() {
+        let nfmt = Join::new(IDFormatter::new(), ValueFormatter::new(), " ");
+        assert_eq!("(0) (first)", nfmt.format_node(&0, &"first"));
+        assert_eq!(None, nfmt.format_short(&0, &"first"));
+
+        struct Multiline {}
+        impl NodeFormat<usize, &str> for Multiline {
+            fn format_node(&self, _: &usize, value: &&str) -> String {
+                value.to_string()
+            }
+            fn format_node_lines(&self, _: &usize, value: &&str) -> Vec<String> {
+                value.split(' ').map(str::to_string).collect()
+            }
+            fn format_short(&self, _: &usize, _: &&str) -> Option<String> {
+                Some("…".to_string())
+            }
+        }
+
+        let nfmt = Join::new(IDFormatter::new(), Multiline {}, ": ");
+        assert_eq!(
+            vec!["(0): first".to_string(), "second".to_string()],
+            nfmt.format_node_lines(&0, &"first second")
+        );
+        assert_eq!(Some("(0): …".to_string()), nfmt.format_short(&0, &"first"));
+    }
+
+    #[test]
+    fn truncate_formatter() {
+        let nfmt = Truncate::new(ValueFormatter::new(), 5);
+        assert_eq!("(abc)", nfmt.format_node(&0, &"abc"));
+        assert_eq!("(abc…", nfmt.format_node(&0, &"abcdef"));
+    }
+}
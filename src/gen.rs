@@ -0,0 +1,136 @@
+//! Deterministic Generators for random Graphs, which are available with the `gen` Feature.
+//!
+//! Every Generator is parameterized by a Seed and always produces the same Graph for the same
+//! Parameters, which makes it easy to reproduce a broken Layout by only sharing the Parameters.
+//! The Nodes are numbered starting at 0 and have no Values.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{gen, Config, IDFormatter};
+//!
+//! let graph = gen::layered_dag(4, 3, 0.5, 42);
+//! assert_eq!(gen::layered_dag(4, 3, 0.5, 42), graph);
+//!
+//! termgraph::display(&graph, &Config::new(IDFormatter::new(), 3));
+//! ```
+
+use crate::{ordering::Rng, DirectedGraph};
+
+/// Generates a random acyclic Graph, where every pair of Nodes is connected with the given
+/// Probability. The Edges follow a random Order of the Nodes, so the IDs do not hint at the
+/// Structure of the Graph.
+pub fn random_dag(nodes: usize, edge_probability: f64, seed: u64) -> DirectedGraph<usize, ()> {
+    let mut rng = Rng::new(seed);
+    let mut order: Vec<usize> = (0..nodes).collect();
+    rng.shuffle(&mut order);
+
+    let mut graph = empty(nodes);
+    for (index, src) in order.iter().enumerate() {
+        for target in &order[index + 1..] {
+            if rng.chance(edge_probability) {
+                graph.add_edges([(*src, *target)]);
+            }
+        }
+    }
+    graph
+}
+
+/// Generates an acyclic Graph made up of `layers` Layers with `width` Nodes each, where the Nodes
+/// of neighbouring Layers are connected with the given Probability. Every Node below the first
+/// Layer has at least one Edge leading to it.
+///
+/// The Nodes of the first Layer are numbered `0..width`, the ones of the second Layer
+/// `width..2 * width` and so on.
+pub fn layered_dag(
+    layers: usize,
+    width: usize,
+    edge_probability: f64,
+    seed: u64,
+) -> DirectedGraph<usize, ()> {
+    let mut rng = Rng::new(seed);
+    let mut graph = empty(layers * width);
+    for layer in 1..layers {
+        let above = (layer - 1) * width;
+        for target in layer * width..(layer + 1) * width {
+            let mut sources: Vec<usize> = (above..above + width)
+                .filter(|_| rng.chance(edge_probability))
+                .collect();
+            if sources.is_empty() {
+                sources.push(above + rng.below(width));
+            }
+            graph.add_edges(sources.into_iter().map(|src| (src, target)));
+        }
+    }
+    graph
+}
+
+/// Generates a random Tree with the Root 0, where every other Node is the Child of a random Node
+/// with a lower ID
+pub fn tree(nodes: usize, seed: u64) -> DirectedGraph<usize, ()> {
+    let mut rng = Rng::new(seed);
+    let mut graph = empty(nodes);
+    graph.add_edges((1..nodes).map(|child| (rng.below(child), child)));
+    graph
+}
+
+/// Generates a [random acyclic Graph](random_dag) and adds `back_edges` Edges to it, where every
+/// one of them closes a Cycle. Fewer Edges are added, if there are not enough Paths in the Graph
+/// to close that many Cycles.
+pub fn cyclic(
+    nodes: usize,
+    edge_probability: f64,
+    back_edges: usize,
+    seed: u64,
+) -> DirectedGraph<usize, ()> {
+    let mut graph = random_dag(nodes, edge_probability, seed);
+    let mut rng = Rng::new(seed.wrapping_add(1));
+
+    // Walks along random Edges from a random Node and leads the last Node back to the first one
+    let mut added = 0;
+    for _ in 0..back_edges.saturating_mul(16) {
+        if added == back_edges || nodes == 0 {
+            break;
+        }
+        let start = rng.below(nodes);
+        let mut current = start;
+        for _ in 0..=rng.below(nodes) {
+            let mut successors: Vec<usize> = graph.successors(&current).copied().collect();
+            if successors.is_empty() {
+                break;
+            }
+            successors.sort_unstable();
+            current = successors[rng.below(successors.len())];
+        }
+
+        if current != start && !graph.successors(&current).any(|t| *t == start) {
+            graph.add_edges([(current, start)]);
+            added += 1;
+        }
+    }
+    graph
+}
+
+/// A Graph with the given number of Nodes and no Edges
+fn empty(nodes: usize) -> DirectedGraph<usize, ()> {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..nodes).map(|id| (id, ())));
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        assert_eq!(random_dag(20, 0.2, 7), random_dag(20, 0.2, 7));
+        assert_eq!(tree(20, 7), tree(20, 7));
+        assert_eq!(cyclic(20, 0.2, 3, 7), cyclic(20, 0.2, 3, 7));
+    }
+
+    #[test]
+    fn cycles() {
+        assert!(random_dag(30, 0.3, 1).to_acyclic().1.is_empty());
+        assert!(!cyclic(30, 0.3, 2, 1).to_acyclic().1.is_empty());
+    }
+}
@@ -1,10 +1,14 @@
 use std::{
-    collections::{HashMap, HashSet},
-    fmt::Debug,
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fmt::{Debug, Display},
     hash::Hash,
+    time::Instant,
 };
 
-use crate::acyclic::AcyclicDirectedGraph;
+use crate::{
+    acyclic::AcyclicDirectedGraph,
+    config::{OrderFn, RankFn, WeightFn},
+};
 
 mod feedback_arc_set;
 mod tarjan;
@@ -23,10 +27,17 @@ mod tarjan;
 /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
 /// graph.add_edges([(0, 1), (1, 2)]);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DirectedGraph<ID, T> {
     nodes: HashMap<ID, T>,
     edges: HashMap<ID, HashSet<ID>>,
+    /// The Edges that should preferably be reversed to break the Cycles, with the Order they were
+    /// marked in
+    back_edges: HashMap<(ID, ID), usize>,
+    /// The Order the next marked Back-Edge gets
+    next_back_edge: usize,
+    /// The Labels of the Edges, by their Source and Target
+    edge_labels: HashMap<ID, HashMap<ID, String>>,
 }
 
 impl<ID, T> DirectedGraph<ID, T>
@@ -38,11 +49,31 @@ where
         Self {
             nodes: HashMap::new(),
             edges: HashMap::new(),
+            back_edges: HashMap::new(),
+            next_back_edge: 0,
+            edge_labels: HashMap::new(),
         }
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
-        self.nodes.is_empty()
+    /// Creates a new empty Graph with preallocated space for at least `nodes` Nodes and Edges
+    /// originating from at least `edges` different Nodes
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::with_capacity(3, 2);
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2)]);
+    /// ```
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self {
+            nodes: HashMap::with_capacity(nodes),
+            edges: HashMap::with_capacity(edges),
+            back_edges: HashMap::new(),
+            next_back_edge: 0,
+            edge_labels: HashMap::new(),
+        }
     }
 
     /// Adds the Nodes to the Graph
@@ -50,11 +81,65 @@ where
     where
         I: IntoIterator<Item = (ID, T)>,
     {
+        let iter = iter.into_iter();
+        self.nodes.reserve(iter.size_hint().0);
+
         for (id, e) in iter {
             self.nodes.insert(id, e);
         }
     }
 
+    /// Adds a single Node to the Graph, unlike [`DirectedGraph::add_nodes`] this does not replace
+    /// an existing Node with the same ID
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::{DirectedGraph, GraphError};
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// assert_eq!(Ok(()), graph.add_node(0, "first"));
+    /// assert_eq!(Err(GraphError::DuplicateNode(0)), graph.add_node(0, "again"));
+    /// ```
+    pub fn add_node(&mut self, id: ID, value: T) -> Result<(), GraphError<ID>> {
+        if self.nodes.contains_key(&id) {
+            return Err(GraphError::DuplicateNode(id));
+        }
+        self.nodes.insert(id, value);
+        Ok(())
+    }
+
+    /// Adds a single Edge to the Graph, after checking that both of its Nodes are part of the
+    /// Graph and that the Edge does not exist yet
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::{DirectedGraph, GraphError};
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second")]);
+    ///
+    /// assert_eq!(Ok(()), graph.add_edge(0, 1));
+    /// assert_eq!(Err(GraphError::DuplicateEdge(0, 1)), graph.add_edge(0, 1));
+    /// assert_eq!(Err(GraphError::MissingNode(2)), graph.add_edge(1, 2));
+    /// ```
+    pub fn add_edge(&mut self, from: ID, to: ID) -> Result<(), GraphError<ID>> {
+        if !self.nodes.contains_key(&from) {
+            return Err(GraphError::MissingNode(from));
+        }
+        if !self.nodes.contains_key(&to) {
+            return Err(GraphError::MissingNode(to));
+        }
+        if self
+            .edges
+            .get(&from)
+            .is_some_and(|targets| targets.contains(&to))
+        {
+            return Err(GraphError::DuplicateEdge(from, to));
+        }
+        self.edges.entry(from).or_default().insert(to);
+        Ok(())
+    }
+
     /// Adds the given Edges to the Graph
     ///
     /// # Input
@@ -63,6 +148,11 @@ where
     where
         I: IntoIterator<Item = (ID, ID)>,
     {
+        let iter = iter.into_iter();
+        // The edges are grouped by their source, so this might reserve more than is actually needed
+        // but avoids rehashing the map for most bulk insertions
+        self.edges.reserve(iter.size_hint().0);
+
         for (from, to) in iter {
             let entry = self.edges.entry(from);
             let value = entry.or_insert_with(|| HashSet::new());
@@ -70,37 +160,1055 @@ where
         }
     }
 
+    /// Adds the given Edges to the Graph, like [`DirectedGraph::add_edges`], where every Edge is
+    /// displayed with its Label next to it, like the Name of a Transition in a State-Machine.
+    ///
+    /// The Label is placed on a horizontal Part of the Edge, if it is long enough, and otherwise
+    /// next to its vertical Part. If there is no free Space next to the Edge, the Label is left
+    /// out, so a larger [Gap between the Edges](crate::Config::edge_gap) makes room for more of
+    /// them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("idle", ()), ("running", ()), ("done", ())]);
+    /// graph.add_labeled_edges([("idle", "running", "start"), ("running", "done", "finish")]);
+    /// assert_eq!(Some("start"), graph.edge_label(&"idle", &"running"));
+    ///
+    /// termgraph::display(&graph, &Config::new(IDFormatter::new(), 3));
+    /// ```
+    pub fn add_labeled_edges<I, L>(&mut self, iter: I)
+    where
+        ID: Clone,
+        I: IntoIterator<Item = (ID, ID, L)>,
+        L: Into<String>,
+    {
+        for (from, to, label) in iter {
+            self.edge_labels
+                .entry(from.clone())
+                .or_default()
+                .insert(to.clone(), label.into());
+            self.add_edges([(from, to)]);
+        }
+    }
+
+    /// The Label of the Edge from `from` to `to`, if it was added with a Label
+    pub fn edge_label(&self, from: &ID, to: &ID) -> Option<&str> {
+        self.edge_labels.get(from)?.get(to).map(String::as_str)
+    }
+
+    /// Adds the given Edges to the Graph like [`DirectedGraph::add_edges`], but only after checking
+    /// that all of their Nodes are part of the Graph. If any of them is not, none of the Edges are
+    /// added and all the Edges referring to missing Nodes are returned in the Error.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::{DirectedGraph, GraphError};
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second")]);
+    ///
+    /// assert_eq!(Ok(()), graph.try_add_edges([(0, 1)]));
+    /// assert_eq!(
+    ///     Err(GraphError::MissingEdgeNodes(vec![(1, 2), (3, 0)])),
+    ///     graph.try_add_edges([(1, 0), (1, 2), (3, 0)])
+    /// );
+    /// assert_eq!(0, graph.successors(&1).count());
+    /// ```
+    pub fn try_add_edges<I>(&mut self, iter: I) -> Result<(), GraphError<ID>>
+    where
+        I: IntoIterator<Item = (ID, ID)>,
+    {
+        let (valid, missing): (Vec<_>, Vec<_>) = iter
+            .into_iter()
+            .partition(|(from, to)| self.nodes.contains_key(from) && self.nodes.contains_key(to));
+        if !missing.is_empty() {
+            return Err(GraphError::MissingEdgeNodes(missing));
+        }
+        self.add_edges(valid);
+        Ok(())
+    }
+
+    /// Adds all the Nodes and Edges of the other Graph to this one, together with the Labels of
+    /// its Edges and its [preferred Back-Edges](DirectedGraph::prefer_back_edge).
+    ///
+    /// Nodes that are part of both Graphs keep their current Value, see
+    /// [`DirectedGraph::merge_with`] to resolve these Conflicts differently. Edges that are
+    /// labeled in both Graphs keep their current Label as well.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut parser = DirectedGraph::new();
+    /// parser.add_nodes([("lexer", "parser"), ("ast", "parser")]);
+    /// parser.add_edges([("lexer", "ast")]);
+    ///
+    /// let mut checker = DirectedGraph::new();
+    /// checker.add_nodes([("ast", "checker"), ("types", "checker")]);
+    /// checker.add_edges([("ast", "types")]);
+    ///
+    /// parser.merge(checker);
+    /// assert_eq!(vec![&"lexer", &"ast", &"types"], parser.bfs(&"lexer"));
+    /// assert_eq!(Some(&"parser"), parser.get(&"ast"));
+    /// ```
+    pub fn merge(&mut self, other: Self) {
+        self.merge_with(other, |_, _, _| {});
+    }
+
+    /// Adds all the Nodes and Edges of the other Graph to this one, like
+    /// [`DirectedGraph::merge`], but calls `resolve` with the ID, the current Value and the Value
+    /// of the other Graph for every Node that is part of both Graphs. Edges that are labeled in
+    /// both Graphs always keep their current Label.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut first = DirectedGraph::new();
+    /// first.add_nodes([(0, 1), (1, 2)]);
+    ///
+    /// let mut second = DirectedGraph::new();
+    /// second.add_nodes([(1, 5), (2, 3)]);
+    ///
+    /// // Sum up the Values of the shared Nodes
+    /// first.merge_with(second, |_, current, other| *current += other);
+    /// assert_eq!(Some(&7), first.get(&1));
+    /// ```
+    pub fn merge_with<F>(&mut self, other: Self, mut resolve: F)
+    where
+        F: FnMut(&ID, &mut T, T),
+    {
+        for (id, value) in other.nodes {
+            match self.nodes.get_mut(&id) {
+                Some(current) => resolve(&id, current, value),
+                None => {
+                    self.nodes.insert(id, value);
+                }
+            }
+        }
+        for (src, targets) in other.edges {
+            self.edges.entry(src).or_default().extend(targets);
+        }
+        for (src, labels) in other.edge_labels {
+            let current = self.edge_labels.entry(src).or_default();
+            for (target, label) in labels {
+                current.entry(target).or_insert(label);
+            }
+        }
+        let mut back_edges: Vec<_> = other.back_edges.into_iter().collect();
+        back_edges.sort_unstable_by_key(|(_, order)| *order);
+        for ((from, to), _) in back_edges {
+            self.prefer_back_edge(from, to);
+        }
+    }
+
+    /// Removes all the Nodes for which the Predicate returns `false`, together with all the Edges
+    /// from or to them. Edges between Nodes that were never added to the Graph are removed as well.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("main", "app"), ("parse", "app"), ("alloc", "std")]);
+    /// graph.add_edges([("main", "parse"), ("main", "alloc"), ("parse", "alloc")]);
+    ///
+    /// graph.retain_nodes(|_, krate| *krate != "std");
+    /// assert_eq!(vec![&"main", &"parse"], graph.bfs(&"main"));
+    /// ```
+    pub fn retain_nodes<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&ID, &T) -> bool,
+    {
+        self.nodes.retain(|id, value| predicate(id, value));
+
+        let nodes = &self.nodes;
+        self.edges.retain(|src, targets| {
+            targets.retain(|target| nodes.contains_key(target));
+            nodes.contains_key(src) && !targets.is_empty()
+        });
+        self.edge_labels.retain(|src, targets| {
+            targets.retain(|target, _| nodes.contains_key(target));
+            nodes.contains_key(src) && !targets.is_empty()
+        });
+        self.back_edges
+            .retain(|(from, to), _| nodes.contains_key(from) && nodes.contains_key(to));
+    }
+
+    /// Removes the Node from the Graph, together with all the Edges from or to it, and returns its
+    /// Value. Returns `None`, if the Node is not part of the Graph.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2), (0, 2)]);
+    ///
+    /// assert_eq!(Some("second"), graph.remove_node(&1));
+    /// assert_eq!(None, graph.remove_node(&1));
+    /// assert_eq!(vec![&0, &2], graph.bfs(&0));
+    /// ```
+    pub fn remove_node(&mut self, id: &ID) -> Option<T> {
+        let value = self.nodes.remove(id)?;
+
+        for targets in self.edges.values_mut() {
+            targets.remove(id);
+        }
+        self.edges.remove(id);
+        self.edges.retain(|_, targets| !targets.is_empty());
+
+        for targets in self.edge_labels.values_mut() {
+            targets.remove(id);
+        }
+        self.edge_labels.remove(id);
+        self.edge_labels.retain(|_, targets| !targets.is_empty());
+
+        self.back_edges
+            .retain(|(from, to), _| from != id && to != id);
+        Some(value)
+    }
+
+    /// Removes the Edge from `from` to `to` together with its Label, and returns whether the Edge
+    /// was part of the Graph
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second")]);
+    /// graph.add_edges([(0, 1)]);
+    ///
+    /// assert!(graph.remove_edge(&0, &1));
+    /// assert!(!graph.remove_edge(&0, &1));
+    /// assert_eq!(vec![&0], graph.bfs(&0));
+    /// ```
+    pub fn remove_edge(&mut self, from: &ID, to: &ID) -> bool {
+        let Some(targets) = self.edges.get_mut(from) else {
+            return false;
+        };
+        let removed = targets.remove(to);
+        if targets.is_empty() {
+            self.edges.remove(from);
+        }
+
+        if let Some(labels) = self.edge_labels.get_mut(from) {
+            labels.remove(to);
+            if labels.is_empty() {
+                self.edge_labels.remove(from);
+            }
+        }
+        self.back_edges.retain(|(f, t), _| f != from || t != to);
+        removed
+    }
+
+    /// Partitions the Nodes into their Strongly Connected Components, where every Node of a
+    /// Component can reach every other Node of the same Component.
+    ///
+    /// The Components are returned in reverse topological order, so every Component comes before
+    /// the Components with Edges leading to it. Nodes that are not part of any Cycle form a
+    /// Component on their own.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2), (2, 1)]);
+    ///
+    /// let sccs = graph.strongly_connected_components();
+    /// assert_eq!(2, sccs.len());
+    /// assert_eq!(vec![&0], sccs[1]);
+    /// ```
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&ID>> {
+        let anodes: HashMap<&ID, &T> = self.nodes.iter().collect();
+        let aedges: HashMap<&ID, HashSet<&ID>> = self
+            .edges
+            .iter()
+            .filter(|(src, _)| self.nodes.contains_key(*src))
+            .map(|(src, targets)| {
+                let targets = targets.iter().filter(|t| self.nodes.contains_key(*t));
+                (src, targets.collect())
+            })
+            .collect();
+
+        tarjan::sccs((&anodes, &aedges))
+            .into_iter()
+            .map(|scc| scc.into_iter().copied().collect())
+            .collect()
+    }
+
+    /// Constructs the Condensation of the Graph, in which every Strongly Connected Component is
+    /// replaced by a single Node, whose Value contains the IDs of its Members.
+    ///
+    /// The Components are numbered in topological order, so every Edge leads from a lower to a
+    /// higher Number and the resulting Graph is acyclic.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::{Config, DebugFormatter, DirectedGraph};
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2), (2, 1)]);
+    ///
+    /// let condensed = graph.condensation();
+    /// assert_eq!(Some(&vec![0]), condensed.get(&0));
+    ///
+    /// let config = Config::new(DebugFormatter::new(), 3);
+    /// termgraph::display(&condensed, &config);
+    /// ```
+    pub fn condensation(&self) -> DirectedGraph<usize, Vec<ID>>
+    where
+        ID: Clone,
+    {
+        let mut sccs = self.strongly_connected_components();
+        sccs.reverse();
+
+        let components: HashMap<&ID, usize> = sccs
+            .iter()
+            .enumerate()
+            .flat_map(|(index, scc)| scc.iter().map(move |id| (*id, index)))
+            .collect();
+
+        let mut condensed = DirectedGraph::with_capacity(sccs.len(), sccs.len());
+        condensed.add_edges(self.edges().filter_map(|(src, target)| {
+            let (src, target) = (*components.get(src)?, *components.get(target)?);
+            (src != target).then_some((src, target))
+        }));
+        condensed.add_nodes(
+            sccs.into_iter()
+                .enumerate()
+                .map(|(index, scc)| (index, scc.into_iter().cloned().collect())),
+        );
+        condensed
+    }
+
+    /// Returns the Nodes without any incoming Edges
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+    ///
+    /// assert_eq!(vec![&0], graph.roots().collect::<Vec<_>>());
+    /// ```
+    pub fn roots(&self) -> impl Iterator<Item = &ID> + '_ {
+        let targets: HashSet<&ID> = self
+            .edges()
+            .filter(|(src, _)| self.nodes.contains_key(*src))
+            .map(|(_, target)| target)
+            .collect();
+        self.nodes.keys().filter(move |id| !targets.contains(id))
+    }
+
+    /// Returns the Nodes without any outgoing Edges
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+    ///
+    /// assert_eq!(vec![&2], graph.leaves().collect::<Vec<_>>());
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = &ID> + '_ {
+        self.nodes.keys().filter(|id| {
+            !self
+                .successors(id)
+                .any(|target| self.nodes.contains_key(target))
+        })
+    }
+
+    /// Returns the Transpose of the Graph, which contains the same Nodes, but where every Edge
+    /// points in the opposite direction
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// // What every Crate depends on
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("app", ()), ("parser", ()), ("lexer", ())]);
+    /// graph.add_edges([("app", "parser"), ("parser", "lexer"), ("app", "lexer")]);
+    ///
+    /// // Who depends on the lexer
+    /// let dependents = graph.reversed();
+    /// let mut affected = dependents.bfs(&"lexer");
+    /// affected.sort();
+    /// assert_eq!(vec![&"app", &"lexer", &"parser"], affected);
+    /// ```
+    pub fn reversed(&self) -> Self
+    where
+        ID: Clone,
+        T: Clone,
+    {
+        let mut reversed = Self::with_capacity(self.nodes.len(), self.edges.len());
+        reversed.add_nodes(
+            self.nodes
+                .iter()
+                .map(|(id, value)| (id.clone(), value.clone())),
+        );
+        reversed.add_edges(
+            self.edges()
+                .map(|(src, target)| (target.clone(), src.clone())),
+        );
+        for (src, targets) in &self.edge_labels {
+            for (target, label) in targets {
+                reversed
+                    .edge_labels
+                    .entry(target.clone())
+                    .or_default()
+                    .insert(src.clone(), label.clone());
+            }
+        }
+        for (from, to) in self.ordered_back_edges() {
+            reversed.prefer_back_edge(to.clone(), from.clone());
+        }
+        reversed
+    }
+
+    /// Returns the Nodes within `depth` Edges of the given Node, following the Edges in both
+    /// Directions, in Breadth-First order starting with the Node itself. Returns an empty List if
+    /// the Node is not part of the Graph.
+    ///
+    /// This can be used to only display the Surroundings of a single Node in a large Graph, see
+    /// [`display_neighborhood`](crate::display_neighborhood).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes((0..5).map(|i| (i, ())));
+    /// graph.add_edges([(0, 1), (1, 2), (3, 2), (4, 3)]);
+    ///
+    /// let mut around = graph.neighborhood(&2, 1);
+    /// around.sort();
+    /// assert_eq!(vec![&1, &2, &3], around);
+    /// ```
+    pub fn neighborhood(&self, id: &ID, depth: usize) -> Vec<&ID> {
+        let Some(start) = self.get_key(id) else {
+            return Vec::new();
+        };
+        let mut predecessors: HashMap<&ID, Vec<&ID>> = HashMap::new();
+        for (src, target) in self.edges() {
+            predecessors.entry(target).or_default().push(src);
+        }
+
+        let mut visited: HashSet<&ID> = HashSet::from([start]);
+        let mut order = vec![start];
+        let mut current = 0;
+        for _ in 0..depth {
+            let end = order.len();
+            for index in current..end {
+                let node = order[index];
+                let neighbours = self
+                    .successors(node)
+                    .chain(predecessors.get(node).into_iter().flatten().copied());
+                for next in neighbours {
+                    if self.nodes.contains_key(next) && visited.insert(next) {
+                        order.push(next);
+                    }
+                }
+            }
+            current = end;
+        }
+        order
+    }
+
+    /// Returns the Nodes reachable from the given Node in Breadth-First order, starting with the
+    /// Node itself. Returns an empty List if the Node is not part of the Graph.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(vec![&0, &1, &2], graph.bfs(&0));
+    /// ```
+    pub fn bfs(&self, start: &ID) -> Vec<&ID> {
+        let Some(start) = self.get_key(start) else {
+            return Vec::new();
+        };
+
+        let mut visited: HashSet<&ID> = HashSet::from([start]);
+        let mut order = vec![start];
+        let mut index = 0;
+        while let Some(current) = order.get(index) {
+            let next: Vec<&ID> = self
+                .successors(current)
+                .filter(|id| self.nodes.contains_key(*id) && visited.insert(*id))
+                .collect();
+            order.extend(next);
+            index += 1;
+        }
+        order
+    }
+
+    /// Returns the Nodes reachable from the given Node in Depth-First order, starting with the
+    /// Node itself. Returns an empty List if the Node is not part of the Graph.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(vec![&0, &1, &2], graph.dfs(&0));
+    /// ```
+    pub fn dfs(&self, start: &ID) -> Vec<&ID> {
+        let Some(start) = self.get_key(start) else {
+            return Vec::new();
+        };
+
+        let mut visited: HashSet<&ID> = HashSet::new();
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            order.push(current);
+            stack.extend(
+                self.successors(current)
+                    .filter(|id| self.nodes.contains_key(*id) && !visited.contains(*id)),
+            );
+        }
+        order
+    }
+
+    /// The number of Nodes on the longest Path through the Graph, which is the number of Levels
+    /// the Graph needs at least when displayed. Cycles are broken like for the
+    /// [critical Path](DirectedGraph::critical_path).
+    ///
+    /// The actual number of Levels can be larger, as the Levels are also limited by the
+    /// [maximum number of Nodes per Layer](crate::Config::max_per_layer), see
+    /// [`Layout::compute_levels`](crate::Layout::compute_levels) for the exact Levels.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+    ///
+    /// assert_eq!(3, graph.depth());
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.critical_path(|_, _| 1, |_, _| 0).len()
+    }
+
+    /// Calculates the critical Path through the Graph, which is the Path with the largest total
+    /// Weight of its Nodes and Edges.
+    ///
+    /// The Cycles in the Graph are broken by ignoring the Edges, that would otherwise be reversed
+    /// when displaying the Graph.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("fetch", 3), ("compile", 10), ("docs", 4), ("link", 2)]);
+    /// graph.add_edges([("fetch", "compile"), ("fetch", "docs"), ("compile", "link")]);
+    ///
+    /// let path = graph.critical_path(|_, duration| *duration, |_, _| 0);
+    /// assert_eq!(vec![&"fetch", &"compile", &"link"], path);
+    /// ```
+    pub fn critical_path<N, E>(&self, node_weight: N, edge_weight: E) -> Vec<&ID>
+    where
+        N: Fn(&ID, &T) -> u64,
+        E: Fn(&ID, &ID) -> u64,
+    {
+        let (agraph, _) = self.to_acyclic_with(|_, _| true, false, None, None, None, None);
+
+        // The Nodes in topological order, so every Node comes after all of its Predecessors
+        let mut in_degrees = agraph.in_degrees();
+        let mut pending: Vec<&ID> = in_degrees
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut ordering = Vec::with_capacity(in_degrees.len());
+        while let Some(node) = pending.pop() {
+            ordering.push(node);
+            for succ in agraph.successors(node).into_iter().flatten() {
+                let degree = in_degrees.entry(succ).or_default();
+                *degree = degree.saturating_sub(1);
+                if *degree == 0 {
+                    pending.push(succ);
+                }
+            }
+        }
+
+        // The heaviest Path ending in every Node and the Predecessor on that Path
+        let mut incoming: HashMap<&ID, (u64, Option<&ID>)> = HashMap::new();
+        let mut best: Option<(u64, &ID)> = None;
+        for node in ordering {
+            let Some(value) = agraph.nodes.get(node) else {
+                continue;
+            };
+            let (before, _) = incoming.get(node).copied().unwrap_or_default();
+            let total = before + node_weight(node, value);
+            if best.is_none_or(|(b, _)| total > b) {
+                best = Some((total, node));
+            }
+
+            for succ in agraph.successors(node).into_iter().flatten() {
+                let candidate = total + edge_weight(node, succ);
+                let entry = incoming.entry(succ).or_insert((0, None));
+                if entry.1.is_none() || candidate > entry.0 {
+                    *entry = (candidate, Some(node));
+                }
+            }
+        }
+
+        let mut path: Vec<&ID> = std::iter::successors(best.map(|(_, end)| end), |node| {
+            incoming.get(node).and_then(|(_, pred)| *pred)
+        })
+        .collect();
+        path.reverse();
+        path
+    }
+
+    /// Finds the maximal Chains of Nodes, in which every Node except the last one has exactly one
+    /// outgoing Edge, which is the only incoming Edge of the next Node. Only the Nodes for which
+    /// the filter returns true are considered.
+    pub(crate) fn chains<F>(&self, filter: F) -> Vec<Vec<&ID>>
+    where
+        F: Fn(&ID, &T) -> bool,
+    {
+        let included = |id: &ID| self.nodes.get(id).is_some_and(|value| filter(id, value));
+
+        let mut in_degrees: HashMap<&ID, usize> = HashMap::new();
+        for (src, target) in self.edges() {
+            if included(src) && included(target) {
+                *in_degrees.entry(target).or_default() += 1;
+            }
+        }
+
+        // The next Node in the Chain for every Node, that links to one
+        let links: HashMap<&ID, &ID> = self
+            .nodes
+            .keys()
+            .filter(|id| included(id))
+            .filter_map(|id| {
+                let mut succs = self.successors(id).filter(|succ| included(succ));
+                match (succs.next(), succs.next()) {
+                    (Some(succ), None) if succ != id && in_degrees.get(succ) == Some(&1) => {
+                        Some((id, succ))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+        let linked: HashSet<&ID> = links.values().copied().collect();
+
+        // Every Chain starts at a Node that is not linked to by another one, so Cycles in which
+        // every Node is linked are never collapsed
+        links
+            .keys()
+            .filter(|id| !linked.contains(*id))
+            .map(|start| {
+                let mut chain = vec![*start];
+                while let Some(next) = links.get(chain[chain.len() - 1]) {
+                    chain.push(next);
+                }
+                chain
+            })
+            .collect()
+    }
+
+    /// Returns all the Nodes in the Graph together with their Values, in no particular Order
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second")]);
+    ///
+    /// let mut nodes: Vec<_> = graph.nodes().collect();
+    /// nodes.sort();
+    /// assert_eq!(vec![(&0, &"first"), (&1, &"second")], nodes);
+    /// ```
+    pub fn nodes(&self) -> impl Iterator<Item = (&ID, &T)> {
+        self.nodes.iter()
+    }
+
+    /// Returns all the Edges in the Graph as `(src, target)`, in no particular Order. This
+    /// includes Edges between Nodes that were never added to the Graph.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2)]);
+    ///
+    /// let mut edges: Vec<_> = graph.edges().collect();
+    /// edges.sort();
+    /// assert_eq!(vec![(&0, &1), (&0, &2)], edges);
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = (&ID, &ID)> {
+        self.edges
+            .iter()
+            .flat_map(|(src, targets)| targets.iter().map(move |t| (src, t)))
+    }
+
+    /// Returns all the Labels of the Edges as `(src, target, label)`
+    pub(crate) fn edge_label_entries(&self) -> impl Iterator<Item = (&ID, &ID, &str)> {
+        self.edge_labels.iter().flat_map(|(src, targets)| {
+            targets
+                .iter()
+                .map(move |(t, label)| (src, t, label.as_str()))
+        })
+    }
+
+    /// Marks the Edge from `from` to `to` as a known Back-Edge, like the Edge from the end of a
+    /// Loop back to its Header. These Edges are reversed to break the Cycles in the Graph, before
+    /// any other Edge is considered, which makes the Layout of Graphs with many Loops predictable.
+    ///
+    /// A marked Edge is only reversed if it actually closes a Cycle. If only some of the marked
+    /// Edges of a Cycle have to be reversed, the ones marked first are preferred. The Hints are not
+    /// used, if the Levels are determined by [Ranks](crate::Config::rank).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::{Config, DirectedGraph, IDFormatter};
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([("entry", ()), ("header", ()), ("body", ()), ("exit", ())]);
+    /// graph.add_edges([
+    ///     ("entry", "header"),
+    ///     ("header", "body"),
+    ///     ("body", "header"),
+    ///     ("header", "exit"),
+    /// ]);
+    /// graph.prefer_back_edge("body", "header");
+    ///
+    /// termgraph::display(&graph, &Config::new(IDFormatter::new(), 3));
+    /// ```
+    pub fn prefer_back_edge(&mut self, from: ID, to: ID) {
+        if let Entry::Vacant(entry) = self.back_edges.entry((from, to)) {
+            entry.insert(self.next_back_edge);
+            self.next_back_edge += 1;
+        }
+    }
+
+    /// The marked Back-Edges, in the Order they were marked in
+    fn ordered_back_edges(&self) -> Vec<&(ID, ID)> {
+        let mut back_edges: Vec<_> = self.back_edges.iter().collect();
+        back_edges.sort_unstable_by_key(|(_, order)| **order);
+        back_edges.into_iter().map(|(edge, _)| edge).collect()
+    }
+
+    /// Returns the Value of the given Node
+    pub fn get(&self, id: &ID) -> Option<&T> {
+        self.nodes.get(id)
+    }
+
+    /// Returns the ID stored in the Graph, that is equal to the given one
+    pub(crate) fn get_key(&self, id: &ID) -> Option<&ID> {
+        self.nodes.get_key_value(id).map(|(key, _)| key)
+    }
+
+    /// Returns the Targets of all the Edges leaving the given Node, in no particular Order
+    ///
+    /// # Example
+    /// ```rust
+    /// # use termgraph::DirectedGraph;
+    /// #
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(vec![&2], graph.successors(&1).collect::<Vec<_>>());
+    /// assert_eq!(0, graph.successors(&2).count());
+    /// ```
+    pub fn successors<'s>(&'s self, id: &ID) -> impl Iterator<Item = &'s ID> + 's {
+        self.edges.get(id).into_iter().flatten()
+    }
+
     /// Converts the [`DirectedGraph`] into an [`AcyclicDirectedGraph`] and also returns a List of edges
     /// that needed to be reversed to make the Graph acyclic.
+    #[cfg(test)]
     pub(crate) fn to_acyclic(&self) -> (AcyclicDirectedGraph<'_, ID, T>, Vec<(&ID, &ID)>) {
-        let anodes: HashMap<_, _> = self.nodes.iter().collect();
-        let mut aedges: HashMap<_, HashSet<_, _>> = self
+        self.to_acyclic_with(|_, _| true, true, None, None, None, None)
+    }
+
+    /// The same as [`to_acyclic`](Self::to_acyclic), but only includes the Nodes for which the
+    /// filter returns true and the Edges between them.
+    ///
+    /// If `reverse` is false, the Edges breaking the Cycles are removed instead of being reversed.
+    /// If a `rank` is given, the Cycles are broken using the Ranks of the Nodes instead, see
+    /// [`break_cycles`]. Otherwise the Edges breaking the Cycles are chosen to minimize their total
+    /// `cost`, where every Edge costs 1 by default. If an `order` is given, the broken Edges do not
+    /// depend on the Order the Nodes are stored in. Once the `deadline` has passed, the remaining
+    /// Cycles are broken without minimizing the cost anymore.
+    pub(crate) fn to_acyclic_with<F>(
+        &self,
+        filter: F,
+        reverse: bool,
+        rank: Option<&RankFn<'_, ID, T>>,
+        cost: Option<&WeightFn<ID>>,
+        order: Option<&OrderFn<'_, ID>>,
+        deadline: Option<Instant>,
+    ) -> (AcyclicDirectedGraph<'_, ID, T>, Vec<(&ID, &ID)>)
+    where
+        F: Fn(&ID, &T) -> bool,
+    {
+        let anodes: HashMap<_, _> = self
+            .nodes
+            .iter()
+            .filter(|(id, value)| filter(id, value))
+            .collect();
+        // Edges are only removed, if they touch a Node that was filtered out
+        let excluded = |id: &ID| self.nodes.contains_key(id) && !anodes.contains_key(id);
+        let aedges: HashMap<_, HashSet<_, _>> = self
             .edges
             .iter()
-            .map(|(id, targets)| (id, targets.iter().collect()))
+            .filter(|(id, _)| !excluded(id))
+            .map(|(id, targets)| (id, targets.iter().filter(|t| !excluded(t)).collect()))
             .collect();
 
-        let sccs = tarjan::sccs((&anodes, &aedges));
+        let hints = self
+            .ordered_back_edges()
+            .into_iter()
+            .filter_map(|(src, target)| {
+                let (src, targets) = aedges.get_key_value(src)?;
+                Some((*src, targets.get(target).copied()?))
+            })
+            .collect();
+
+        break_cycles(anodes, aedges, reverse, rank, cost, order, hints, deadline)
+    }
+
+    /// Collapses every Strongly Connected Component into a single Node, which is represented by
+    /// one of its members. Also returns the number of Nodes in every Component, keyed by its
+    /// Representative.
+    pub(crate) fn to_condensed(
+        &self,
+        rank: Option<&RankFn<'_, ID, T>>,
+    ) -> (AcyclicDirectedGraph<'_, ID, T>, HashMap<&ID, usize>) {
+        let sccs = self.strongly_connected_components();
 
-        // If the given Graph has no cycles, we can just return the same Nodes and Edges
-        if sccs.iter().all(|s| s.len() == 1) {
-            return (AcyclicDirectedGraph::new(anodes, aedges), Vec::new());
+        let mut representatives: HashMap<&ID, &ID> = HashMap::with_capacity(self.nodes.len());
+        let mut sizes: HashMap<&ID, usize> = HashMap::with_capacity(sccs.len());
+        for scc in sccs.iter() {
+            let representative = scc[0];
+            sizes.insert(representative, scc.len());
+            for member in scc {
+                representatives.insert(*member, representative);
+            }
         }
 
-        let feedback_arc =
-            feedback_arc_set::calulate(anodes.keys().cloned().collect(), aedges.clone());
+        (
+            self.to_merged(&representatives, |_, _| true, false, rank, None, None)
+                .0,
+            sizes,
+        )
+    }
 
-        for edge in feedback_arc.iter() {
-            let last_targets = aedges.get_mut(edge.0).expect("");
-            last_targets.remove(edge.1);
-            let first_targets = aedges.get_mut(edge.1).expect("");
-            first_targets.insert(edge.0);
+    /// Merges Groups of Nodes into a single Node each, which is represented by one of its members.
+    ///
+    /// The `representatives` map the Nodes of every Group to its Representative, Nodes without an
+    /// entry stay on their own. Edges between the Groups are combined and Edges within a Group are
+    /// removed. Only the Nodes for which the filter returns true are included and the Cycles in the
+    /// merged Graph are broken like in [`to_acyclic_with`](Self::to_acyclic_with).
+    pub(crate) fn to_merged<'s, F>(
+        &'s self,
+        representatives: &HashMap<&'s ID, &'s ID>,
+        filter: F,
+        reverse: bool,
+        rank: Option<&RankFn<'_, ID, T>>,
+        cost: Option<&WeightFn<ID>>,
+        deadline: Option<Instant>,
+    ) -> (AcyclicDirectedGraph<'s, ID, T>, Vec<(&'s ID, &'s ID)>)
+    where
+        F: Fn(&ID, &T) -> bool,
+    {
+        let merged = |id: &'s ID| representatives.get(id).copied().unwrap_or(id);
+        let excluded = |id: &ID| self.nodes.get(id).is_some_and(|value| !filter(id, value));
+
+        let nodes: HashMap<&ID, &T> = self
+            .nodes
+            .iter()
+            .filter(|(id, value)| merged(id) == *id && filter(id, value))
+            .collect();
+        let mut edges: HashMap<&ID, HashSet<&ID>> = HashMap::new();
+        for (src, target) in self.edges() {
+            if excluded(src) || excluded(target) {
+                continue;
+            }
+            // Only the Edges within a Group are removed, actual Self-Loops are handled like Cycles
+            let (msrc, mtarget) = (merged(src), merged(target));
+            if msrc != mtarget || src == target {
+                edges.entry(msrc).or_default().insert(mtarget);
+            }
         }
 
-        (AcyclicDirectedGraph::new(anodes, aedges), feedback_arc)
+        let hints = self
+            .ordered_back_edges()
+            .into_iter()
+            .filter_map(|(src, target)| {
+                let (src, target) = (merged(self.get_key(src)?), merged(self.get_key(target)?));
+                edges.get(src)?.contains(target).then_some((src, target))
+            })
+            .collect();
+
+        break_cycles(nodes, edges, reverse, rank, cost, None, hints, deadline)
     }
 }
 
+/// Breaks the Cycles in the Graph, by either reversing or removing the Edges of a Feedback-Arc-Set,
+/// and returns the affected Edges. Self-Loops are always removed and also returned.
+///
+/// The `hints` are the preferred Back-Edges, which are removed before the Cycles are broken and
+/// added back afterwards. They are only reversed, if they would close a Cycle.
+///
+/// Once the `deadline` has passed, the Feedback-Arc-Set is completed without minimizing its cost.
+#[allow(clippy::too_many_arguments)]
+fn break_cycles<'g, ID, T>(
+    anodes: HashMap<&'g ID, &'g T>,
+    mut aedges: HashMap<&'g ID, HashSet<&'g ID>>,
+    reverse: bool,
+    rank: Option<&RankFn<'_, ID, T>>,
+    cost: Option<&WeightFn<ID>>,
+    order: Option<&OrderFn<'_, ID>>,
+    hints: Vec<(&'g ID, &'g ID)>,
+    deadline: Option<Instant>,
+) -> (AcyclicDirectedGraph<'g, ID, T>, Vec<(&'g ID, &'g ID)>)
+where
+    ID: Hash + Eq,
+{
+    // Edges from a Node to itself can not be displayed at all
+    let mut self_loops: Vec<(&ID, &ID)> = aedges
+        .iter_mut()
+        .filter_map(|(src, targets)| targets.remove(src).then_some((*src, *src)))
+        .collect();
+    if let Some(order) = order {
+        self_loops.sort_by(|(a, _), (b, _)| order(a, b));
+    }
+
+    let hints: Vec<(&ID, &ID)> = match rank {
+        Some(_) => Vec::new(),
+        None => hints
+            .into_iter()
+            .filter(|(src, target)| src != target)
+            .filter(|(src, target)| aedges.get_mut(src).is_some_and(|t| t.remove(target)))
+            .collect(),
+    };
+
+    // The Hints marked first are inserted last, so they are the most likely to close a Cycle
+    let (mut agraph, mut broken) =
+        break_acyclic_cycles(anodes, aedges, reverse, rank, cost, order, deadline);
+    for (src, target) in hints.into_iter().rev() {
+        if !agraph.reaches(target, src) {
+            agraph.insert_edge(src, target);
+            continue;
+        }
+
+        if reverse {
+            agraph.insert_edge(target, src);
+        }
+        broken.push((src, target));
+    }
+    broken.extend(self_loops);
+    (agraph, broken)
+}
+
+/// Breaks the Cycles of a Graph without Self-Loops, see [`break_cycles`]
+fn break_acyclic_cycles<'g, ID, T>(
+    anodes: HashMap<&'g ID, &'g T>,
+    mut aedges: HashMap<&'g ID, HashSet<&'g ID>>,
+    reverse: bool,
+    rank: Option<&RankFn<'_, ID, T>>,
+    cost: Option<&WeightFn<ID>>,
+    order: Option<&OrderFn<'_, ID>>,
+    deadline: Option<Instant>,
+) -> (AcyclicDirectedGraph<'g, ID, T>, Vec<(&'g ID, &'g ID)>)
+where
+    ID: Hash + Eq,
+{
+    // With explicit Ranks, every Edge that does not lead to a higher Rank is treated like it
+    // would close a Cycle. Edges between Nodes of the same Rank are always removed, as reversing
+    // them would not help
+    if let Some(rank) = rank {
+        let ranks: HashMap<&ID, usize> = anodes.iter().map(|(id, v)| (*id, rank(id, v))).collect();
+        let mut against: Vec<(&ID, &ID)> = aedges
+            .iter()
+            .flat_map(|(src, targets)| targets.iter().map(move |target| (*src, *target)))
+            .filter(|(src, target)| match (ranks.get(src), ranks.get(target)) {
+                (Some(src), Some(target)) => src >= target,
+                _ => false,
+            })
+            .collect();
+        if let Some(order) = order {
+            against.sort_by(|(sa, ta), (sb, tb)| order(sa, sb).then_with(|| order(ta, tb)));
+        }
+
+        for (src, target) in against.iter() {
+            if let Some(targets) = aedges.get_mut(src) {
+                targets.remove(target);
+            }
+        }
+        for (src, target) in against.iter() {
+            if reverse && ranks[src] > ranks[target] {
+                aedges.entry(target).or_default().insert(src);
+            }
+        }
+
+        return (AcyclicDirectedGraph::new(anodes, aedges), against);
+    }
+
+    let sccs = tarjan::sccs((&anodes, &aedges));
+
+    // If the given Graph has no cycles, we can just return the same Nodes and Edges
+    if sccs.iter().all(|s| s.len() == 1) {
+        return (AcyclicDirectedGraph::new(anodes, aedges), Vec::new());
+    }
+
+    let cost = |src: &ID, target: &ID| cost.map_or(1, |cost| cost(src, target));
+    let feedback_arc = feedback_arc_set::calulate_weighted(
+        anodes.keys().cloned().collect(),
+        aedges.clone(),
+        cost,
+        order,
+        deadline,
+    );
+
+    for edge in feedback_arc.iter() {
+        let last_targets = aedges.get_mut(edge.0).expect("");
+        last_targets.remove(edge.1);
+        if reverse {
+            // Sinks have no Entry yet, which can only be reversed into after the deadline
+            aedges.entry(edge.1).or_default().insert(edge.0);
+        }
+    }
+
+    (AcyclicDirectedGraph::new(anodes, aedges), feedback_arc)
+}
+
 impl<ID, T> Default for DirectedGraph<ID, T>
 where
     ID: Hash + Eq,
@@ -116,7 +1224,9 @@ where
     T: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.nodes == other.nodes && self.edges == other.edges
+        self.nodes == other.nodes
+            && self.edges == other.edges
+            && self.edge_labels == other.edge_labels
     }
 }
 impl<ID, T> Eq for DirectedGraph<ID, T>
@@ -126,10 +1236,217 @@ where
 {
 }
 
+/// The Error returned when a Node or Edge can not be added to a [`DirectedGraph`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError<ID> {
+    /// A Node with the ID is already part of the Graph
+    DuplicateNode(ID),
+    /// The Edge refers to a Node, that is not part of the Graph
+    MissingNode(ID),
+    /// The Edge from the first to the second Node is already part of the Graph
+    DuplicateEdge(ID, ID),
+    /// The Edges refer to Nodes, that are not part of the Graph
+    MissingEdgeNodes(Vec<(ID, ID)>),
+}
+
+impl<ID> Display for GraphError<ID>
+where
+    ID: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateNode(id) => write!(f, "the node {id} already exists"),
+            Self::MissingNode(id) => write!(f, "the node {id} does not exist"),
+            Self::DuplicateEdge(from, to) => write!(f, "the edge {from} -> {to} already exists"),
+            Self::MissingEdgeNodes(edges) => {
+                write!(f, "the edges ")?;
+                for (index, (from, to)) in edges.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{from} -> {to}")?;
+                }
+                write!(f, " refer to nodes that do not exist")
+            }
+        }
+    }
+}
+
+impl<ID> std::error::Error for GraphError<ID> where ID: Debug + Display {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn chains_of_linear_runs() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..7).map(|i| (i, ())));
+        // 0 -> 1 -> 2 -> 3 fans out into 4 and 5 -> 6
+        graph.add_edges([(0, 1), (1, 2), (2, 3), (3, 4), (3, 5), (5, 6)]);
+
+        let mut chains = graph.chains(|_, _| true);
+        chains.sort();
+        assert_eq!(vec![vec![&0, &1, &2, &3], vec![&5, &6]], chains);
+
+        // Without the second Node, the first one is no longer part of a Chain
+        let mut chains = graph.chains(|id, _| *id != 1);
+        chains.sort();
+        assert_eq!(vec![vec![&2, &3], vec![&5, &6]], chains);
+    }
+
+    #[test]
+    fn critical_path_weights() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, 1), (1, 5), (2, 2), (3, 1)]);
+        graph.add_edges([(0, 1), (0, 2), (1, 3), (2, 3)]);
+
+        assert_eq!(vec![&0, &1, &3], graph.critical_path(|_, w| *w, |_, _| 0));
+        assert_eq!(
+            vec![&0, &2, &3],
+            graph.critical_path(|_, w| *w, |src, _| if *src == 2 { 10 } else { 0 })
+        );
+        assert!(DirectedGraph::<i32, i32>::new()
+            .critical_path(|_, w| *w as u64, |_, _| 0)
+            .is_empty());
+    }
+
+    #[test]
+    fn retain_removes_edges() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1), (1, 2), (2, 0), (0, 2)]);
+
+        graph.retain_nodes(|id, _| *id != 1);
+
+        let mut expected = DirectedGraph::new();
+        expected.add_nodes([(0, "first"), (2, "third")]);
+        expected.add_edges([(2, 0), (0, 2)]);
+        assert_eq!(expected, graph);
+    }
+
+    #[test]
+    fn merge_graphs() {
+        let mut first = DirectedGraph::new();
+        first.add_nodes([(0, "first"), (1, "second")]);
+        first.add_labeled_edges([(0, 1, "a")]);
+
+        let mut second = DirectedGraph::new();
+        second.add_nodes([(0, "zero"), (1, "other"), (2, "third")]);
+        second.add_labeled_edges([(0, 1, "z"), (1, 2, "b"), (2, 1, "c")]);
+        second.prefer_back_edge(2, 1);
+
+        // The shared Node and the shared Edge keep their current Value and Label
+        first.merge(second);
+
+        let mut expected = DirectedGraph::new();
+        expected.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        expected.add_labeled_edges([(0, 1, "a"), (1, 2, "b"), (2, 1, "c")]);
+        assert_eq!(expected, first);
+        assert_eq!(vec![&(2, 1)], first.ordered_back_edges());
+    }
+
+    #[test]
+    fn remove_nodes_and_edges() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_labeled_edges([(0, 1, "a"), (1, 2, "b"), (2, 0, "c"), (0, 2, "d")]);
+        graph.prefer_back_edge(1, 2);
+
+        assert_eq!(Some("second"), graph.remove_node(&1));
+        assert!(graph.remove_edge(&2, &0));
+        assert!(!graph.remove_edge(&2, &0));
+
+        let mut expected = DirectedGraph::new();
+        expected.add_nodes([(0, "first"), (2, "third")]);
+        expected.add_labeled_edges([(0, 2, "d")]);
+        assert_eq!(expected, graph);
+        assert!(graph.back_edges.is_empty());
+    }
+
+    #[test]
+    fn reversed_edges() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+
+        let mut expected = DirectedGraph::new();
+        expected.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        expected.add_edges([(1, 0), (2, 0), (2, 1)]);
+        assert_eq!(expected, graph.reversed());
+
+        graph.add_edges([(2, 0)]);
+        graph.prefer_back_edge(2, 0);
+        graph.prefer_back_edge(1, 2);
+        assert_eq!(
+            vec![&(0, 2), &(2, 1)],
+            graph.reversed().ordered_back_edges()
+        );
+    }
+
+    #[test]
+    fn retain_back_edges() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1), (1, 2), (2, 0), (1, 0)]);
+        graph.prefer_back_edge(2, 0);
+        graph.prefer_back_edge(1, 0);
+        graph.prefer_back_edge(2, 0);
+
+        graph.retain_nodes(|id, _| *id != 2);
+        assert_eq!(vec![&(1, 0)], graph.ordered_back_edges());
+    }
+
+    #[test]
+    fn roots_and_leaves() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "lonely")]);
+        // The Edges from and to the unknown Node 4 are ignored
+        graph.add_edges([(0, 1), (1, 2), (2, 1), (4, 0), (2, 4)]);
+
+        let mut roots: Vec<_> = graph.roots().collect();
+        roots.sort();
+        assert_eq!(vec![&0, &3], roots);
+
+        let mut leaves: Vec<_> = graph.leaves().collect();
+        leaves.sort();
+        assert_eq!(vec![&3], leaves);
+    }
+
+    #[test]
+    fn sccs_in_reverse_topological_order() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..5).map(|i| (i, ())));
+        graph.add_edges([(0, 1), (1, 2), (2, 1), (2, 3), (3, 4), (4, 3)]);
+
+        let mut sccs = graph.strongly_connected_components();
+        for scc in sccs.iter_mut() {
+            scc.sort();
+        }
+        assert_eq!(vec![vec![&3, &4], vec![&1, &2], vec![&0]], sccs);
+    }
+
+    #[test]
+    fn condensation_is_topological() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..5).map(|i| (i, ())));
+        graph.add_edges([(0, 1), (1, 2), (2, 1), (2, 3), (3, 4), (4, 3), (0, 3)]);
+
+        let condensed = graph.condensation();
+        let mut expected = DirectedGraph::new();
+        expected.add_nodes([(0, vec![0]), (1, vec![1, 2]), (2, vec![3, 4])]);
+        expected.add_edges([(0, 1), (1, 2), (0, 2)]);
+
+        let mut sorted = DirectedGraph::new();
+        sorted.add_nodes(condensed.nodes().map(|(id, members)| {
+            let mut members = members.clone();
+            members.sort();
+            (*id, members)
+        }));
+        sorted.add_edges(condensed.edges().map(|(s, t)| (*s, *t)));
+        assert_eq!(expected, sorted);
+    }
+
     #[test]
     fn toacyclic_without_cycle() {
         let nodes = [(0, "first"), (1, "second"), (2, "third")];
@@ -177,4 +1494,41 @@ mod tests {
         // Determine a way to check if the Graph is truly acyclic
         let _ = result_graph;
     }
+
+    #[test]
+    fn preferred_back_edges() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..4).map(|i| (i, ())));
+        graph.add_edges([(0, 1), (1, 2), (2, 1), (2, 3), (3, 0)]);
+        graph.prefer_back_edge(2, 1);
+        graph.prefer_back_edge(3, 0);
+        // Only closes a Cycle once the Edge from 3 to 0 is kept, so it is kept as is
+        graph.prefer_back_edge(0, 1);
+
+        let (result_graph, mut reved_edges) = graph.to_acyclic();
+        reved_edges.sort();
+
+        assert_eq!(vec![(&2, &1), (&3, &0)], reved_edges);
+        assert!(result_graph.successors(&0).unwrap().contains(&1));
+        assert!(result_graph.successors(&1).unwrap().contains(&2));
+    }
+
+    #[test]
+    fn toacyclic_passed_deadline_with_sink() {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..=3).map(|i| (i, ())));
+        graph.add_edges([(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)]);
+
+        // The Order of the Nodes is arbitrary after the deadline, so this needs a couple of runs
+        // to also reverse Edges into the Sink
+        for _ in 0..50 {
+            let (result_graph, reved_edges) =
+                graph.to_acyclic_with(|_, _| true, true, None, None, None, Some(Instant::now()));
+
+            assert!(!reved_edges.is_empty());
+            for (src, target) in reved_edges {
+                assert!(result_graph.successors(target).unwrap().contains(src));
+            }
+        }
+    }
 }
@@ -1,29 +1,55 @@
 use std::{
+    cmp::Ordering,
     collections::{HashMap, HashSet},
     hash::Hash,
+    time::Instant,
 };
 
+use crate::config::OrderFn;
+
+/// Whether the given Deadline has already passed
+fn passed(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Picks the first of the Candidates by the given Order, or any of them without an Order
+fn pick<'g, ID, I>(mut candidates: I, order: Option<&OrderFn<'_, ID>>) -> Option<&'g ID>
+where
+    I: Iterator<Item = &'g ID>,
+{
+    match order {
+        Some(order) => candidates.min_by(|a, b| order(a, b)),
+        None => candidates.next(),
+    }
+}
+
 fn find_sink<'g, ID>(
     nodes: &mut HashSet<&'g ID>,
     edges: &mut HashMap<&'g ID, HashSet<&'g ID>>,
     s2: &mut Vec<&'g ID>,
+    order: Option<&OrderFn<'_, ID>>,
+    deadline: Option<Instant>,
 ) where
     ID: Hash + Eq,
 {
-    loop {
+    while !passed(deadline) {
         let node_targeted_count: HashMap<_, _> = nodes.iter().map(|id| (*id, 0)).collect();
-        let pot_sink = edges
-            .iter()
-            .flat_map(|(_, targets)| targets.iter())
-            .fold(node_targeted_count, |mut acc, elem| {
+        let targeted = edges.values().flat_map(|targets| targets.iter()).fold(
+            node_targeted_count,
+            |mut acc, elem| {
                 let entry = acc.entry(*elem);
                 let value = entry.or_insert(0);
                 *value += 1;
                 acc
-            })
-            .into_iter()
-            .find(|(_, m)| *m == 0)
-            .map(|(id, _)| id);
+            },
+        );
+        let pot_sink = pick(
+            targeted
+                .into_iter()
+                .filter(|(_, m)| *m == 0)
+                .map(|(id, _)| id),
+            order,
+        );
 
         let sink = match pot_sink {
             Some(s) => s,
@@ -44,16 +70,19 @@ fn find_source<'g, ID>(
     nodes: &mut HashSet<&'g ID>,
     edges: &mut HashMap<&'g ID, HashSet<&'g ID>>,
     s1: &mut Vec<&'g ID>,
+    order: Option<&OrderFn<'_, ID>>,
+    deadline: Option<Instant>,
 ) where
     ID: Hash + Eq,
 {
-    loop {
-        let pot_source = nodes
-            .iter()
-            .map(|id| (id, edges.get(id).map(|e| e.len()).unwrap_or(0)))
-            .find(|(_, es)| *es == 0)
-            .map(|(id, _)| id)
-            .copied();
+    while !passed(deadline) {
+        let pot_source = pick(
+            nodes
+                .iter()
+                .copied()
+                .filter(|id| edges.get(id).map(|e| e.len()).unwrap_or(0) == 0),
+            order,
+        );
 
         let source = match pot_source {
             Some(s) => s,
@@ -70,47 +99,69 @@ fn find_source<'g, ID>(
     }
 }
 
-fn find_vertex_sequence<'g, ID>(
+fn find_vertex_sequence<'g, ID, C>(
     nodes: &mut HashSet<&'g ID>,
     edges: &mut HashMap<&'g ID, HashSet<&'g ID>>,
+    cost: C,
+    order: Option<&OrderFn<'_, ID>>,
+    deadline: Option<Instant>,
 ) -> Vec<&'g ID>
 where
     ID: Hash + Eq,
+    C: Fn(&ID, &ID) -> u64,
 {
     let mut s1: Vec<&ID> = Vec::new();
     let mut s2: Vec<&ID> = Vec::new();
 
     while !nodes.is_empty() {
+        // Any Sequence of the Nodes results in a valid Feedback-Arc-Set, so once the Deadline has
+        // passed, the remaining Nodes are simply appended, which reverses more Edges than needed
+        if passed(deadline) {
+            let mut remaining: Vec<&ID> = nodes.drain().collect();
+            if let Some(order) = order {
+                remaining.sort_by(|a, b| order(a, b));
+            }
+            s1.extend(remaining);
+            break;
+        }
+
         // Find Sink
-        find_sink(nodes, edges, &mut s2);
+        find_sink(nodes, edges, &mut s2, order, deadline);
 
         // Find Source
-        find_source(nodes, edges, &mut s1);
+        find_source(nodes, edges, &mut s1, order, deadline);
 
         {
             if !nodes.is_empty() {
-                let node_inputs: HashMap<&ID, usize> = edges
+                // The Sequence is built from the Sinks backwards, so the remaining Edges leaving
+                // the chosen Node end up in the Feedback-Arc-Set. Therefore the Node with the
+                // highest Cost of its incoming Edges compared to its outgoing ones is chosen
+                let node_inputs: HashMap<&ID, i128> = edges
                     .iter()
-                    .flat_map(|(_, targets)| targets.iter())
-                    .fold(HashMap::new(), |mut acc, elem| {
-                        let entry = acc.entry(*elem);
+                    .flat_map(|(src, targets)| targets.iter().map(move |t| (*src, *t)))
+                    .fold(HashMap::new(), |mut acc, (src, target)| {
+                        let entry = acc.entry(target);
                         let value = entry.or_default();
-                        *value += 1;
+                        *value += i128::from(cost(src, target));
                         acc
                     });
                 let u = nodes
                     .iter()
                     .copied()
-                    .map(|id| (id, edges.get(id).map(|targets| targets.len()).unwrap_or(0)))
-                    .map(|(id, out)| {
-                        (
-                            id,
-                            out as isize,
-                            node_inputs.get(id).copied().unwrap_or(0) as isize,
-                        )
+                    .map(|id| {
+                        let out: i128 = edges
+                            .get(id)
+                            .map(|targets| targets.iter().map(|t| i128::from(cost(id, t))).sum())
+                            .unwrap_or(0);
+                        (id, out)
+                    })
+                    .map(|(id, out)| (id, out, node_inputs.get(id).copied().unwrap_or(0)))
+                    .map(|(id, out, in_)| (id, in_ - out))
+                    // Ties go to the Node that comes first in the Order
+                    .max_by(|(a, va), (b, vb)| {
+                        va.cmp(vb)
+                            .then_with(|| order.map_or(Ordering::Equal, |order| order(b, a)))
                     })
-                    .map(|(id, out, in_)| (id, out - in_))
-                    .max_by_key(|(_, v)| *v)
                     .map(|(id, _)| id)
                     .expect("We previously made sure that there is at least one Node");
 
@@ -131,34 +182,54 @@ where
 }
 
 /// Based on this [Paper](https://www.sciencedirect.com/science/article/pii/002001909390079O)
+#[cfg(test)]
 pub fn calulate<'g, ID>(
+    nodes: HashSet<&'g ID>,
+    edges: HashMap<&'g ID, HashSet<&'g ID>>,
+) -> Vec<(&'g ID, &'g ID)>
+where
+    ID: Eq + Hash,
+{
+    calulate_weighted(nodes, edges, |_, _| 1, None, None)
+}
+
+/// The same as [`calulate`], but tries to minimize the total Cost of the Edges in the
+/// Feedback-Arc-Set, instead of their number. Like the unweighted Version, this is a Heuristic and
+/// does not always find the optimal Set.
+///
+/// If an `order` is given, the Nodes are always picked in that Order, when several of them are
+/// equally good, so the Set does not depend on the Order the Nodes are stored in.
+///
+/// Once the `deadline` has passed, the remaining Nodes are ordered arbitrarily, which still breaks
+/// all the Cycles, but may include more Edges than necessary.
+pub fn calulate_weighted<'g, ID, C>(
     mut nodes: HashSet<&'g ID>,
     edges: HashMap<&'g ID, HashSet<&'g ID>>,
+    cost: C,
+    order: Option<&OrderFn<'_, ID>>,
+    deadline: Option<Instant>,
 ) -> Vec<(&'g ID, &'g ID)>
 where
     ID: Eq + Hash,
+    C: Fn(&ID, &ID) -> u64,
 {
     let mut tmp = edges.clone();
-    let sequence = find_vertex_sequence(&mut nodes, &mut tmp);
+    let sequence = find_vertex_sequence(&mut nodes, &mut tmp, cost, order, deadline);
+    let indices: HashMap<&ID, usize> = sequence
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (*id, index))
+        .collect();
 
     let mut feedback_arc_set = Vec::new();
 
     for (src_index, src) in sequence.iter().enumerate() {
-        let targets = edges
-            .get(src)
-            .cloned()
-            .unwrap_or_default()
-            .into_iter()
-            .map(|tid| {
-                sequence
-                    .iter()
-                    .enumerate()
-                    .find(|(_, sid)| **sid == tid)
-                    .unwrap()
-            });
-
-        for (target_index, target) in targets {
-            if src_index <= target_index {
+        let mut targets: Vec<&&ID> = edges.get(src).into_iter().flatten().collect();
+        if let Some(order) = order {
+            targets.sort_by(|a, b| order(a, b));
+        }
+        for target in targets {
+            if src_index <= indices[target] {
                 feedback_arc_set.push((*src, *target));
             }
         }
@@ -188,6 +259,23 @@ mod tests {
         assert_eq!(expected, feedback_set);
     }
 
+    #[test]
+    fn reverses_fewest_edges() {
+        let nodes: HashSet<&usize> = [&0, &1, &2].into_iter().collect();
+        let edges: HashMap<&usize, HashSet<&usize>> = [
+            (&0, [&1].into_iter().collect()),
+            (&1, [&0, &2].into_iter().collect()),
+            (&2, [&0].into_iter().collect()),
+        ]
+        .into_iter()
+        .collect();
+
+        // Choosing the Node with the most outgoing Edges instead would reverse both Edges leaving 1
+        let feedback_set = calulate(nodes, edges);
+
+        assert_eq!(vec![(&0, &1)], feedback_set);
+    }
+
     #[test]
     fn with_cycle() {
         let nodes: HashSet<&usize> = [&0, &1, &2, &3].into_iter().collect();
@@ -216,6 +304,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn passed_deadline() {
+        let nodes: HashSet<&usize> = [&0, &1, &2, &3].into_iter().collect();
+        let edges: HashMap<&usize, HashSet<&usize>> = [
+            (&0, [&1].into_iter().collect()),
+            (&1, [&2].into_iter().collect()),
+            (&2, [&0, &3].into_iter().collect()),
+            (&3, [&1].into_iter().collect()),
+        ]
+        .into_iter()
+        .collect();
+
+        let feedback_set =
+            calulate_weighted(nodes, edges.clone(), |_, _| 1, None, Some(Instant::now()));
+
+        // Without the Feedback-Arc-Set, all the Nodes can still be removed in topological Order
+        let mut remaining = edges;
+        for (src, target) in feedback_set {
+            remaining.get_mut(src).unwrap().remove(target);
+        }
+        while let Some(sink) = remaining
+            .keys()
+            .copied()
+            .find(|id| remaining[id].is_empty())
+        {
+            remaining.remove(sink);
+            for targets in remaining.values_mut() {
+                targets.remove(sink);
+            }
+        }
+        assert!(remaining.is_empty());
+    }
+
     #[test]
     fn long_cycle() {
         let nodes: HashSet<&usize> = [&0, &1, &2, &3, &4].into_iter().collect();
@@ -234,4 +355,26 @@ mod tests {
 
         assert_eq!(1, feedback_set.len());
     }
+
+    #[test]
+    fn weighted_cycle() {
+        let nodes: HashSet<&usize> = [&0, &1, &2, &3].into_iter().collect();
+        let edges: HashMap<&usize, HashSet<&usize>> = [
+            (&0, [&1].into_iter().collect()),
+            (&1, [&2].into_iter().collect()),
+            (&2, [&3].into_iter().collect()),
+            (&3, [&1].into_iter().collect()),
+        ]
+        .into_iter()
+        .collect();
+
+        // Every Edge of the Cycle is expensive to reverse, except the one from 2 to 3
+        let cost = |src: &usize, target: &usize| match (*src, *target) {
+            (2, 3) => 1,
+            _ => 10,
+        };
+        let feedback_set = calulate_weighted(nodes, edges, cost, None, None);
+
+        assert_eq!(vec![(&2, &3)], feedback_set);
+    }
 }
@@ -91,21 +91,23 @@ fn strongconnect<'g, ID, I, AS>(
                 None => continue,
             };
 
-            if w.index.is_none() {
-                strongconnect(succ_id, nodes, edges, stack, index_fn, add_scc);
-
-                let w = nodes
-                    .get(succ_id)
-                    .expect("We previously already accessed that Node");
-                let w_lowlink = w.lowlink.expect("");
-
-                let v = nodes.get_mut(node).expect("");
-                v.lowlink = Some(std::cmp::min(v.lowlink.expect(""), w_lowlink));
-            } else if w.onstack {
-                let w_index = w.index.unwrap();
-
-                let v = nodes.get_mut(node).expect("");
-                v.lowlink = Some(std::cmp::min(v.lowlink.expect(""), w_index));
+            match (w.index, w.onstack) {
+                (None, _) => {
+                    strongconnect(succ_id, nodes, edges, stack, index_fn, add_scc);
+
+                    let w = nodes
+                        .get(succ_id)
+                        .expect("We previously already accessed that Node");
+                    let w_lowlink = w.lowlink.expect("");
+
+                    let v = nodes.get_mut(node).expect("");
+                    v.lowlink = Some(std::cmp::min(v.lowlink.expect(""), w_lowlink));
+                }
+                (Some(w_index), true) => {
+                    let v = nodes.get_mut(node).expect("");
+                    v.lowlink = Some(std::cmp::min(v.lowlink.expect(""), w_index));
+                }
+                (Some(_), false) => {}
             }
         }
     }
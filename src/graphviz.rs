@@ -0,0 +1,510 @@
+//! Displays Graphs that were already laid out by Graphviz, by reading its `-Tplain` Output and
+//! mapping the computed Coordinates onto the Character-Grid.
+//!
+//! This skips the Layout of termgraph entirely, so it can be used for Graphs where the Layout of
+//! Graphviz is preferred, while still displaying them in the Terminal. The Splines of the Edges
+//! are approximated using straight Line-Segments.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{graphviz::PlainLayout, Config, IDFormatter};
+//!
+//! // The Output of `echo 'digraph { a -> b }' | dot -Tplain`
+//! let plain = "\
+//! graph 1 0.75 1.5
+//! node a 0.375 1.25 0.75 0.5 a solid ellipse black lightgrey
+//! node b 0.375 0.25 0.75 0.5 b solid ellipse black lightgrey
+//! edge a b 4 0.375 0.99 0.375 0.88 0.375 0.75 0.375 0.61 solid black
+//! stop
+//! ";
+//!
+//! let layout: PlainLayout = plain.parse().unwrap();
+//! let config = Config::new(IDFormatter::new(), 3);
+//! layout.display(&config);
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::{names, Config, DirectedGraph, NodeContext};
+
+/// The Glyph for Edges going down to the left
+const DIAGONAL_LEFT: char = '/';
+/// The Glyph for Edges going down to the right
+const DIAGONAL_RIGHT: char = '\\';
+
+/// The number of Points sampled along every Bezier-Curve of an Edge
+const CURVE_SAMPLES: usize = 16;
+
+/// The maximum number of Columns and Rows the Layout is mapped to, where everything beyond is
+/// clamped to the last Column or Row
+const MAX_CELLS: f64 = 1024.0;
+
+/// A Node of a [`PlainLayout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlainNode {
+    /// The Name of the Node
+    pub name: String,
+    /// The Label of the Node
+    pub label: String,
+    /// The x-Coordinate of the Center in Inches
+    pub x: f64,
+    /// The y-Coordinate of the Center in Inches, starting at the bottom
+    pub y: f64,
+}
+
+/// An Edge of a [`PlainLayout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlainEdge {
+    /// The Name of the Source
+    pub tail: String,
+    /// The Name of the Target
+    pub head: String,
+    /// The Control-Points of the B-Spline of the Edge in Inches
+    pub points: Vec<(f64, f64)>,
+}
+
+/// A Graph laid out by Graphviz, parsed from its `-Tplain` Output, see the
+/// [module level docs](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlainLayout {
+    width: f64,
+    height: f64,
+    nodes: Vec<PlainNode>,
+    edges: Vec<PlainEdge>,
+    columns_per_inch: f64,
+    rows_per_inch: f64,
+}
+
+/// The Error returned when parsing a malformed [`PlainLayout`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlainError {
+    /// The Line of the Input, starting at 1
+    pub line: usize,
+    /// What is wrong with the Line
+    pub message: String,
+}
+
+impl Display for PlainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for PlainError {}
+
+impl PlainLayout {
+    /// The Nodes of the Layout
+    pub fn nodes(&self) -> &[PlainNode] {
+        &self.nodes
+    }
+
+    /// The Edges of the Layout
+    pub fn edges(&self) -> &[PlainEdge] {
+        &self.edges
+    }
+
+    /// Sets the number of Columns and Rows every Inch of the Layout is mapped to, which default to
+    /// 12 Columns and 4 Rows. The Layout is mapped to at most 1024 Columns and Rows.
+    #[must_use]
+    pub fn scale(mut self, columns_per_inch: f64, rows_per_inch: f64) -> Self {
+        self.columns_per_inch = columns_per_inch;
+        self.rows_per_inch = rows_per_inch;
+        self
+    }
+
+    /// Converts the Layout into a Graph, mapping the Name of every Node to its Label, so it can
+    /// also be displayed using the Layout of termgraph
+    pub fn graph(&self) -> DirectedGraph<String, String> {
+        let mut graph = DirectedGraph::with_capacity(self.nodes.len(), self.edges.len());
+        graph.add_nodes(self.nodes.iter().map(|n| (n.name.clone(), n.label.clone())));
+        graph.add_edges(self.edges.iter().map(|e| (e.tail.clone(), e.head.clone())));
+        graph
+    }
+
+    /// Displays the Layout, where the Nodes are formatted using the Formatter of the Config with
+    /// their Name and Label
+    pub fn display(&self, config: &Config<String, String>) {
+        self.fdisplay(config, std::io::stdout().lock());
+    }
+
+    /// The same as [`PlainLayout::display`], but allows you to specify the Output Target
+    pub fn fdisplay<W>(&self, config: &Config<String, String>, mut dest: W)
+    where
+        W: std::io::Write,
+    {
+        // The Position in Cells, where the Rows start at the top
+        let to_grid = |(x, y): (f64, f64)| {
+            let column = (x * self.columns_per_inch).clamp(0.0, MAX_CELLS);
+            let row = ((self.height - y) * self.rows_per_inch).clamp(0.0, MAX_CELLS);
+            (column, row)
+        };
+        let round = |(column, row): (f64, f64)| (column.round() as usize, row.round() as usize);
+        let to_cell = |point| round(to_grid(point));
+
+        let mut canvas = Canvas::new(to_cell((self.width, 0.0)).0 + 1, to_cell((0.0, 0.0)).1 + 1);
+        let glyphs = &config.line_glyphs;
+        for (index, edge) in self.edges.iter().enumerate() {
+            let points: Vec<(f64, f64)> = sample(&edge.points).into_iter().map(to_grid).collect();
+            let mut previous: Option<(usize, usize)> = None;
+            let mut direction = (0.0, 0.0);
+            for (from, to) in points.iter().zip(points.iter().skip(1)) {
+                let (columns, rows) = (to.0 - from.0, to.1 - from.1);
+                // A Cell is about twice as high as it is wide
+                let glyph = match columns.abs() / rows.abs() {
+                    ratio if ratio > 4.0 => glyphs.horizontal,
+                    ratio if ratio < 1.0 => glyphs.vertical,
+                    _ if (columns > 0.0) == (rows > 0.0) => DIAGONAL_RIGHT,
+                    _ => DIAGONAL_LEFT,
+                };
+
+                let cells = line(round(*from), round(*to));
+                // The first Cell of the Edge gets the Glyph of the first Segment
+                if previous.is_none() && !cells.is_empty() {
+                    canvas.draw(round(*from), glyph, index, glyphs.crossing);
+                }
+                for cell in cells {
+                    canvas.draw(cell, glyph, index, glyphs.crossing);
+                    previous = Some(cell);
+                }
+                if columns != 0.0 || rows != 0.0 {
+                    direction = (columns, rows);
+                }
+            }
+
+            if let Some(end) = previous {
+                let arrow = match direction {
+                    (c, r) if r > 0.0 && c.abs() < 4.0 * r => glyphs.arrow_down,
+                    (c, r) if r < 0.0 && c.abs() < -4.0 * r => glyphs.arrow_up,
+                    (c, _) if c < 0.0 => glyphs.arrow_left,
+                    _ => glyphs.arrow_right,
+                };
+                if let Some(cell) = canvas.cells.get_mut(end.1).and_then(|r| r.get_mut(end.0)) {
+                    *cell = (arrow, Some(index));
+                }
+            }
+        }
+
+        // The Labels are drawn last, so they are never covered by an Edge
+        let degree = |name: &String, outgoing: bool| {
+            self.edges
+                .iter()
+                .filter(|e| (if outgoing { &e.tail } else { &e.head }) == name)
+                .count()
+        };
+        for node in &self.nodes {
+            let context = || NodeContext {
+                in_degree: degree(&node.name, false),
+                out_degree: degree(&node.name, true),
+                level: 0,
+            };
+            let label = names::single_line(config, &node.name, &node.label, context);
+            let (column, row) = to_cell((node.x, node.y));
+            canvas.write(
+                column.saturating_sub(label.chars().count() / 2),
+                row,
+                &label,
+            );
+        }
+
+        let lines: Vec<String> = canvas
+            .cells
+            .into_iter()
+            .map(|row| row.into_iter().map(|(glyph, _)| glyph).collect::<String>())
+            .map(|line| line.trim_end().to_string())
+            .collect();
+        // The Margins of the Layout would only add empty Lines
+        let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(0);
+        let end = lines
+            .iter()
+            .rposition(|l| !l.is_empty())
+            .map_or(0, |i| i + 1);
+        for line in lines.get(start..end).unwrap_or_default() {
+            let _ = writeln!(dest, "{}{}", config.line_prefix, line);
+        }
+    }
+}
+
+/// The Characters of the Output, together with the Edge every Character belongs to
+struct Canvas {
+    cells: Vec<Vec<(char, Option<usize>)>>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: vec![vec![(' ', None); width]; height],
+        }
+    }
+
+    /// Draws the Glyph of the Edge, where different Edges crossing each other are drawn using the
+    /// crossing Glyph
+    fn draw(&mut self, (column, row): (usize, usize), glyph: char, edge: usize, crossing: char) {
+        let Some(cell) = self.cells.get_mut(row).and_then(|r| r.get_mut(column)) else {
+            return;
+        };
+        *cell = match cell.1 {
+            Some(other) if other != edge => (crossing, Some(edge)),
+            _ => (glyph, Some(edge)),
+        };
+    }
+
+    /// Writes the Text starting at the given Cell, growing the Row if it is too short
+    fn write(&mut self, column: usize, row: usize, text: &str) {
+        let Some(cells) = self.cells.get_mut(row) else {
+            return;
+        };
+        for (offset, c) in text.chars().enumerate() {
+            match cells.get_mut(column + offset) {
+                Some(cell) => *cell = (c, None),
+                None => {
+                    cells.resize(column + offset, (' ', None));
+                    cells.push((c, None));
+                }
+            }
+        }
+    }
+}
+
+/// Samples Points along the B-Spline given by the Control-Points, which consists of a Bezier-Curve
+/// for every 3 Points after the first one. Other numbers of Points are treated as a Polyline.
+fn sample(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    if points.len() < 4 || points.len() % 3 != 1 {
+        return points.to_vec();
+    }
+
+    let mut result = vec![points[0]];
+    for curve in points.windows(4).step_by(3) {
+        for step in 1..=CURVE_SAMPLES {
+            let t = step as f64 / CURVE_SAMPLES as f64;
+            let u = 1.0 - t;
+            let weights = [u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t];
+            let point = curve
+                .iter()
+                .zip(weights)
+                .fold((0.0, 0.0), |(x, y), ((px, py), w)| (x + px * w, y + py * w));
+            result.push(point);
+        }
+    }
+    result
+}
+
+/// The Cells on the Line between the two Cells, excluding the first one
+fn line(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let (mut x, mut y) = (from.0 as isize, from.1 as isize);
+    let (tx, ty) = (to.0 as isize, to.1 as isize);
+    let (dx, dy) = ((tx - x).abs(), -(ty - y).abs());
+    let (sx, sy) = ((tx - x).signum(), (ty - y).signum());
+
+    let mut error = dx + dy;
+    let mut cells = Vec::new();
+    while (x, y) != (tx, ty) {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += sx;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += sy;
+        }
+        cells.push((x as usize, y as usize));
+    }
+    cells
+}
+
+/// Replaces the Escape-Sequences of a Graphviz Label, where `\\N` stands for the Name of the Node
+/// and the Line-Breaks are turned into Newlines. Like in Graphviz, the Escape-Sequences within
+/// the Name are replaced as well, while unknown Escape-Sequences are kept as they are.
+pub(crate) fn unescape_label(name: &str, label: &str) -> String {
+    let mut unescaped = String::with_capacity(label.len());
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('N') => unescaped.push_str(&unescape_label("", name)),
+            Some('n' | 'l' | 'r') => unescaped.push('\n'),
+            Some(c @ ('\\' | '"')) => unescaped.push(c),
+            Some(c) => unescaped.extend(['\\', c]),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped.trim_end().to_string()
+}
+
+/// Splits a Line into its Tokens, which are separated by Whitespace or quoted
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '"' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if chars.peek() == Some(&'"') => token.push('"'),
+                        Some(c) => token.push(c),
+                        None => return Err("unterminated quoted string".to_string()),
+                    }
+                }
+                tokens.push(token);
+            }
+            c => {
+                let mut token = String::from(c);
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    token.push(c);
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+impl FromStr for PlainLayout {
+    type Err = PlainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut layout = PlainLayout {
+            width: 0.0,
+            height: 0.0,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            columns_per_inch: 12.0,
+            rows_per_inch: 4.0,
+        };
+
+        for (index, content) in s.lines().enumerate() {
+            let line = index + 1;
+            let error = |message: String| PlainError { line, message };
+            let tokens = tokenize(content).map_err(error)?;
+            let number = |index: usize| -> Result<f64, PlainError> {
+                let token = tokens
+                    .get(index)
+                    .ok_or_else(|| error(format!("missing field {index}")))?;
+                token
+                    .parse()
+                    .ok()
+                    .filter(|number: &f64| number.is_finite())
+                    .ok_or_else(|| error(format!("expected a number, found {token:?}")))
+            };
+            let size = |index: usize| -> Result<f64, PlainError> {
+                let size = number(index)?;
+                match size < 0.0 {
+                    true => Err(error(format!(
+                        "expected a non-negative number, found {size}"
+                    ))),
+                    false => Ok(size),
+                }
+            };
+            let text = |index: usize| -> Result<String, PlainError> {
+                tokens
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| error(format!("missing field {index}")))
+            };
+
+            match tokens.first().map(String::as_str) {
+                Some("graph") => {
+                    // The Scale is already applied to all the Coordinates
+                    size(1)?;
+                    layout.width = size(2)?;
+                    layout.height = size(3)?;
+                }
+                Some("node") => {
+                    let name = text(1)?;
+                    let label = unescape_label(&name, &text(6)?);
+                    layout.nodes.push(PlainNode {
+                        name,
+                        label,
+                        x: number(2)?,
+                        y: number(3)?,
+                    });
+                }
+                Some("edge") => {
+                    let count = number(3)? as usize;
+                    let points = (0..count)
+                        .map(|i| Ok((number(4 + 2 * i)?, number(5 + 2 * i)?)))
+                        .collect::<Result<Vec<_>, PlainError>>()?;
+                    layout.edges.push(PlainEdge {
+                        tail: text(1)?,
+                        head: text(2)?,
+                        points,
+                    });
+                }
+                Some("stop") => break,
+                None => {}
+                Some(other) => return Err(error(format!("unknown statement {other:?}"))),
+            }
+        }
+
+        Ok(layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    const PLAIN: &str = "\
+graph 1 0.75 1.5
+node a 0.375 1.25 0.75 0.5 a solid ellipse black lightgrey
+node \"b c\" 0.375 0.25 0.75 0.5 \"second\\nline\" solid ellipse black lightgrey
+edge a \"b c\" 4 0.375 0.99 0.375 0.88 0.375 0.75 0.375 0.61 solid black
+stop
+";
+
+    #[test]
+    fn parse() {
+        let layout: PlainLayout = PLAIN.parse().unwrap();
+        assert_eq!(2, layout.nodes().len());
+        assert_eq!("b c", layout.nodes()[1].name);
+        assert_eq!("second\nline", layout.nodes()[1].label);
+        assert_eq!(4, layout.edges()[0].points.len());
+
+        let error = "graph 1 x 2".parse::<PlainLayout>().unwrap_err();
+        assert_eq!(1, error.line);
+
+        for invalid in [
+            "graph 1 inf 1",
+            "graph nan 1 1",
+            "graph 1 1 -2",
+            "graph -1 1 1",
+        ] {
+            assert!(invalid.parse::<PlainLayout>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn render_huge_layout() {
+        let layout: PlainLayout = "graph 1 1e9 1e9\nnode a 1e9 1e9 1 1 a solid ellipse black none"
+            .parse()
+            .unwrap();
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut output = Vec::new();
+        layout.fdisplay(&config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(
+            vec![format!("{}(a)", " ".repeat(1023))],
+            output.lines().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn render() {
+        let layout: PlainLayout = PLAIN.parse().unwrap();
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut output = Vec::new();
+        layout.fdisplay(&config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        let expected = ["    (a)", "     |", "     |", "     V", "   (b c)"];
+        assert_eq!(expected.to_vec(), output.lines().collect::<Vec<_>>());
+    }
+}
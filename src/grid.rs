@@ -1,13 +1,25 @@
 use std::{
-    collections::HashMap,
-    fmt::{Debug, Display},
+    collections::{HashMap, HashSet},
+    fmt::Debug,
     hash::Hash,
+    time::Instant,
 };
 
-use crate::{acyclic::AcyclicDirectedGraph, levels::Level, Color, Config, LineGlyphs};
+use crate::{
+    acyclic::AcyclicDirectedGraph,
+    config::{default_palette, LaneKey},
+    levels::Level,
+    names::NodeNames,
+    BackEdgeStyle, CellStyle, Color, ColorBy, Config, DisplayOptions, Justify, LayoutWarning,
+    LineGlyphs, LineStyle,
+};
+
+mod canvas;
+use canvas::color_start;
+pub use canvas::{Canvas, Paint, TextCanvas};
 
 mod entry;
-pub use entry::Entry;
+pub use entry::{Entry, EntryNode, NodeLabel, Owner};
 
 mod grid_structure;
 use grid_structure::*;
@@ -18,6 +30,9 @@ use internalnode::InternalNode;
 mod levelcon;
 use levelcon::LevelConnection;
 
+mod positions;
+use positions::LevelPositions;
+
 #[derive(Clone, Copy)]
 pub struct NodeNameLength(usize);
 
@@ -57,6 +72,24 @@ impl<'g, ID> Clone for LevelEntry<'g, ID> {
     }
 }
 
+/// How an Edge ends at one of the Targets of a [`Horizontal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetEnd {
+    /// The Edge ends in an Arrow pointing into the Target
+    Arrow,
+    /// The Target is a Dummy, through which the Edge continues into the next Level
+    Continue,
+    /// The Edge was reversed and ends in an Arrow pointing away from the Target
+    Reversed,
+}
+
+/// What the Colors of the Palette are assigned to, depending on the [`ColorBy`] Strategy
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum ColorKey<'g, ID> {
+    Node(&'g ID),
+    Edge(&'g ID, &'g ID),
+}
+
 /// A Horizontal is used to connect from a single Source in the upper layer to one or multiple
 /// Targets in the lower layer
 #[derive(Debug)]
@@ -67,8 +100,9 @@ enum Horizontal<'g, ID> {
         src_x: GridCoordinate,
         /// The ID of the Source
         src: &'g ID,
-        /// The X-Coordinates of the Targets in the lower Level
-        targets: Vec<(GridCoordinate, bool)>,
+        /// The X-Coordinates of the Targets in the lower Level, together with the final Target of
+        /// the Edge
+        targets: Vec<(GridCoordinate, TargetEnd, &'g ID)>,
         /// A touple of the smallest and largest x coordinates
         x_bounds: (GridCoordinate, GridCoordinate),
     },
@@ -77,6 +111,8 @@ enum Horizontal<'g, ID> {
         src_x: GridCoordinate,
         src: &'g ID,
         target: GridCoordinate,
+        /// The original Target of the reversed Edge
+        target_id: &'g ID,
         x_bounds: (GridCoordinate, GridCoordinate),
     },
     /// Connect two Nodes on the same level along the top
@@ -84,6 +120,8 @@ enum Horizontal<'g, ID> {
         src_x: GridCoordinate,
         src: &'g ID,
         target: GridCoordinate,
+        /// The original Target of the reversed Edge
+        target_id: &'g ID,
         x_bounds: (GridCoordinate, GridCoordinate),
     },
     /// Connect two Nodes on the same level along the bottom
@@ -91,11 +129,30 @@ enum Horizontal<'g, ID> {
         src_x: GridCoordinate,
         src: &'g ID,
         target: GridCoordinate,
+        /// The original Target of the reversed Edge
+        target_id: &'g ID,
         x_bounds: (GridCoordinate, GridCoordinate),
     },
 }
 
-impl<'g, ID> Horizontal<'g, ID> {
+impl<'g, ID> Horizontal<'g, ID>
+where
+    ID: PartialEq,
+{
+    /// The Owner of the parts shared by all the Edges of this Horizontal
+    pub fn owner(&self) -> Owner<'g, ID> {
+        match self {
+            Self::TopBottom { src, targets, .. } => {
+                let target = targets.first().map(|t| t.2);
+                let shared = targets.iter().all(|t| Some(t.2) == target);
+                Owner::new(src, target.filter(|_| shared))
+            }
+            Self::BottomTop { src, target_id, .. }
+            | Self::TopTop { src, target_id, .. }
+            | Self::BottomBottom { src, target_id, .. } => Owner::new(src, Some(target_id)),
+        }
+    }
+
     pub fn x_bounds(&self) -> (GridCoordinate, GridCoordinate) {
         match self {
             Self::TopBottom { x_bounds, .. } => *x_bounds,
@@ -124,33 +181,39 @@ impl<'g, ID> Clone for Horizontal<'g, ID> {
                 src_x,
                 src,
                 target,
+                target_id,
                 x_bounds,
             } => Self::BottomTop {
                 src_x: *src_x,
                 src: *src,
                 target: *target,
+                target_id: *target_id,
                 x_bounds: *x_bounds,
             },
             Self::TopTop {
                 src_x,
                 src,
                 target,
+                target_id,
                 x_bounds,
             } => Self::TopTop {
                 src_x: *src_x,
                 src: *src,
                 target: *target,
+                target_id: *target_id,
                 x_bounds: *x_bounds,
             },
             Self::BottomBottom {
                 src_x,
                 src,
                 target,
+                target_id,
                 x_bounds,
             } => Self::BottomBottom {
                 src_x: *src_x,
                 src: *src,
                 target: *target,
+                target_id: *target_id,
                 x_bounds: *x_bounds,
             },
         }
@@ -165,8 +228,26 @@ where
 {
     /// The actual Grid Data-Structure
     inner: InnerGrid<'g, ID>,
-    /// Maps from the IDs to the Names that should be displayed in the Graph
-    names: HashMap<&'g ID, String>,
+    /// The number of Nodes that were not placed in the Grid, because the render budget was exceeded
+    omitted: usize,
+    /// The number of Levels that were not placed in the Grid, because of the
+    /// [Level limit](Config::max_levels)
+    omitted_levels: usize,
+    /// The y-coordinate of the Nodes of every Level of the Graph, together with the index of the
+    /// Level
+    level_rows: Vec<(usize, usize)>,
+    /// The Swimlanes, if the Nodes are partitioned into them
+    lanes: Vec<Lane>,
+}
+
+/// The Glyph used for the Lines between the Swimlanes
+const LANE_SEPARATOR: char = ':';
+
+/// A Swimlane spanning the Columns starting at `x` up to the next Lane
+struct Lane {
+    name: String,
+    /// The Column of the Separator in front of the Lane
+    x: usize,
 }
 
 // TODO
@@ -179,41 +260,40 @@ enum Alignment {
 
 impl<'g, ID> Grid<'g, ID>
 where
-    ID: Hash + Eq + Display,
+    ID: Hash + Eq,
 {
-    /// This is responsible for generating all the Horizontals needed for each Layer
-    fn generate_horizontals<T>(
-        agraph: &AcyclicDirectedGraph<'g, ID, T>,
-        levels: &[Vec<InternalNode<'g, ID>>],
-        node_names: &HashMap<&ID, String>,
-        max_x: usize,
-    ) -> impl Iterator<Item = Vec<Horizontal<'g, ID>>> {
-        levels
-            .windows(2)
-            .map(|window| {
-                // The upper and lower level that need to be connected
-                let first = &window[0];
-                let second = &window[1];
-
-                LevelConnection::construct(agraph, first, second, node_names, max_x).0
-            })
-            .collect::<Vec<_>>()
-            .into_iter()
-    }
-
-    fn insert_nodes(
+    /// Inserts the Nodes of the Level starting at the given y-coordinate
+    ///
+    /// # Returns
+    /// The number of rows occupied by the Nodes, which is more than 1 if any of the Nodes has a
+    /// Name spanning multiple Lines
+    fn insert_nodes<T>(
         y: usize,
         result: &mut InnerGrid<'g, ID>,
         level: &[InternalNode<'g, ID>],
-        node_names: &HashMap<&ID, String>,
+        positions: &LevelPositions,
+        node_names: &NodeNames<'g, ID>,
+        config: &Config<ID, T>,
         max_x: usize,
-    ) {
+    ) -> usize {
         let row = result.row_mut(y);
         let mut cursor = row.into_cursor();
-        for entry in level.iter() {
-            if cursor.next_x() > max_x {
+        for (index, entry) in level.iter().enumerate() {
+            let start = positions.start(index);
+
+            if start - 1 > max_x {
                 cursor.set_x(max_x);
 
+                if let InternalNode::Dummy { src, target, .. }
+                | InternalNode::ReverseDummy { src, target, .. } = entry
+                {
+                    config.warn(LayoutWarning::ClampedEdge {
+                        src,
+                        target,
+                        x: start,
+                        max_x,
+                    });
+                }
                 match &entry {
                     InternalNode::User(_) => {
                         unreachable!("");
@@ -224,7 +304,7 @@ where
                                 from: src,
                                 to: target,
                             },
-                            "",
+                            0,
                         );
                     }
                     InternalNode::ReverseDummy { src, target, .. } => {
@@ -233,7 +313,7 @@ where
                                 from: src,
                                 to: target,
                             },
-                            "",
+                            0,
                         );
                     }
                 };
@@ -241,11 +321,10 @@ where
                 continue;
             }
 
-            cursor.set(Entry::Empty);
+            cursor.set_x(start);
             match &entry {
                 InternalNode::User(id) => {
-                    let name = node_names.get(id).expect("There is a Name for every Node");
-                    cursor.set_node(LevelEntry::User(id), name);
+                    cursor.set_node(LevelEntry::User(id), node_names.width(id));
                 }
                 InternalNode::Dummy { src, target, .. } => {
                     cursor.set_node(
@@ -253,7 +332,7 @@ where
                             from: src,
                             to: target,
                         },
-                        "",
+                        0,
                     );
                 }
                 InternalNode::ReverseDummy { src, target, .. } => {
@@ -262,18 +341,50 @@ where
                             from: src,
                             to: target,
                         },
-                        "",
+                        0,
                     );
                 }
             };
 
             cursor.set(Entry::Empty);
         }
+
+        let height = level
+            .iter()
+            .filter_map(|n| match n {
+                InternalNode::User(id) => Some(node_names.height(id)),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(1);
+
+        // Continue the Nodes onto the following rows, where the Dummy-Nodes simply pass through
+        for line in 1..height {
+            let continued: Vec<_> = result.inner[y]
+                .iter()
+                .map(|entry| match entry {
+                    Entry::Node(EntryNode::User(id), part) if line < node_names.height(id) => {
+                        Entry::Node(EntryNode::UserLine(*id, line), *part)
+                    }
+                    Entry::Node(EntryNode::SingleSrc(owner), _) => Entry::Veritcal(*owner),
+                    Entry::Node(EntryNode::MultiSrc, _) => Entry::Veritcal(Owner::none()),
+                    _ => Entry::Empty,
+                })
+                .collect();
+
+            let mut row = result.row_mut(y + line);
+            for (x, entry) in continued.into_iter().enumerate() {
+                row.set(x, entry);
+            }
+        }
+
+        height
     }
 
     /// # Params:
     /// * `src_y`: The y-coordinate for the src nodes
     /// * `horis`: An Iterator over all the Horizontals in this Connection Layer
+    /// * `layer_spacing`: The number of rows between the src nodes and the first horizontal line
     /// * `horizontal_spacer`: Determines how much space should be left between each horizontal line
     ///
     /// # Returns
@@ -281,12 +392,13 @@ where
     fn determine_ys<'h>(
         src_y: usize,
         horis: &'h [Horizontal<'g, ID>],
+        layer_spacing: usize,
         horizontal_spacer: usize,
     ) -> (
         impl Iterator<Item = (Horizontal<'g, ID>, usize)> + 'h,
         usize,
     ) {
-        let mut y = src_y + 2;
+        let mut y = src_y + 1 + layer_spacing;
 
         let final_y = src_y
             + horis
@@ -301,7 +413,8 @@ where
                     }
                 })
                 .sum::<usize>()
-            + 4;
+            + layer_spacing
+            + 3;
 
         (
             horis.iter().cloned().map(move |hori| {
@@ -320,38 +433,46 @@ where
     /// This is used to actually "draw" the lines between two layers
     fn connect_layer<T>(
         y: &mut usize,
-        level: &[InternalNode<'g, ID>],
+        (level, positions): (&[InternalNode<'g, ID>], &LevelPositions),
         result: &mut InnerGrid<'g, ID>,
         horizontals: Vec<Horizontal<'g, ID>>,
-        node_names: &HashMap<&ID, String>,
+        node_names: &NodeNames<'g, ID>,
         config: &Config<ID, T>,
+        max_x: usize,
     ) {
         // Inserts the Nodes at the current y-Level
-        Self::insert_nodes(*y, result, level, node_names, config.glyph_width() - 1);
-        *y += 1;
-
-        // Insert the Vertical Row below every Node
-        for hori in horizontals.iter() {
-            match hori {
-                Horizontal::TopBottom { src_x, src, .. } => {
-                    result.set(*src_x, *y, Entry::Veritcal(Some(src)));
-                }
-                Horizontal::BottomTop { target, src, .. } => {
-                    result.set(*target, *y, Entry::Veritcal(Some(src)));
-                }
-                Horizontal::TopTop { src_x, src, .. } => {
-                    result.set(*src_x, *y, Entry::Veritcal(Some(src)));
-                }
-                Horizontal::BottomBottom { .. } => {
-                    // Do nothing
-                }
-            };
+        let node_y = *y;
+        let node_height =
+            Self::insert_nodes(node_y, result, level, positions, node_names, config, max_x);
+
+        // Insert the Vertical Rows below every Node, starting right below Nodes that have fewer
+        // Lines than the tallest Node in this Level
+        let stem_y = node_y + node_height;
+        for row_y in (node_y + 1)..(stem_y + config.layer_spacing) {
+            for hori in horizontals.iter() {
+                match hori {
+                    Horizontal::TopBottom { src_x: x, .. }
+                    | Horizontal::BottomTop { target: x, .. }
+                    | Horizontal::TopTop { src_x: x, .. } => {
+                        if row_y >= stem_y || result.is_empty(*x, row_y) {
+                            result.set(*x, row_y, Entry::Veritcal(hori.owner()));
+                        }
+                    }
+                    Horizontal::BottomBottom { .. } => {
+                        // Do nothing
+                    }
+                };
+            }
         }
-        *y += 1;
 
-        let (hori_iter, lowest_y) =
-            Self::determine_ys(*y - 2, &horizontals, config.vertical_edge_spacing);
+        let (hori_iter, lowest_y) = Self::determine_ys(
+            stem_y - 1,
+            &horizontals,
+            config.layer_spacing,
+            config.vertical_edge_spacing,
+        );
         for (hori, y_height) in hori_iter {
+            let owner = hori.owner();
             match hori {
                 Horizontal::TopBottom {
                     src_x,
@@ -362,101 +483,103 @@ where
                     // Draw the horizontal line
                     if x_bounds.0 != x_bounds.1 {
                         for x in x_bounds.0.between(&(x_bounds.1 + 1)) {
-                            result.set(x, y_height, Entry::Horizontal(src));
+                            result.set(x, y_height, Entry::Horizontal(owner));
                         }
                     }
 
                     // Connect the src node to the horizontal line being drawn
-                    for vy in (*y - 1)..=y_height {
-                        result.set(src_x, vy, Entry::Veritcal(Some(src)));
+                    for vy in stem_y..=y_height {
+                        result.set(src_x, vy, Entry::Veritcal(owner));
                     }
 
                     for target in targets {
+                        let target_owner = Owner::new(src, Some(target.2));
+
                         for y in y_height..(lowest_y - 1) {
-                            result.set(target.0, y, Entry::Veritcal(Some(src)));
+                            result.set(target.0, y, Entry::Veritcal(target_owner));
                         }
 
-                        for py in y_height..(*y - 1) {
-                            result.set(target.0, py, Entry::Veritcal(Some(src)));
+                        for py in y_height..stem_y {
+                            result.set(target.0, py, Entry::Veritcal(target_owner));
                         }
 
-                        let ent = if target.1 {
-                            Entry::Veritcal(Some(src))
-                        } else {
-                            Entry::ArrowDown(Some(src))
+                        let ent = match target.1 {
+                            TargetEnd::Arrow => Entry::ArrowDown(target_owner),
+                            TargetEnd::Continue => Entry::Veritcal(target_owner),
+                            TargetEnd::Reversed => Entry::ArrowUp(target_owner),
                         };
                         result.set(target.0, lowest_y - 1, ent);
                     }
                 }
                 Horizontal::BottomTop {
                     src_x,
-                    src,
                     x_bounds,
                     target,
+                    ..
                 } => {
                     // Draw the horizontal line
                     if x_bounds.0 != x_bounds.1 {
                         for x in x_bounds.0.between(&(x_bounds.1 + 1)) {
-                            result.set(x, y_height, Entry::Horizontal(src));
+                            result.set(x, y_height, Entry::Horizontal(owner));
                         }
                     }
 
                     // Connect the src node to the horizontal line being drawn
-                    for vy in (*y - 1)..=y_height {
-                        result.set(target, vy, Entry::Veritcal(Some(src)));
+                    for vy in stem_y..=y_height {
+                        result.set(target, vy, Entry::Veritcal(owner));
                     }
 
                     for y in y_height..=(lowest_y - 1) {
-                        result.set(src_x, y, Entry::Veritcal(Some(src)));
+                        result.set(src_x, y, Entry::Veritcal(owner));
                     }
 
-                    for py in y_height..(*y - 1) {
-                        result.set(src_x, py, Entry::Veritcal(Some(src)));
+                    for py in y_height..stem_y {
+                        result.set(src_x, py, Entry::Veritcal(owner));
                     }
                 }
                 Horizontal::TopTop {
                     src_x,
-                    src,
                     x_bounds,
                     target,
+                    ..
                 } => {
                     // Draw the horizontal line
                     if x_bounds.0 != x_bounds.1 {
                         for x in x_bounds.0.between(&(x_bounds.1 + 1)) {
-                            result.set(x, y_height, Entry::Horizontal(src));
+                            result.set(x, y_height, Entry::Horizontal(owner));
                         }
                     }
 
                     // Connect the src node to the horizontal line being drawn
-                    for vy in (*y - 1)..=y_height {
-                        result.set(src_x, vy, Entry::Veritcal(Some(src)));
+                    for vy in stem_y..=y_height {
+                        result.set(src_x, vy, Entry::Veritcal(owner));
                     }
 
-                    for vy in (*y - 1)..=y_height {
-                        result.set(target, vy, Entry::Veritcal(Some(src)));
+                    for vy in stem_y..=y_height {
+                        result.set(target, vy, Entry::Veritcal(owner));
                     }
                 }
                 Horizontal::BottomBottom {
                     src_x,
-                    src,
                     target,
                     x_bounds,
+                    ..
                 } => {
                     // Draw the horizontal line
                     if x_bounds.0 != x_bounds.1 {
                         for x in x_bounds.0.between(&(x_bounds.1 + 1)) {
-                            result.set(x, y_height, Entry::Horizontal(src));
+                            result.set(x, y_height, Entry::Horizontal(owner));
                         }
                     }
 
                     // Connect the src node to the horizontal line being drawn
                     for vy in y_height..(lowest_y) {
-                        result.set(src_x, vy, Entry::Veritcal(Some(src)));
+                        result.set(src_x, vy, Entry::Veritcal(owner));
                     }
                     for vy in y_height..(lowest_y - 1) {
-                        result.set(target, vy, Entry::Veritcal(Some(src)));
+                        result.set(target, vy, Entry::Veritcal(owner));
                     }
-                    result.set(target, lowest_y - 1, Entry::ArrowDown(Some(src)));
+                    result.set(target, lowest_y - 1, Entry::ArrowDown(owner));
                 }
             };
         }
@@ -478,6 +601,22 @@ where
     ) {
         let mut dummy_id = 0;
 
+        // The Successors of a Node are visited in the Order of their Positions, so the Dummy
+        // Nodes do not depend on the arbitrary Order of the Edges
+        let positions: HashMap<&'g ID, (usize, usize)> = internal_levels
+            .iter()
+            .enumerate()
+            .flat_map(|(l, level)| {
+                level
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(i, node)| match node {
+                        InternalNode::User(id) => Some((*id, (l, i))),
+                        _ => None,
+                    })
+            })
+            .collect();
+
         for index in index_iter {
             let split = internal_levels.split_at_mut(index + 1);
             let first = split
@@ -494,7 +633,13 @@ where
             for fnode in first.iter() {
                 match fnode {
                     InternalNode::User(uid) => {
-                        let graph_succs = agraph.successors(uid).cloned().unwrap_or_default();
+                        let mut graph_succs: Vec<&'g ID> = agraph
+                            .successors(uid)
+                            .into_iter()
+                            .flatten()
+                            .copied()
+                            .collect();
+                        graph_succs.sort_by_key(|id| positions.get(id));
 
                         for gsucc in graph_succs {
                             if reved_edges.iter().any(|re| re.0 == gsucc) {
@@ -565,7 +710,7 @@ where
     }
 
     fn generate_levels<T>(
-        levels: Vec<Level<'g, ID>>,
+        levels: &[Level<'g, ID>],
         agraph: &AcyclicDirectedGraph<'g, ID, T>,
         reved_edges: &[(&'g ID, &'g ID)],
     ) -> Vec<Vec<InternalNode<'g, ID>>> {
@@ -635,75 +780,882 @@ where
     }
 
     /// Construct the Grid based on the given information about the levels and overall structure
+    ///
+    /// # Params
+    /// * `started`: When the rendering was started, used to enforce the time budget of the Config
     pub fn construct<T>(
         agraph: &AcyclicDirectedGraph<'g, ID, T>,
-        levels: Vec<Level<'g, ID>>,
+        levels: &[Level<'g, ID>],
         reved_edges: Vec<(&'g ID, &'g ID)>,
         config: &Config<ID, T>,
-        names: HashMap<&'g ID, String>,
+        names: &NodeNames<'g, ID>,
+        started: Instant,
     ) -> Self {
+        // With the Marked style, the reversed Edges are drawn like any other Edge and only marked
+        let (reved_edges, marked) = match config.back_edge_style {
+            BackEdgeStyle::Marked => (Vec::new(), reved_edges),
+            BackEdgeStyle::Reverse | BackEdgeStyle::Omit => (reved_edges, Vec::new()),
+        };
+
         // Convert all the previously generated Levels into the Levels we need for this step
-        let internal_levels = Self::generate_levels(levels, agraph, &reved_edges);
+        let mut internal_levels = Self::generate_levels(levels, agraph, &reved_edges);
 
-        // We first generate all the horizontals to connect all the Levels
-        let horizontal =
-            Self::generate_horizontals(agraph, &internal_levels, &names, config.glyph_width() - 1);
+        // The positions of all the Entries in every Level
+        let (mut positions, lanes) = match config.swimlanes.as_ref() {
+            Some(key) => Self::lane_positions(&mut internal_levels, agraph, key, names, config),
+            None => (
+                internal_levels
+                    .iter()
+                    .map(|level| {
+                        LevelPositions::construct(
+                            level,
+                            names,
+                            config.node_padding,
+                            config.edge_gap,
+                        )
+                    })
+                    .collect(),
+                Vec::new(),
+            ),
+        };
 
-        // An Iterator over all the Layers and the Horizontal connecting it to the Layer below
-        let level_horizontal_iter = internal_levels.into_iter().zip(
-            horizontal
-                .into_iter()
-                .chain(std::iter::repeat_with(Vec::new)),
-        );
+        // The Lanes determine the width themselves and the Ranks determine how many Nodes share a
+        // Level, so neither is limited to the glyph width
+        let max_x = match lanes.is_empty() && config.rank.is_none() {
+            true => config.glyph_width().saturating_sub(1),
+            false => usize::MAX,
+        };
+
+        if config.justify != Justify::Left && lanes.is_empty() {
+            let available = positions
+                .iter()
+                .map(LevelPositions::end)
+                .max()
+                .unwrap_or(0)
+                .min(config.glyph_width());
+            for level_positions in positions.iter_mut() {
+                level_positions.justify(config.justify, available);
+            }
+        }
 
         let mut result = InnerGrid::new();
 
-        // Connect all the layers
+        // Connect all the layers, each one to the layer below it
+        // An additional Level might have been inserted at the top to route reversed Edges, which
+        // does not count as a Level of the Graph itself
+        let first_level = match internal_levels.first() {
+            Some(level) if !level.iter().any(|n| matches!(n, InternalNode::User(_))) => 1,
+            _ => 0,
+        };
+
         let mut y = 0;
-        for (level, horizontals) in level_horizontal_iter {
-            Self::connect_layer(&mut y, &level, &mut result, horizontals, &names, config);
+        let mut level_rows = Vec::with_capacity(levels.len());
+        for (index, level) in internal_levels.iter().enumerate() {
+            if let Some(graph_level) = index.checked_sub(first_level) {
+                if graph_level < levels.len() {
+                    level_rows.push((y, graph_level));
+                }
+            }
+
+            let horizontals = match internal_levels.get(index + 1) {
+                Some(next) => {
+                    LevelConnection::construct(
+                        agraph,
+                        (level, &positions[index]),
+                        (next, &positions[index + 1]),
+                        names,
+                        &marked,
+                        config.edge_gap,
+                        max_x,
+                    )
+                    .0
+                }
+                None => Vec::new(),
+            };
+
+            Self::connect_layer(
+                &mut y,
+                (level, &positions[index]),
+                &mut result,
+                horizontals,
+                names,
+                config,
+                max_x,
+            );
+
+            let exceeded_cells = config
+                .max_cells
+                .is_some_and(|max| result.cell_count() > max);
+            let exceeded_time = config
+                .max_render_time
+                .is_some_and(|max| started.elapsed() > max);
+            // The Level limit only applies, if there actually are more Levels to display
+            let exceeded_levels = config
+                .max_levels
+                .is_some_and(|max| level_rows.len() >= max && level_rows.len() < levels.len());
+            if exceeded_cells || exceeded_time || exceeded_levels {
+                let omitted = internal_levels[index + 1..]
+                    .iter()
+                    .flatten()
+                    .filter(|n| matches!(n, InternalNode::User(_)))
+                    .count();
+                let omitted_levels = match exceeded_levels {
+                    true => levels.len() - level_rows.len(),
+                    false => 0,
+                };
+
+                return Self {
+                    inner: result,
+                    omitted,
+                    omitted_levels,
+                    level_rows,
+                    lanes,
+                };
+            }
         }
 
         Self {
             inner: result,
-            names,
+            omitted: 0,
+            omitted_levels: 0,
+            level_rows,
+            lanes,
+        }
+    }
+
+    /// Sorts the Entries of every Level by their Swimlane and positions them, so that every Lane
+    /// spans the same Columns across all Levels.
+    ///
+    /// Dummy Nodes are placed in the Lane of the Node their Edge leads to.
+    fn lane_positions<T>(
+        internal_levels: &mut [Vec<InternalNode<'g, ID>>],
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        key: &LaneKey<ID, T>,
+        names: &NodeNames<'g, ID>,
+        config: &Config<ID, T>,
+    ) -> (Vec<LevelPositions>, Vec<Lane>) {
+        let node_lanes: HashMap<&'g ID, String> = agraph
+            .nodes
+            .iter()
+            .map(|(id, value)| (*id, key(id, value)))
+            .collect();
+        let mut lane_names: Vec<&String> = node_lanes.values().collect();
+        lane_names.sort();
+        lane_names.dedup();
+
+        let lane_of = |node: &InternalNode<'g, ID>| {
+            let id = match node {
+                InternalNode::User(id) => id,
+                InternalNode::Dummy { target, .. } | InternalNode::ReverseDummy { target, .. } => {
+                    target
+                }
+            };
+            node_lanes
+                .get(id)
+                .and_then(|name| lane_names.binary_search(&name).ok())
+                .unwrap_or(0)
+        };
+
+        // The Positions of the Entries of every Lane in every Level, relative to the Lane
+        let parts: Vec<Vec<LevelPositions>> = internal_levels
+            .iter_mut()
+            .map(|level| {
+                level.sort_by_key(lane_of);
+                (0..lane_names.len())
+                    .map(|lane| {
+                        let start = level.partition_point(|n| lane_of(n) < lane);
+                        let end = level.partition_point(|n| lane_of(n) <= lane);
+                        LevelPositions::construct(
+                            &level[start..end],
+                            names,
+                            config.node_padding,
+                            config.edge_gap,
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Every Lane is as wide as its widest Level or its Name, followed by a blank Column and
+        // the Separator of the next Lane
+        let mut lanes = Vec::with_capacity(lane_names.len());
+        let mut x = 0;
+        for (index, name) in lane_names.iter().enumerate() {
+            lanes.push(Lane {
+                name: name.to_string(),
+                x,
+            });
+
+            let width = parts
+                .iter()
+                .map(|level| level[index].end())
+                .chain(std::iter::once(name.chars().count() + 1))
+                .max()
+                .unwrap_or(1);
+            x += Self::lane_offset(index) + width + 1;
+        }
+
+        let positions = parts
+            .into_iter()
+            .map(|level| {
+                let offsets = lanes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, l)| l.x + Self::lane_offset(i));
+                LevelPositions::concat(offsets.zip(level))
+            })
+            .collect();
+
+        (positions, lanes)
+    }
+
+    /// The Number of Columns between the Separator in front of a Lane and its Entries
+    fn lane_offset(index: usize) -> usize {
+        usize::from(index > 0)
+    }
+
+    /// The Gutter shows a Label for every Level in front of its Nodes
+    fn gutter<T>(&self, config: &Config<ID, T>) -> Option<Vec<String>> {
+        match (&config.gutter, config.debug_layout) {
+            (Some(label), _) => Some(self.level_rows.iter().map(|(_, l)| label(*l)).collect()),
+            (None, true) => Some(self.level_rows.iter().map(|(_, l)| l.to_string()).collect()),
+            (None, false) => None,
+        }
+    }
+
+    fn gutter_width(gutter: &Option<Vec<String>>) -> usize {
+        gutter
+            .iter()
+            .flatten()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The number of Glyphs used for the Row-Numbers of the Ruler
+    fn ruler_width(&self) -> usize {
+        self.inner.inner.len().saturating_sub(1).to_string().len()
+    }
+
+    /// The Column and Row in the Output at which the Grid starts, which is shifted by the
+    /// Line-Prefix, the Ruler, the Gutter and the Header of the Swimlanes
+    pub fn origin<T>(&self, config: &Config<ID, T>) -> (usize, usize) {
+        let gutter = self.gutter(config);
+        let mut column = config.line_prefix.chars().count();
+        let mut row = usize::from(!self.lanes.is_empty());
+        if config.ruler {
+            column += self.ruler_width() + 1;
+            row += 2;
+        }
+        if gutter.is_some() {
+            column += Self::gutter_width(&gutter) + 1;
         }
+        (column, row)
     }
 
-    /// Writes the grid to the provided writer
-    pub fn fdisplay<W>(&self, color_palette: Option<&Vec<Color>>, glyphs: &LineGlyphs, dest: &mut W)
-    where
+    /// The number of Columns of the widest Row
+    pub fn width(&self) -> usize {
+        self.inner.inner.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// The number of Rows
+    pub fn height(&self) -> usize {
+        self.inner.inner.len()
+    }
+
+    /// The number of Entries that are covered by the Lines of Edges
+    pub fn edge_length(&self) -> usize {
+        self.inner
+            .inner
+            .iter()
+            .flatten()
+            .filter(|e| !matches!(e, Entry::Empty | Entry::Node(..)))
+            .count()
+    }
+
+    /// The number of User-Nodes that were left out, because a Limit was exceeded
+    pub fn omitted(&self) -> usize {
+        self.omitted
+    }
+
+    /// The number of Entries at which Edges cross, that have neither their Source nor their
+    /// Target in common
+    pub fn crossings(&self) -> usize {
+        self.inner
+            .inner
+            .iter()
+            .flatten()
+            .filter(|e| matches!(e, Entry::Cross(owner) if owner.src.is_none() && owner.target.is_none()))
+            .count()
+    }
+
+    /// All the Entries of the Grid together with their Column and Row
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, &Entry<'g, ID>)> {
+        self.inner
+            .inner
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, entry)| (x, y, entry)))
+    }
+
+    /// The number of Glyphs reserved for the Node, that starts at the beginning of the given Row
+    fn node_width(row: &[Entry<'g, ID>], id: &ID) -> usize {
+        row.iter()
+            .take_while(|e| {
+                matches!(e, Entry::Node(EntryNode::User(uid) | EntryNode::UserLine(uid, _), _) if *uid == id)
+            })
+            .count()
+    }
+
+    /// Finds a free Spot for the Label of every Edge and returns the Glyphs of the placed Labels by
+    /// their Column and Row.
+    ///
+    /// A Label is placed in the middle of a horizontal Part of its Edge, if that is long enough,
+    /// and otherwise next to a vertical Part of it, starting at the Part closest to the Target.
+    /// The Parts only belonging to the Edge are preferred over the ones it shares with other Edges
+    /// from its Source or to its Target. The Labels are placed in the Order their Edges appear in,
+    /// where the Edges sharing their first Part are ordered by the Position of their Target, so
+    /// they never overlap and are placed the same way every time. Labels without any free Spot are
+    /// dropped and reported as a [`LayoutWarning::DroppedEdgeLabel`].
+    fn edge_label_cells<T>(
+        &self,
+        labels: &HashMap<(&'g ID, &'g ID), &str>,
+        blocked_rows: &HashSet<usize>,
+        config: &Config<ID, T>,
+    ) -> HashMap<(usize, usize), char> {
+        let rows = &self.inner.inner;
+        // Whether the Entry only belongs to the Edge, or is shared with other Edges
+        let part_of = |entry: &Entry<'g, ID>, (src, target): (&ID, &ID)| {
+            let owner = entry.owner()?;
+            match (owner.src, owner.target) {
+                (Some(s), Some(t)) if s == src && t == target => Some(true),
+                (Some(s), None) if s == src => Some(false),
+                (None, Some(t)) if t == target => Some(false),
+                _ => None,
+            }
+        };
+
+        let mut edges: Vec<_> = labels
+            .iter()
+            .filter_map(|(edge, label)| {
+                let (x, y, _) = self
+                    .entries()
+                    .find(|(_, _, e)| part_of(e, *edge).is_some())?;
+                let target = self.entries().find(|(_, _, e)| {
+                    matches!(e, Entry::Node(EntryNode::User(id) | EntryNode::UserLine(id, _), _) if *id == edge.1)
+                });
+                let target = target.map(|(x, y, _)| (y, x));
+                Some(((y, x), target, *edge, *label))
+            })
+            .collect();
+        edges.sort_by_key(|(position, target, _, _)| (*position, *target));
+
+        let mut cells: HashMap<(usize, usize), char> = HashMap::new();
+        for (_, _, edge, label) in edges {
+            let label: Vec<char> = label.chars().collect();
+            if label.is_empty() {
+                continue;
+            }
+            let free = |cells: &HashMap<(usize, usize), char>, x: usize, y: usize| {
+                let empty = match rows[y].get(x) {
+                    Some(entry) => matches!(entry, Entry::Empty),
+                    None => self.lanes.is_empty(),
+                };
+                empty && !cells.contains_key(&(x, y)) && !self.lanes.iter().any(|l| l.x == x)
+            };
+
+            // The horizontal Parts of the Edge, with some Line left on both sides of the Label
+            let horizontal = rows.iter().enumerate().find_map(|(y, row)| {
+                if blocked_rows.contains(&y) {
+                    return None;
+                }
+                let mut start = 0;
+                for x in 0..=row.len() {
+                    let line = row.get(x).is_some_and(|e| {
+                        matches!(e, Entry::Horizontal(_)) && part_of(e, edge) == Some(true)
+                    });
+                    if line {
+                        continue;
+                    }
+                    if x - start >= label.len() + 2
+                        && (start..x).all(|x| !cells.contains_key(&(x, y)))
+                    {
+                        return Some((start + (x - start - label.len()) / 2, y));
+                    }
+                    start = x + 1;
+                }
+                None
+            });
+
+            // Next to the vertical Parts of the Edge, separated from it and everything else by a
+            // blank Column
+            let vertical = |exclusive: bool| {
+                let parts: Vec<(usize, usize)> = self
+                    .entries()
+                    .filter(|(_, y, e)| {
+                        !blocked_rows.contains(y)
+                            && part_of(e, edge) == Some(exclusive)
+                            && matches!(
+                                e,
+                                Entry::Veritcal(_)
+                                    | Entry::ArrowDown(_)
+                                    | Entry::ArrowUp(_)
+                                    | Entry::ArrowBoth(_)
+                                    | Entry::Node(EntryNode::SingleSrc(_), _)
+                            )
+                    })
+                    .map(|(x, y, _)| (x, y))
+                    .collect();
+                parts.into_iter().rev().find_map(|(x, y)| {
+                    let right = x + 1..x + label.len() + 3;
+                    if right.clone().all(|x| free(&cells, x, y)) {
+                        return Some((x + 2, y));
+                    }
+                    let left = x.checked_sub(label.len() + 2)?..x;
+                    match left.clone().all(|x| free(&cells, x, y)) {
+                        true => Some((left.start + 1, y)),
+                        false => None,
+                    }
+                })
+            };
+
+            let spot = horizontal
+                .or_else(|| vertical(true))
+                .or_else(|| vertical(false));
+            match spot {
+                Some((x, y)) => {
+                    for (offset, glyph) in label.iter().enumerate() {
+                        cells.insert((x + offset, y), *glyph);
+                    }
+                }
+                None => config.warn(LayoutWarning::DroppedEdgeLabel {
+                    src: edge.0,
+                    target: edge.1,
+                }),
+            }
+        }
+        cells
+    }
+
+    /// Writes the grid to the provided writer, using the given names and links for the Nodes
+    pub fn fdisplay<T, W>(
+        &self,
+        names: &NodeNames<'g, ID>,
+        links: &HashMap<&'g ID, String>,
+        edge_labels: &HashMap<(&'g ID, &'g ID), &str>,
+        config: &Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+        dest: &mut W,
+    ) where
         W: std::io::Write,
     {
-        let mut colors = HashMap::new();
-        let mut current_color = 0;
+        let mut canvas = TextCanvas::new(dest, config, options);
+        self.draw(names, links, edge_labels, config, options, &mut canvas);
+    }
+
+    /// Draws the grid onto the Canvas, using the given names and links for the Nodes
+    pub fn draw<T, C>(
+        &self,
+        names: &NodeNames<'g, ID>,
+        links: &HashMap<&'g ID, String>,
+        edge_labels: &HashMap<(&'g ID, &'g ID), &str>,
+        config: &Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+        canvas: &mut C,
+    ) where
+        C: Canvas<'g, ID>,
+    {
+        let paint = |color: &Color, style: CellStyle| Paint {
+            color: Some(color.clone()),
+            style,
+            prefix: color_start(config, options, color),
+        };
 
-        let mut get_color = |id: &'g ID| {
-            let color_p = color_palette.as_ref()?;
+        // The escape sequences for every Color are rendered once upfront
+        let palette = match (options.colors, config.color_palette.as_ref()) {
+            (Some(false), _) | (None, None) => Vec::new(),
+            (_, Some(palette)) => palette.clone(),
+            (Some(true), None) => default_palette(),
+        };
+        let prefixes: Vec<Paint> = palette.iter().map(|c| paint(c, CellStyle::Plain)).collect();
+        let highlight = paint(&options.highlight_color, CellStyle::Highlighted);
 
-            let entry = colors.entry(id);
-            let color = entry.or_insert_with(|| {
-                current_color += 1;
-                color_p[current_color % color_p.len()].clone()
-            });
+        // The explicit Colors of the Options
+        let enabled = options.colors != Some(false);
+        let node_colors: HashMap<&ID, Paint> = options
+            .node_colors
+            .iter()
+            .filter(|_| enabled)
+            .map(|(id, color)| (id, paint(color, CellStyle::Plain)))
+            .collect();
+        let edge_colors: HashMap<(&ID, &ID), Paint> = options
+            .edge_colors
+            .iter()
+            .filter(|_| enabled)
+            .flat_map(|(src, targets)| targets.iter().map(move |(t, c)| ((src, t), c)))
+            .map(|(edge, color)| (edge, paint(color, CellStyle::Plain)))
+            .collect();
+        let edge_color = |owner: Owner<'g, ID>| edge_colors.get(&(owner.src?, owner.target?));
+
+        // Everything that is not part of the highlighted Path is dimmed, using the faint Style
+        let dim = Paint {
+            color: None,
+            style: CellStyle::Dimmed,
+            prefix: color_start(config, options, &Color::Custom(2)),
+        };
+        let path_nodes: HashSet<&ID> = options.path.iter().collect();
+        let path_edges: HashSet<(&ID, &ID)> =
+            options.path.windows(2).map(|w| (&w[0], &w[1])).collect();
+        let path_style = |owner: Owner<'g, ID>| {
+            if path_nodes.is_empty() {
+                return None;
+            }
+            match (owner.src, owner.target) {
+                (Some(src), Some(target)) if path_edges.contains(&(src, target)) => {
+                    Some(&highlight)
+                }
+                _ => Some(&dim),
+            }
+        };
 
-            Some(usize::from(color.clone()))
+        // The Key, which determines the Color of an Edge, for the configured Strategy
+        let color_key = |owner: Owner<'g, ID>| match config.color_strategy {
+            ColorBy::SourceNode => owner.src.map(ColorKey::Node),
+            ColorBy::TargetNode => owner.target.map(ColorKey::Node),
+            ColorBy::Edge => Some(ColorKey::Edge(owner.src?, owner.target?)),
+            ColorBy::Level => None,
         };
 
-        for row in &self.inner.inner {
-            for entry in row {
-                entry.fdisplay(
-                    &mut get_color,
-                    |id| self.names.get(id).unwrap().clone(),
-                    glyphs,
-                    dest,
+        // Assign the Colors to the Keys in the order they appear in, so that the actual output
+        // loop only needs to look them up
+        let mut colors: HashMap<ColorKey<'g, ID>, usize> = HashMap::new();
+        if !prefixes.is_empty() {
+            for key in self
+                .inner
+                .inner
+                .iter()
+                .flatten()
+                .filter_map(Entry::owner)
+                .filter_map(color_key)
+            {
+                let next = colors.len() + 1;
+                colors.entry(key).or_insert(next % prefixes.len());
+            }
+        }
+
+        let debug_glyphs = |glyphs: LineGlyphs| match config.debug_layout {
+            true => LineGlyphs {
+                passthrough: '·',
+                ..glyphs
+            },
+            false => glyphs,
+        };
+        let glyphs = debug_glyphs(config.line_glyphs);
+
+        // The Glyphs and Colors for every Tier of Edge-Weights
+        let tiers: Vec<(LineGlyphs, Option<Paint>)> = config
+            .weight_tiers
+            .tiers
+            .iter()
+            .map(|(_, glyphs, color)| {
+                let prefix = color
+                    .as_ref()
+                    .filter(|_| options.colors != Some(false))
+                    .map(|color| paint(color, CellStyle::Plain));
+                (debug_glyphs(*glyphs), prefix)
+            })
+            .collect();
+        let tier_of = |entry: &Entry<'g, ID>| {
+            let weight = config.edge_weight.as_ref()?;
+            let owner = entry.owner()?;
+            let index = config
+                .weight_tiers
+                .find(weight(owner.src?, owner.target?))?;
+            Some(&tiers[index])
+        };
+        // The Patterns are assigned to the same Keys as the Colors, in the order they appear in
+        let patterns: Vec<LineGlyphs> = config.patterns.iter().copied().map(debug_glyphs).collect();
+        let mut pattern_keys: HashMap<ColorKey<'g, ID>, usize> = HashMap::new();
+        if !patterns.is_empty() {
+            for key in self
+                .inner
+                .inner
+                .iter()
+                .flatten()
+                .filter_map(Entry::owner)
+                .filter_map(color_key)
+            {
+                let next = pattern_keys.len();
+                pattern_keys.entry(key).or_insert(next % patterns.len());
+            }
+        }
+        let pattern_of = |entry: &Entry<'g, ID>, band: usize| {
+            if patterns.is_empty() {
+                return None;
+            }
+            let index = match config.color_strategy {
+                ColorBy::Level => band % patterns.len(),
+                _ => *pattern_keys.get(&color_key(entry.owner()?)?)?,
+            };
+            Some(&patterns[index])
+        };
+        // The Glyphs of the Style of an Edge take precedence over the ones of its Tier
+        let style_of = |entry: &Entry<'g, ID>| {
+            let style = config.edge_style.as_ref()?;
+            let owner = entry.owner()?;
+            match style(owner.src?, owner.target?) {
+                LineStyle::Solid => None,
+                other => Some(debug_glyphs(config.style_glyphs(other))),
+            }
+        };
+
+        let gutter = self.gutter(config);
+        let gutter_width = Self::gutter_width(&gutter);
+        let width = self.width();
+
+        // Everything in front of the Grid in every Line, which is only the Row-Number of the
+        // Ruler at the Start of the Rows of the Grid
+        let ruler_width = self.ruler_width();
+        let draw_prefix = |canvas: &mut C, row: Option<usize>| {
+            canvas.draw(&config.line_prefix, None, None, None);
+            match (config.ruler, row) {
+                (true, Some(row)) => {
+                    canvas.draw(&format!("{row:>ruler_width$} "), None, None, None)
+                }
+                (true, None) => canvas.draw(&format!("{:ruler_width$} ", ""), None, None, None),
+                (false, _) => {}
+            }
+        };
+
+        // The Ruler marks every Column of the Grid, with the Tens above the Ones
+        if config.ruler {
+            let tens: String = (0..width)
+                .map(|x| match x % 10 {
+                    0 => char::from_digit((x / 10 % 10) as u32, 10).unwrap_or(' '),
+                    _ => ' ',
+                })
+                .collect();
+            let ones: String = (0..width)
+                .map(|x| char::from_digit((x % 10) as u32, 10).unwrap_or(' '))
+                .collect();
+            for line in [tens, ones] {
+                draw_prefix(canvas, None);
+                if gutter.is_some() {
+                    canvas.draw(&format!("{:gutter_width$} ", ""), None, None, None);
+                }
+                canvas.draw(line.trim_end(), None, None, None);
+                canvas.newline();
+            }
+        }
+
+        // The Columns of the Separators between the Swimlanes
+        let separators: Vec<usize> = self.lanes.iter().skip(1).map(|lane| lane.x).collect();
+        if !self.lanes.is_empty() {
+            let mut header = String::new();
+            let pad_to = |header: &mut String, x: usize| {
+                let padding = x.saturating_sub(header.chars().count());
+                header.extend(std::iter::repeat_n(' ', padding));
+            };
+            for (index, lane) in self.lanes.iter().enumerate() {
+                pad_to(&mut header, lane.x);
+                if index > 0 {
+                    header.push(LANE_SEPARATOR);
+                }
+                // The Names are aligned with the first Column available to the Entries
+                pad_to(&mut header, lane.x + Self::lane_offset(index) + 1);
+                header.push_str(&lane.name);
+            }
+
+            draw_prefix(canvas, None);
+            if gutter.is_some() {
+                canvas.draw(&format!("{:gutter_width$} ", ""), None, None, None);
+            }
+            canvas.draw(header.trim_end(), None, None, None);
+            canvas.newline();
+        }
+
+        // The Rules between the Levels are drawn in the first row below the Nodes of a Level
+        let rule_rows: HashSet<usize> = match config.level_separator {
+            Some(_) => self
+                .level_rows
+                .windows(2)
+                .filter_map(|w| {
+                    (w[0].0 + 1..w[1].0).find(|y| {
+                        !self.inner.inner[*y].iter().any(|e| {
+                            matches!(
+                                e,
+                                Entry::Node(EntryNode::User(_) | EntryNode::UserLine(..), _)
+                            )
+                        })
+                    })
+                })
+                .collect(),
+            None => HashSet::new(),
+        };
+        // The Rules are faint, if the Edges are colored
+        let rule_paint = (!prefixes.is_empty()).then_some(&dim);
+        // The Labels are kept out of the Rules, as those are drawn in a single Color
+        let label_cells = match edge_labels.is_empty() {
+            true => HashMap::new(),
+            false => self.edge_label_cells(edge_labels, &rule_rows, config),
+        };
+
+        let mut next_level = 0;
+        for (y, row) in self.inner.inner.iter().enumerate() {
+            let rule = config.level_separator.filter(|_| rule_rows.contains(&y));
+            // The Glyphs of the Rule are drawn together, once the Rule is interrupted
+            let mut rule_run = String::new();
+            let flush_rule = |canvas: &mut C, rule_run: &mut String| {
+                if !rule_run.is_empty() {
+                    canvas.draw(rule_run, rule_paint, None, None);
+                    rule_run.clear();
+                }
+            };
+
+            let starts_level =
+                matches!(self.level_rows.get(next_level), Some((level_y, _)) if *level_y == y);
+            if starts_level {
+                next_level += 1;
+            }
+            // The Level whose Nodes or outgoing Edges are in the current row
+            let band = next_level
+                .checked_sub(1)
+                .map(|i| self.level_rows[i].1)
+                .unwrap_or(0);
+
+            draw_prefix(canvas, Some(y));
+            if let Some(gutter) = gutter.as_ref() {
+                let label = match starts_level {
+                    true => gutter[next_level - 1].as_str(),
+                    false => "",
+                };
+                let padding = gutter_width - label.chars().count();
+                canvas.draw(&format!("{}{:padding$} ", label, ""), None, None, None);
+            }
+
+            let mut glyph_text = [0; 4];
+            for (x, entry) in row.iter().enumerate() {
+                if let Some(glyph) = rule.filter(|_| matches!(entry, Entry::Empty)) {
+                    if !label_cells.contains_key(&(x, y)) && !separators.contains(&x) {
+                        rule_run.push(glyph);
+                        continue;
+                    }
+                }
+                flush_rule(canvas, &mut rule_run);
+
+                if let Some(glyph) = label_cells.get(&(x, y)) {
+                    canvas.draw(glyph.encode_utf8(&mut glyph_text), None, None, None);
+                    continue;
+                }
+                if matches!(entry, Entry::Empty) && separators.contains(&x) {
+                    canvas.draw(
+                        LANE_SEPARATOR.encode_utf8(&mut glyph_text),
+                        None,
+                        None,
+                        None,
+                    );
+                    continue;
+                }
+
+                let tier = tier_of(entry);
+                let styled = style_of(entry);
+                entry.draw(
+                    |owner| match (config.color_strategy, tier) {
+                        _ if !path_nodes.is_empty() => path_style(owner),
+                        (_, Some((_, Some(prefix)))) => Some(prefix),
+                        _ if edge_color(owner).is_some() => edge_color(owner),
+                        (ColorBy::Level, _) if !prefixes.is_empty() => {
+                            Some(&prefixes[band % prefixes.len()])
+                        }
+                        _ => color_key(owner)
+                            .and_then(|key| colors.get(&key))
+                            .map(|i| &prefixes[*i]),
+                    },
+                    |id, line| NodeLabel {
+                        name: names
+                            .line(id, line)
+                            .expect("There is a Name for every Line of a Node"),
+                        width: Self::node_width(&row[x..], id),
+                        style: match (options.highlight.contains(id), path_nodes.is_empty()) {
+                            (true, _) => Some(&highlight),
+                            (false, true) => node_colors.get(id),
+                            (false, false) if path_nodes.contains(id) => Some(&highlight),
+                            (false, false) => Some(&dim),
+                        },
+                        link: links.get(id).map(String::as_str),
+                    },
+                    styled
+                        .as_ref()
+                        .or(tier.map(|(glyphs, _)| glyphs))
+                        .or(pattern_of(entry, band))
+                        .unwrap_or(&glyphs),
+                    canvas,
                 );
             }
-            let _ = writeln!(dest);
+            // The Labels next to the last Column of the Row
+            let label_end = label_cells
+                .keys()
+                .filter(|(x, label_y)| *label_y == y && *x >= row.len())
+                .map(|(x, _)| x + 1)
+                .max();
+            let trailing: String = (row.len()..label_end.unwrap_or(0))
+                .map(|x| label_cells.get(&(x, y)).copied().unwrap_or(' '))
+                .collect();
+            if !trailing.is_empty() {
+                flush_rule(canvas, &mut rule_run);
+                canvas.draw(&trailing, None, None, None);
+            }
+            if let Some(last) = separators.last().filter(|last| **last >= row.len()) {
+                flush_rule(canvas, &mut rule_run);
+                let trailing: String = (row.len()..=*last)
+                    .map(|x| match separators.contains(&x) {
+                        true => LANE_SEPARATOR,
+                        false => ' ',
+                    })
+                    .collect();
+                canvas.draw(&trailing, None, None, None);
+            }
+            if let Some(glyph) = rule {
+                let padding = width.saturating_sub(row.len());
+                rule_run.extend(std::iter::repeat_n(glyph, padding));
+            }
+            flush_rule(canvas, &mut rule_run);
+            canvas.newline();
+        }
+
+        if self.omitted_levels > 0 {
+            canvas.draw(
+                &format!(
+                    "{} … {}, {}",
+                    config.line_prefix,
+                    more(self.omitted_levels, "level"),
+                    more(self.omitted, "node")
+                ),
+                None,
+                None,
+                None,
+            );
+            canvas.newline();
+        } else if self.omitted > 0 {
+            canvas.draw(
+                &format!("{} … {}", config.line_prefix, more(self.omitted, "node")),
+                None,
+                None,
+                None,
+            );
+            canvas.newline();
         }
     }
 }
 
+/// Describes the number of omitted Things, like `1 more node` or `12 more nodes`
+fn more(count: usize, thing: &str) -> String {
+    match count {
+        1 => format!("1 more {thing}"),
+        _ => format!("{count} more {thing}s"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -711,7 +1663,7 @@ mod tests {
     #[test]
     fn determine_ys_nogap_0hori() {
         let horizontals = [];
-        let (mut result_iter, result_y) = Grid::<usize>::determine_ys(0, &horizontals, 0);
+        let (mut result_iter, result_y) = Grid::<usize>::determine_ys(0, &horizontals, 1, 0);
 
         assert_eq!(4, result_y);
         assert!(result_iter.next().is_none());
@@ -722,10 +1674,10 @@ mod tests {
         let horizontals = [Horizontal::TopBottom {
             src: &0,
             src_x: GridCoordinate(0),
-            targets: vec![(GridCoordinate(0), false)],
+            targets: vec![(GridCoordinate(0), TargetEnd::Arrow, &1)],
             x_bounds: (GridCoordinate(0), GridCoordinate(0)),
         }];
-        let (mut result_iter, result_y) = Grid::<usize>::determine_ys(0, &horizontals, 0);
+        let (mut result_iter, result_y) = Grid::<usize>::determine_ys(0, &horizontals, 1, 0);
 
         assert_eq!(4, result_y);
 
@@ -740,10 +1692,10 @@ mod tests {
         let horizontals = [Horizontal::TopBottom {
             src: &0,
             src_x: GridCoordinate(0),
-            targets: vec![(GridCoordinate(2), false)],
+            targets: vec![(GridCoordinate(2), TargetEnd::Arrow, &1)],
             x_bounds: (GridCoordinate(0), GridCoordinate(2)),
         }];
-        let (mut result_iter, result_y) = Grid::<usize>::determine_ys(0, &horizontals, 0);
+        let (mut result_iter, result_y) = Grid::<usize>::determine_ys(0, &horizontals, 1, 0);
 
         assert_eq!(5, result_y);
 
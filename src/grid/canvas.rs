@@ -0,0 +1,90 @@
+use std::io::Write;
+
+use crate::{
+    config::{ansi_color_start, ANSI_RESET},
+    CellStyle, Color, Config, DisplayOptions,
+};
+
+/// How Glyphs are painted, which is their Color and Style together with the rendered
+/// Escape-Sequence that starts them in the Text Output
+#[derive(Debug, Clone)]
+pub struct Paint {
+    /// The Color of the Glyphs, which is `None` if they are only styled
+    pub color: Option<Color>,
+    /// The Style of the Glyphs
+    pub style: CellStyle,
+    /// The rendered Escape-Sequence or Markup written in front of the Glyphs
+    pub prefix: String,
+}
+
+/// The Target a [`Grid`](super::Grid) is drawn onto, which either writes the Glyphs as Text or
+/// collects them into the Cells of a [`RenderBuffer`](crate::RenderBuffer)
+pub trait Canvas<'g, ID> {
+    /// Draws the Text using the Paint, where the Text is part of the Label of the given Node and
+    /// links to the given URL
+    fn draw(&mut self, text: &str, paint: Option<&Paint>, node: Option<&'g ID>, link: Option<&str>);
+
+    /// Ends the current Line
+    fn newline(&mut self);
+}
+
+/// Writes the drawn Glyphs as Text, where every painted Span is closed by the end of the Color
+/// and Links are written as OSC 8 Hyperlinks
+pub struct TextCanvas<'w, W> {
+    dest: &'w mut W,
+    color_end: &'w str,
+}
+
+impl<'w, W> TextCanvas<'w, W>
+where
+    W: Write,
+{
+    /// Creates the Canvas writing to `dest`, which ends the Colors like the Config, unless the
+    /// Options request plain ANSI-Escape-Sequences
+    pub fn new<ID, T>(
+        dest: &'w mut W,
+        config: &'w Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+    ) -> Self {
+        let color_end = match options.ansi_markup {
+            true => ANSI_RESET,
+            false => config.color_end.as_str(),
+        };
+        Self { dest, color_end }
+    }
+}
+
+impl<'g, ID, W> Canvas<'g, ID> for TextCanvas<'_, W>
+where
+    W: Write,
+{
+    fn draw(&mut self, text: &str, paint: Option<&Paint>, _: Option<&'g ID>, link: Option<&str>) {
+        if let Some(link) = link {
+            let _ = write!(self.dest, "\x1b]8;;{link}\x1b\\");
+        }
+        let _ = match paint {
+            Some(paint) => write!(self.dest, "{}{}{}", paint.prefix, text, self.color_end),
+            None => self.dest.write_all(text.as_bytes()),
+        };
+        if link.is_some() {
+            let _ = self.dest.write_all(b"\x1b]8;;\x1b\\");
+        }
+    }
+
+    fn newline(&mut self) {
+        let _ = writeln!(self.dest);
+    }
+}
+
+/// Renders the Escape-Sequence or Markup starting the Color, which are always ANSI-Escape-Sequences
+/// if the Options request them
+pub fn color_start<ID, T>(
+    config: &Config<ID, T>,
+    options: &DisplayOptions<ID, T>,
+    color: &Color,
+) -> String {
+    match options.ansi_markup {
+        true => ansi_color_start(color),
+        false => (config.color_start)(color),
+    }
+}
@@ -1,12 +1,62 @@
 use std::{fmt::Debug, ops::Add};
 
-use crate::LineGlyphs;
+use crate::{names, LineGlyphs};
 
-use super::LevelEntry;
+use super::{
+    canvas::{Canvas, Paint},
+    LevelEntry,
+};
+
+/// The Edge a Glyph belongs to, which is used to determine its Color.
+///
+/// If a Glyph is shared by multiple Edges, only the parts they have in common are kept.
+pub struct Owner<'g, ID> {
+    pub src: Option<&'g ID>,
+    pub target: Option<&'g ID>,
+}
+
+impl<'g, ID> Owner<'g, ID> {
+    pub fn new(src: &'g ID, target: Option<&'g ID>) -> Self {
+        Self {
+            src: Some(src),
+            target,
+        }
+    }
+
+    /// An Owner shared by Edges with nothing in common
+    pub fn none() -> Self {
+        Self {
+            src: None,
+            target: None,
+        }
+    }
+}
+
+impl<'g, ID> Owner<'g, ID>
+where
+    ID: PartialEq,
+{
+    /// Only keeps the parts that both Owners have in common
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            src: self.src.filter(|s| other.src == Some(*s)),
+            target: self.target.filter(|t| other.target == Some(*t)),
+        }
+    }
+}
+
+impl<'g, ID> Clone for Owner<'g, ID> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'g, ID> Copy for Owner<'g, ID> {}
 
 pub enum EntryNode<'g, ID> {
     User(&'g ID),
-    SingleSrc(&'g ID),
+    /// One of the following Lines of a User-Node, whose Name spans multiple Lines
+    UserLine(&'g ID, usize),
+    SingleSrc(Owner<'g, ID>),
     MultiSrc,
 }
 
@@ -14,17 +64,20 @@ impl<'g, ID> From<LevelEntry<'g, ID>> for EntryNode<'g, ID> {
     fn from(src: LevelEntry<'g, ID>) -> Self {
         match src {
             LevelEntry::User(id) => EntryNode::User(id),
-            LevelEntry::Dummy { from, .. } => EntryNode::SingleSrc(from),
+            LevelEntry::Dummy { from, to } => EntryNode::SingleSrc(Owner::new(from, Some(to))),
         }
     }
 }
 
 pub enum Entry<'g, ID> {
     Empty,
-    Horizontal(&'g ID),
-    Veritcal(Option<&'g ID>),
-    Cross(Option<&'g ID>),
-    ArrowDown(Option<&'g ID>),
+    Horizontal(Owner<'g, ID>),
+    Veritcal(Owner<'g, ID>),
+    Cross(Owner<'g, ID>),
+    ArrowDown(Owner<'g, ID>),
+    ArrowUp(Owner<'g, ID>),
+    /// Edges in both directions ending at the same Column of a Node
+    ArrowBoth(Owner<'g, ID>),
     Node(EntryNode<'g, ID>, usize),
     OpenParen,
     CloseParen,
@@ -38,6 +91,8 @@ impl<'g, ID> Debug for Entry<'g, ID> {
             Self::Veritcal(_) => f.debug_struct("Veritcal").finish(),
             Self::Cross(_) => f.debug_struct("Cross").finish(),
             Self::ArrowDown(_) => f.debug_struct("ArrowDown").finish(),
+            Self::ArrowUp(_) => f.debug_struct("ArrowUp").finish(),
+            Self::ArrowBoth(_) => f.debug_struct("ArrowBoth").finish(),
             Self::Node(_, _) => f.debug_struct("Node").finish(),
             Self::OpenParen => f.debug_struct("OpenParen").finish(),
             Self::CloseParen => f.debug_struct("CloseParen").finish(),
@@ -55,43 +110,48 @@ where
         match (self, rhs) {
             (Entry::Empty, other) => other,
             // Something being added to an existing Horizontal Line
-            (Entry::Horizontal(og), Entry::Horizontal(n)) if *og == n => Entry::Horizontal(n),
+            (Entry::Horizontal(og), Entry::Horizontal(n)) if og.src == n.src => {
+                Entry::Horizontal(og.merge(n))
+            }
             (Entry::Horizontal(_), Entry::Horizontal(_)) => {
                 panic!("Overlapping Horizontals with different SRC's")
             }
             (Entry::Horizontal(n), Entry::Empty) => Entry::Horizontal(*n),
-            (Entry::Horizontal(hsrc), Entry::Veritcal(Some(vsrc))) if *hsrc == vsrc => {
-                Entry::Cross(Some(vsrc))
-            }
-            (Entry::Horizontal(_), Entry::Veritcal(_)) => Entry::Cross(None),
+            (Entry::Horizontal(h), Entry::Veritcal(v)) => Entry::Cross(h.merge(v)),
             // Something being added to an existing Vertical Line
-            (Entry::Veritcal(og), Entry::Veritcal(n)) if *og == n => Entry::Veritcal(n),
-            (Entry::Veritcal(_), Entry::Veritcal(_)) => Entry::Veritcal(None),
+            (Entry::Veritcal(og), Entry::Veritcal(n)) => Entry::Veritcal(og.merge(n)),
             (Entry::Veritcal(n), Entry::Empty) => Entry::Veritcal(*n),
-            (Entry::Veritcal(Some(vsrc)), Entry::Horizontal(hsrc)) if *vsrc == hsrc => {
-                Entry::Cross(Some(hsrc))
-            }
-            (Entry::Veritcal(_), Entry::Horizontal(_)) => Entry::Cross(None),
+            (Entry::Veritcal(v), Entry::Horizontal(h)) => Entry::Cross(v.merge(h)),
             // Something being added to an existing arrow-down
-            (Entry::ArrowDown(og), Entry::ArrowDown(n)) if *og == n => Entry::ArrowDown(n),
-            (Entry::ArrowDown(_), Entry::ArrowDown(_)) => Entry::ArrowDown(None),
-            (Entry::Veritcal(og), Entry::ArrowDown(n)) if *og == n => Entry::ArrowDown(n),
-            (Entry::Veritcal(_), Entry::ArrowDown(_)) => Entry::ArrowDown(None),
+            (Entry::ArrowDown(og) | Entry::Veritcal(og), Entry::ArrowDown(n)) => {
+                Entry::ArrowDown(og.merge(n))
+            }
+            // Something being added to an existing arrow-up
+            (Entry::ArrowUp(og) | Entry::Veritcal(og), Entry::ArrowUp(n)) => {
+                Entry::ArrowUp(og.merge(n))
+            }
+            // Edges in both directions end at the same Node, which neither arrow head can show on
+            // its own
+            (Entry::ArrowDown(og), Entry::ArrowUp(n))
+            | (Entry::ArrowUp(og), Entry::ArrowDown(n))
+            | (
+                Entry::ArrowBoth(og),
+                Entry::ArrowDown(n) | Entry::ArrowUp(n) | Entry::ArrowBoth(n),
+            )
+            | (
+                Entry::ArrowDown(og) | Entry::ArrowUp(og) | Entry::Veritcal(og),
+                Entry::ArrowBoth(n),
+            ) => Entry::ArrowBoth(og.merge(n)),
             // Something being added to an existing Cross
             (Entry::Cross(n), Entry::Empty) => Entry::Cross(*n),
-            (Entry::Cross(None), _) => Entry::Cross(None),
-            (Entry::Cross(Some(csrc)), Entry::Horizontal(hsrc)) if *csrc == hsrc => {
-                Entry::Cross(Some(hsrc))
-            }
-            (Entry::Cross(Some(_)), Entry::Horizontal(_)) => Entry::Cross(None),
-            (Entry::Cross(Some(csrc)), Entry::Veritcal(Some(vsrc))) if *csrc == vsrc => {
-                Entry::Cross(Some(vsrc))
+            (Entry::Cross(c), Entry::Horizontal(o) | Entry::Veritcal(o)) => {
+                Entry::Cross(c.merge(o))
             }
-            (Entry::Cross(Some(_)), Entry::Veritcal(_)) => Entry::Cross(None),
+            (Entry::Cross(c), _) if c.src.is_none() => Entry::Cross(*c),
             (
                 Entry::Node(EntryNode::SingleSrc(fid), _),
                 Entry::Node(EntryNode::SingleSrc(sid), _),
-            ) if sid == *fid => Entry::Node(EntryNode::SingleSrc(sid), 0),
+            ) if sid.src == fid.src => Entry::Node(EntryNode::SingleSrc(fid.merge(sid)), 0),
             (Entry::Node(EntryNode::SingleSrc(_), _), Entry::Node(EntryNode::SingleSrc(_), _)) => {
                 Entry::Node(EntryNode::MultiSrc, 0)
             }
@@ -107,56 +167,110 @@ where
     }
 }
 
+/// The Label to display for a User-Node
+pub struct NodeLabel<'r> {
+    /// The Name of the Node
+    pub name: &'r str,
+    /// The number of Glyphs reserved for the Node
+    pub width: usize,
+    /// The Paint used to style the Name
+    pub style: Option<&'r Paint>,
+    /// The URL the Name links to
+    pub link: Option<&'r str>,
+}
+
 impl<'g, ID> Entry<'g, ID> {
-    pub fn fdisplay<C, N, W>(
-        &self,
-        get_color: &mut C,
-        get_name: N,
-        glyphs: &LineGlyphs,
-        dest: &mut W,
-    ) where
-        C: FnMut(&'g ID) -> Option<usize>,
-        N: FnOnce(&'g ID) -> String,
-        W: std::io::Write,
+    /// The Edge this Entry belongs to, if it is part of an Edge at all
+    pub fn owner(&self) -> Option<Owner<'g, ID>> {
+        match self {
+            Entry::Horizontal(owner)
+            | Entry::Veritcal(owner)
+            | Entry::Cross(owner)
+            | Entry::ArrowDown(owner)
+            | Entry::ArrowUp(owner)
+            | Entry::ArrowBoth(owner) => Some(*owner),
+            Entry::Node(EntryNode::SingleSrc(owner), 0) => Some(*owner),
+            Entry::Node(EntryNode::MultiSrc, 0) => Some(Owner::none()),
+            _ => None,
+        }
+    }
+
+    /// Draws the Entry onto the Canvas
+    ///
+    /// # Params
+    /// * `get_paint`: Returns the Paint for the Color of an Edge
+    /// * `get_label`: Returns the Label for a Line of a User-Node
+    pub fn draw<'r, P, N, C>(&self, get_paint: P, get_label: N, glyphs: &LineGlyphs, canvas: &mut C)
+    where
+        P: FnOnce(Owner<'g, ID>) -> Option<&'r Paint>,
+        N: FnOnce(&'g ID, usize) -> NodeLabel<'r>,
+        C: Canvas<'g, ID>,
     {
-        let _ = match self {
-            Entry::Empty => write!(dest, " "),
-            Entry::OpenParen => write!(dest, "("),
-            Entry::CloseParen => write!(dest, ")"),
-            Entry::Horizontal(src) => match get_color(*src) {
-                Some(c) => write!(dest, "\x1b[{}m{}\x1b[0m", c, glyphs.horizontal),
-                None => write!(dest, "{}", glyphs.horizontal),
-            },
-            Entry::Veritcal(src) => match src {
-                Some(src) => match get_color(*src) {
-                    Some(c) => write!(dest, "\x1b[{}m{}\x1b[0m", c, glyphs.vertical),
-                    None => write!(dest, "{}", glyphs.vertical),
-                },
-                None => write!(dest, "{}", glyphs.vertical),
-            },
-            Entry::Cross(src) => match src {
-                Some(src) => match get_color(*src) {
-                    Some(c) => write!(dest, "\x1b[{}m{}\x1b[0m", c, glyphs.crossing),
-                    None => write!(dest, "{}", glyphs.crossing),
-                },
-                None => write!(dest, "{}", glyphs.crossing),
-            },
-            Entry::ArrowDown(src) => match src {
-                Some(src) => match get_color(*src) {
-                    Some(c) => write!(dest, "\x1b[{}m{}\x1b[0m", c, glyphs.arrow_down),
-                    None => write!(dest, "{}", glyphs.arrow_down),
-                },
-                None => write!(dest, "{}", glyphs.arrow_down),
-            },
-            Entry::Node(_, part) if *part > 0 => Ok(()),
-            Entry::Node(id, _) => match id {
-                EntryNode::User(id) => write!(dest, "{}", get_name(id)),
-                EntryNode::SingleSrc(from) => match get_color(*from) {
-                    Some(c) => write!(dest, "\x1b[{}m|\x1b[0m", c),
-                    None => write!(dest, "|"),
-                },
-                EntryNode::MultiSrc => write!(dest, "|"),
-            },
+        let glyph = match self {
+            Entry::Empty => ' ',
+            Entry::OpenParen => '(',
+            Entry::CloseParen => ')',
+            Entry::Horizontal(_) => glyphs.horizontal,
+            Entry::Veritcal(_) => glyphs.vertical,
+            Entry::Cross(_) => glyphs.crossing,
+            Entry::ArrowDown(_) => glyphs.arrow_down,
+            Entry::ArrowUp(_) => glyphs.arrow_up,
+            Entry::ArrowBoth(_) => glyphs.arrow_both,
+            Entry::Node(_, part) if *part > 0 => return,
+            Entry::Node(EntryNode::User(id), _) => {
+                draw_label(canvas, id, get_label(id, 0));
+                return;
+            }
+            Entry::Node(EntryNode::UserLine(id, line), _) => {
+                draw_label(canvas, id, get_label(id, *line));
+                return;
+            }
+            Entry::Node(EntryNode::SingleSrc(_) | EntryNode::MultiSrc, _) => glyphs.passthrough,
         };
+
+        let mut text = [0; 4];
+        canvas.draw(
+            glyph.encode_utf8(&mut text),
+            self.owner().and_then(get_paint),
+            None,
+            None,
+        );
+    }
+}
+
+/// Draws the Label using its Style and Link, if it has them
+fn draw_label<'g, ID, C>(canvas: &mut C, id: &'g ID, label: NodeLabel<'_>)
+where
+    C: Canvas<'g, ID>,
+{
+    canvas.draw(
+        &fitted(label.name, label.width),
+        label.style,
+        Some(id),
+        label.link,
+    );
+}
+
+/// Cuts off the Name or pads it with spaces, so that it occupies exactly `width` Columns
+fn fitted(name: &str, width: usize) -> String {
+    let mut fitted = names::take_columns(name, width);
+    let count = names::width(&fitted);
+    fitted.extend(std::iter::repeat_n(' ', width - count));
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opposite_arrows() {
+        let owner = Owner::new(&0, Some(&1));
+        let mut merged = &&mut Entry::ArrowDown(owner) + Entry::ArrowUp(Owner::new(&2, Some(&1)));
+        assert!(matches!(merged, Entry::ArrowBoth(_)));
+
+        // Further Edges ending at the same Column keep both directions
+        let merged = &&mut merged + Entry::ArrowDown(owner);
+        assert!(matches!(merged, Entry::ArrowBoth(_)));
     }
 }
@@ -27,6 +27,8 @@ impl Add<usize> for GridCoordinate {
 
 pub struct InnerGrid<'g, ID> {
     pub inner: Vec<Vec<Entry<'g, ID>>>,
+    /// The total number of Cells in all the Rows, which is kept up to date while the Rows grow
+    cells: usize,
 }
 
 impl<'g, ID> InnerGrid<'g, ID>
@@ -34,7 +36,10 @@ where
     ID: PartialEq,
 {
     pub fn new() -> Self {
-        Self { inner: Vec::new() }
+        Self {
+            inner: Vec::new(),
+            cells: 0,
+        }
     }
 
     pub fn row_mut(&mut self, y: usize) -> Row<'_, 'g, ID> {
@@ -48,6 +53,7 @@ where
                 .inner
                 .get_mut(y)
                 .expect("We previously made sure that there are enough lines"),
+            cells: &mut self.cells,
         }
     }
 
@@ -55,11 +61,25 @@ where
         let mut row = self.row_mut(y);
         row.set(x.0, entry);
     }
+
+    /// Whether or not the given cell is still empty
+    pub fn is_empty(&self, x: GridCoordinate, y: usize) -> bool {
+        self.inner
+            .get(y)
+            .and_then(|row| row.get(x.0))
+            .is_none_or(|e| matches!(e, Entry::Empty))
+    }
+
+    /// The total number of cells in the Grid
+    pub fn cell_count(&self) -> usize {
+        self.cells
+    }
 }
 
 pub struct Row<'r, 'g, ID> {
     y: usize,
     row: &'r mut Vec<Entry<'g, ID>>,
+    cells: &'r mut usize,
 }
 
 impl<'r, 'g, ID> From<Cursor<'r, 'g, ID>> for Row<'r, 'g, ID> {
@@ -67,6 +87,7 @@ impl<'r, 'g, ID> From<Cursor<'r, 'g, ID>> for Row<'r, 'g, ID> {
         Self {
             y: cur.y,
             row: cur.row,
+            cells: cur.cells,
         }
     }
 }
@@ -76,9 +97,7 @@ where
     ID: PartialEq,
 {
     pub fn set(&mut self, x: usize, entry: Entry<'g, ID>) {
-        while self.row.len() <= x {
-            self.row.push(Entry::Empty);
-        }
+        grow(self.row, self.cells, x);
 
         let target = self
             .row
@@ -92,6 +111,7 @@ where
             y: self.y,
             x: 0,
             row: self.row,
+            cells: self.cells,
         }
     }
 }
@@ -100,28 +120,20 @@ pub struct Cursor<'r, 'g, ID> {
     y: usize,
     x: usize,
     row: &'r mut Vec<Entry<'g, ID>>,
+    cells: &'r mut usize,
 }
 
 impl<'r, 'g, ID> Cursor<'r, 'g, ID>
 where
     ID: PartialEq,
 {
-    pub fn next_x(&self) -> usize {
-        self.x
-    }
-
     pub fn set_x(&mut self, n_x: usize) {
         self.x = n_x;
     }
 
     /// Returns the Middle Index of the Node
-    pub fn set_node(&mut self, entry: LevelEntry<'g, ID>, name: &str) -> GridCoordinate {
-        let length = name.len();
-
-        let last_x = self.x + length;
-        while self.row.len() <= last_x {
-            self.row.push(Entry::Empty);
-        }
+    pub fn set_node(&mut self, entry: LevelEntry<'g, ID>, length: usize) -> GridCoordinate {
+        grow(self.row, self.cells, self.x + length);
 
         if entry.is_user() {
             for part in 0..length {
@@ -143,13 +155,11 @@ where
             self.x += 1;
         }
 
-        GridCoordinate(self.x - ((length + 1) / 2))
+        GridCoordinate(self.x - length.div_ceil(2))
     }
 
     pub fn set(&mut self, entry: Entry<'g, ID>) -> usize {
-        while self.row.len() <= self.x {
-            self.row.push(Entry::Empty);
-        }
+        grow(self.row, self.cells, self.x);
 
         let target = self
             .row
@@ -161,3 +171,12 @@ where
         self.x - 1
     }
 }
+
+/// Extends the Row with empty Cells until it contains the Column `x`, while counting the added
+/// Cells
+fn grow<ID>(row: &mut Vec<Entry<'_, ID>>, cells: &mut usize, x: usize) {
+    if row.len() <= x {
+        *cells += x + 1 - row.len();
+        row.resize_with(x + 1, || Entry::Empty);
+    }
+}
@@ -1,8 +1,8 @@
-use std::{collections::HashMap, fmt::Display, hash::Hash};
+use std::{collections::HashMap, hash::Hash};
 
 use crate::acyclic::AcyclicDirectedGraph;
 
-use super::{Index, NodeNameLength};
+use super::{positions::LevelPositions, Index, NodeNameLength};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum InternalNode<'g, ID> {
@@ -22,8 +22,9 @@ pub enum InternalNode<'g, ID> {
 
 impl<'g, ID> InternalNode<'g, ID>
 where
-    ID: Hash + Eq + Display,
+    ID: Hash + Eq,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn successor_targets<'a, T>(
         &'a self,
         agraph: &'a AcyclicDirectedGraph<'g, ID, T>,
@@ -31,8 +32,9 @@ where
         second: &'a [InternalNode<'g, ID>],
         first_entries: &'a HashMap<&InternalNode<'g, ID>, (Index, NodeNameLength)>,
         second_entries: &'a HashMap<&'a InternalNode<'g, ID>, (Index, NodeNameLength)>,
-        node_names: &'a HashMap<&'a ID, String>,
-    ) -> Box<dyn Iterator<Item = (&'a InternalNode<ID>, usize)> + 'a> {
+        first_positions: &'a LevelPositions,
+        second_positions: &'a LevelPositions,
+    ) -> Box<dyn Iterator<Item = (&'a InternalNode<'g, ID>, usize)> + 'a> {
         match self {
             InternalNode::User(id) => {
                 let raw_succs = agraph.successors(id).cloned().unwrap_or_default();
@@ -47,7 +49,7 @@ where
                             }) {
                                 Some(s) => s,
                                 None => {
-                                    panic!("Could not find successor Node in second {}", succ_id)
+                                    panic!("Could not find successor Node in second Level")
                                 }
                             }
                         }).map(|t_id| {
@@ -59,22 +61,7 @@ where
                                     unreachable!("We previously checked and inserted all missing Entries/Dummy Nodes")
                                 }
                             };
-
-                            // Calculate the Offset until the Target
-                            let offset: usize = second
-                                .iter()
-                                .take(index)
-                                .map(|id| {
-                                    match id {
-                                        InternalNode::User(id) => {
-                                            node_names.get(id).map_or(0, String::len)
-                                        }
-                                        _ => 1,
-                                    }
-                                })
-                                .sum();
-
-                                let raw_x = index * 2 + offset + in_node_offset / 2 + 1;
+                            let raw_x = second_positions.start(index) + in_node_offset / 2;
 
                             (t_id, raw_x)
                         }))
@@ -95,22 +82,7 @@ where
                                     unreachable!("We previously checked and inserted all missing Entries/Dummy Nodes")
                                 }
                             };
-
-                            // Calculate the Offset until the Target
-                            let offset: usize = second
-                                .iter()
-                                .take(index)
-                                .map(|id| {
-                                    match id {
-                                        InternalNode::User(id) => {
-                                            node_names.get(id).map_or(0, String::len)
-                                        }
-                                        _ => 1,
-                                    }
-                                })
-                                .sum();
-
-                                let raw_x = index * 2 + offset + in_node_offset / 2 + 1;
+                            let raw_x = second_positions.start(index) + in_node_offset / 2;
 
                             (t_id, raw_x)
                         }))
@@ -129,21 +101,7 @@ where
                                         unreachable!("We previously checked and inserted all missing Entries/Dummy Nodes")
                                     }
                                 };
-
-                                // Calculate the Offset until the Target
-                                let offset: usize = first
-                                    .iter()
-                                    .take(index)
-                                    .map(|id| {
-                                        match id {
-                                            InternalNode::User(id) => {
-                                                node_names.get(id).map_or(0, String::len)
-                                            }
-                                            _ => 1,
-                                        }
-                                    })
-                                    .sum();
-                                    let raw_x = index * 2 + offset + in_node_offset / 2 + 1;
+                                let raw_x = first_positions.start(index) + in_node_offset / 2;
 
                                 (t_id, raw_x)
                             }))
@@ -171,22 +129,7 @@ where
                                         unreachable!("We previously checked and inserted all missing Entries/Dummy Nodes")
                                     }
                                 };
-
-                                // Calculate the Offset until the Target
-                                let offset: usize = second
-                                    .iter()
-                                    .take(index)
-                                    .map(|id| {
-                                        match id {
-                                            InternalNode::User(id) => {
-                                                node_names.get(id).map_or(0, String::len)
-                                            }
-                                            _ => 1,
-                                        }
-                                    })
-                                    .sum();
-
-                                let raw_x = index * 2 + offset + in_node_offset / 2 + 1;
+                                let raw_x = second_positions.start(index) + in_node_offset / 2;
 
                                 (t_id, raw_x)
                             }))
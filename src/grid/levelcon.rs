@@ -1,53 +1,41 @@
-use std::{collections::HashMap, fmt::Display, hash::Hash};
+use std::{collections::HashMap, hash::Hash};
 
-use crate::acyclic::AcyclicDirectedGraph;
+use crate::{acyclic::AcyclicDirectedGraph, names::NodeNames};
 
 use super::{
-    grid_structure::GridCoordinate, internalnode::InternalNode, Alignment, Horizontal, Index,
-    NodeNameLength,
+    grid_structure::GridCoordinate, internalnode::InternalNode, positions::LevelPositions,
+    Alignment, Horizontal, Index, NodeNameLength, TargetEnd,
 };
 
 pub struct LevelConnection<'g, ID>(pub(super) Vec<Horizontal<'g, ID>>);
 
 impl<'g, ID> LevelConnection<'g, ID>
 where
-    ID: Hash + Eq + Display,
+    ID: Hash + Eq,
 {
     fn get_x_coord(
         target_idx: usize,
-        nodes: &[InternalNode<'g, ID>],
-        node_names: &HashMap<&ID, String>,
+        positions: &LevelPositions,
+        node_names: &NodeNames<'g, ID>,
         user_id: Option<&ID>,
         max_x: usize,
         alignment: Alignment,
     ) -> usize {
-        let offset: usize = nodes
-            .iter()
-            .take(target_idx)
-            .map(|id| match id {
-                InternalNode::User(id) => node_names.get(id).map_or(0, String::len),
-                _ => 1,
-            })
-            .sum();
-
         let inner_align = match alignment {
             Alignment::Left => 0,
-            Alignment::Center => {
-                user_id.map_or(0, |id| node_names.get(id).map_or(0, |n| n.len() / 2))
-            }
-            Alignment::Right => {
-                user_id.map_or(0, |id| node_names.get(id).map_or(0, |n| n.len() / 2))
-            }
+            Alignment::Center => user_id.map_or(0, |id| node_names.width(id) / 2),
+            Alignment::Right => user_id.map_or(0, |id| node_names.width(id) / 2),
         };
 
-        let raw_x = target_idx * 2 + offset + inner_align + 1;
+        let raw_x = positions.start(target_idx) + inner_align;
 
         raw_x.min(max_x)
     }
 
     fn get_reverse_dummies(
         second: &[InternalNode<'g, ID>],
-        node_names: &HashMap<&ID, String>,
+        second_positions: &LevelPositions,
+        node_names: &NodeNames<'g, ID>,
         max_x: usize,
     ) -> Vec<Horizontal<'g, ID>> {
         // assert!(!second.is_empty());
@@ -69,7 +57,7 @@ where
                 // Calculate the Offset until the Target
                 let target_x = Self::get_x_coord(
                     target_index,
-                    second,
+                    second_positions,
                     node_names,
                     Some(target_user_id),
                     max_x,
@@ -79,7 +67,7 @@ where
                 // Calculate the Offset until the Target
                 let src_x = Self::get_x_coord(
                     src_index,
-                    second,
+                    second_positions,
                     node_names,
                     None,
                     max_x,
@@ -93,22 +81,208 @@ where
                     src_x: GridCoordinate(src_x),
                     src: *src,
                     target: GridCoordinate(target_x),
+                    target_id: *target,
                     x_bounds: (sx, tx),
                 })
             })
             .collect()
     }
 
+    /// The columns occupied by every User-Node in the Level, keyed by the column where Edges end
+    /// by default
+    fn node_spans(
+        (second, second_positions): (&[InternalNode<'g, ID>], &LevelPositions),
+        node_names: &NodeNames<'g, ID>,
+        max_x: usize,
+    ) -> HashMap<usize, (usize, usize)> {
+        second
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match node {
+                InternalNode::User(id) => {
+                    let width = node_names.width(id);
+                    let start = second_positions.start(index);
+                    let default_x = Self::get_x_coord(
+                        index,
+                        second_positions,
+                        node_names,
+                        Some(id),
+                        max_x,
+                        Alignment::Center,
+                    );
+                    Some((
+                        default_x,
+                        (start, (start + width).min(max_x.saturating_add(1))),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Moves the marked Edges ending at a User-Node in the lower Level to a different column of
+    /// that Node, if an unmarked Edge ends at the same column. Otherwise both arrow heads would
+    /// merge and it would no longer be visible, which of the Edges was reversed.
+    ///
+    /// The marked Edges only share their column with the other Edges, if the Node has no free
+    /// column left.
+    fn separate_marked(
+        horizontals: &mut [Horizontal<'g, ID>],
+        spans: &HashMap<usize, (usize, usize)>,
+    ) {
+        let mut arrows: Vec<usize> = Vec::new();
+        let mut occupied: Vec<usize> = Vec::new();
+        for hori in horizontals.iter() {
+            match hori {
+                Horizontal::TopBottom { src_x, targets, .. } => {
+                    occupied.push(src_x.0);
+                    for (target, end, _) in targets {
+                        occupied.push(target.0);
+                        if !matches!(end, TargetEnd::Reversed) {
+                            arrows.push(target.0);
+                        }
+                    }
+                }
+                Horizontal::BottomTop { src_x, target, .. }
+                | Horizontal::TopTop { src_x, target, .. }
+                | Horizontal::BottomBottom { src_x, target, .. } => {
+                    occupied.push(src_x.0);
+                    occupied.push(target.0);
+                }
+            };
+        }
+
+        let mut moved: HashMap<usize, usize> = HashMap::new();
+        for hori in horizontals.iter_mut() {
+            let Horizontal::TopBottom {
+                src_x,
+                targets,
+                x_bounds,
+                ..
+            } = hori
+            else {
+                continue;
+            };
+
+            for (target, end, _) in targets.iter_mut() {
+                if !matches!(end, TargetEnd::Reversed) || !arrows.contains(&target.0) {
+                    continue;
+                }
+                let Some((start, end)) = spans.get(&target.0).copied() else {
+                    continue;
+                };
+
+                let default_x = target.0;
+                target.0 = *moved.entry(default_x).or_insert_with(|| {
+                    // Prefers the side the Edge comes from, so it does not cross the other Edges
+                    let mut candidates: Vec<usize> = (start..end).collect();
+                    candidates.sort_by_key(|c| {
+                        let away = (*c < default_x) != (src_x.0 < default_x);
+                        (c.abs_diff(default_x), away)
+                    });
+                    let x = candidates
+                        .into_iter()
+                        .find(|c| !occupied.contains(c))
+                        .unwrap_or(default_x);
+
+                    occupied.push(x);
+                    x
+                });
+            }
+
+            let xs = || std::iter::once(src_x.0).chain(targets.iter().map(|(t, _, _)| t.0));
+            *x_bounds = (
+                GridCoordinate(xs().min().unwrap_or(src_x.0)),
+                GridCoordinate(xs().max().unwrap_or(src_x.0)),
+            );
+        }
+    }
+
+    /// Moves the Edges ending at a User-Node in the lower Level to a different column of that
+    /// Node, if they would otherwise run vertically within `edge_gap` columns of another Edge.
+    ///
+    /// All the Edges ending at the same Node are still moved together, so they keep joining into
+    /// a single Arrow.
+    fn separate_targets(
+        horizontals: &mut [Horizontal<'g, ID>],
+        spans: &HashMap<usize, (usize, usize)>,
+        edge_gap: usize,
+    ) {
+        // All the other vertical Edges stay where they are
+        let mut occupied: Vec<usize> = Vec::new();
+        for hori in horizontals.iter() {
+            match hori {
+                Horizontal::TopBottom { src_x, targets, .. } => {
+                    occupied.push(src_x.0);
+                    occupied.extend(
+                        targets
+                            .iter()
+                            .map(|(t, _, _)| t.0)
+                            .filter(|t| !spans.contains_key(t)),
+                    );
+                }
+                Horizontal::BottomTop { src_x, target, .. }
+                | Horizontal::TopTop { src_x, target, .. }
+                | Horizontal::BottomBottom { src_x, target, .. } => {
+                    occupied.push(src_x.0);
+                    occupied.push(target.0);
+                }
+            };
+        }
+
+        let mut moved: HashMap<usize, usize> = HashMap::new();
+        for hori in horizontals.iter_mut() {
+            let Horizontal::TopBottom {
+                src_x,
+                targets,
+                x_bounds,
+                ..
+            } = hori
+            else {
+                continue;
+            };
+
+            for (target, _, _) in targets.iter_mut() {
+                let Some((start, end)) = spans.get(&target.0).copied() else {
+                    continue;
+                };
+
+                let default_x = target.0;
+                let x = *moved.entry(default_x).or_insert_with(|| {
+                    let fits =
+                        |x: usize| occupied.iter().all(|o| *o == x || o.abs_diff(x) > edge_gap);
+
+                    let mut candidates: Vec<usize> = (start..end).collect();
+                    candidates.sort_by_key(|c| c.abs_diff(default_x));
+                    let x = candidates
+                        .into_iter()
+                        .find(|c| fits(*c))
+                        .unwrap_or(default_x);
+
+                    occupied.push(x);
+                    x
+                });
+                target.0 = x;
+            }
+
+            let xs = || std::iter::once(src_x.0).chain(targets.iter().map(|(t, _, _)| t.0));
+            *x_bounds = (
+                GridCoordinate(xs().min().unwrap_or(src_x.0)),
+                GridCoordinate(xs().max().unwrap_or(src_x.0)),
+            );
+        }
+    }
+
     fn calc_entries<'a>(
         first: &'a [InternalNode<'g, ID>],
-        node_names: &HashMap<&ID, String>,
+        node_names: &NodeNames<'g, ID>,
     ) -> HashMap<&'a InternalNode<'g, ID>, (Index, NodeNameLength)> {
         first
             .iter()
             .enumerate()
             .map(|(i, id)| {
                 let len = match id {
-                    InternalNode::User(uid) => node_names.get(uid).map_or(0, String::len),
+                    InternalNode::User(uid) => node_names.width(uid),
                     _ => 0,
                 };
 
@@ -118,15 +292,21 @@ where
     }
 
     /// Construct the connection between the two given Layers
+    ///
+    /// # Params
+    /// * `marked`: The reversed Edges, in their original orientation, that should be marked
+    /// * `edge_gap`: The minimum number of blank columns between two vertical Edges
     pub fn construct<T>(
         agraph: &AcyclicDirectedGraph<'g, ID, T>,
-        first: &[InternalNode<'g, ID>],
-        second: &[InternalNode<'g, ID>],
-        node_names: &HashMap<&ID, String>,
+        (first, first_positions): (&[InternalNode<'g, ID>], &LevelPositions),
+        (second, second_positions): (&[InternalNode<'g, ID>], &LevelPositions),
+        node_names: &NodeNames<'g, ID>,
+        marked: &[(&'g ID, &'g ID)],
+        edge_gap: usize,
         max_x: usize,
     ) -> Self {
         // Special case
-        let base = Self::get_reverse_dummies(second, node_names, max_x);
+        let base = Self::get_reverse_dummies(second, second_positions, node_names, max_x);
 
         // The Entries in the second/lower level mapped to their respective X-Indices
         let first_entries: HashMap<_, (Index, NodeNameLength)> =
@@ -142,7 +322,7 @@ where
 
             let cord = Self::get_x_coord(
                 raw_x,
-                first,
+                first_positions,
                 node_names,
                 match e {
                     InternalNode::User(id) => Some(id),
@@ -160,15 +340,24 @@ where
                 // Connect the Source to its Targets in the lower Level
 
                 // An Iterator over the Successors of the src_entry
-                let succs: Box<dyn Iterator<Item = (&InternalNode<ID>, usize)>> = src_entry.successor_targets(agraph, first, second, &first_entries, &second_entries, node_names);
+                let succs: Box<dyn Iterator<Item = (&InternalNode<'g, ID>, usize)>> = src_entry.successor_targets(agraph, first, second, &first_entries, &second_entries, first_positions, second_positions);
 
                 let targets: Vec<_> = succs
                     .map(|(t_id, raw_x)| {
+                        let end = match (t_id, src_entry) {
+                            (InternalNode::Dummy { .. }, _) => TargetEnd::Continue,
+                            (InternalNode::User(target), InternalNode::User(src) | InternalNode::Dummy { src, .. }) if marked.iter().any(|(ms, mt)| ms == target && mt == src) => TargetEnd::Reversed,
+                            _ => TargetEnd::Arrow,
+                        };
+
+                        let target_id = match t_id {
+                            InternalNode::User(target)
+                            | InternalNode::Dummy { target, .. }
+                            | InternalNode::ReverseDummy { target, .. } => *target,
+                        };
+
                         // Calculate the Coordinate of the Target
-                        (
-                            GridCoordinate(raw_x.min(max_x)),
-                            matches!(t_id, InternalNode::Dummy { .. }),
-                        )
+                        (GridCoordinate(raw_x.min(max_x)), end, target_id)
                     })
                     .collect();
 
@@ -196,23 +385,23 @@ where
                             x_bounds: (sx, tx),
                         })
                     }
-                    InternalNode::ReverseDummy { src, target, .. } => {
+                    InternalNode::ReverseDummy { src, target: target_id, .. } => {
                         if first.iter().any(|n| match n {
                             InternalNode::User(uid) => uid == src,
                             _ => false,
                         }) {
-                            let target = targets.into_iter().next().map(|(c, _)| c).expect("We previously checked that targets is not empty");
-                            Some(Horizontal::TopTop { src_x: root, src: *src, target, x_bounds: (sx, tx) })
+                            let target = targets.into_iter().next().map(|(c, _, _)| c).expect("We previously checked that targets is not empty");
+                            Some(Horizontal::TopTop { src_x: root, src: *src, target, target_id: *target_id, x_bounds: (sx, tx) })
                         } else if let Some((_, _)) = second.iter().enumerate().find(|(_, n)| match n {
-                            InternalNode::ReverseDummy { src: s_src, target: s_target, .. } => src == s_src && target == s_target,
+                            InternalNode::ReverseDummy { src: s_src, target: s_target, .. } => src == s_src && target_id == s_target,
                             _ => false,
                         }) {
-                            let target = targets.into_iter().next().map(|(c, _)| c).expect("We previously checked that targets is not empty");
+                            let target = targets.into_iter().next().map(|(c, _, _)| c).expect("We previously checked that targets is not empty");
 
                             let sx = target.min(root);
                             let tx = target.max(root);
 
-                            Some(Horizontal::BottomTop { src_x: target, src: *src, target: root, x_bounds: (sx, tx) })
+                            Some(Horizontal::BottomTop { src_x: target, src: *src, target: root, target_id: *target_id, x_bounds: (sx, tx) })
                         } else {
                             // FIXME
                             // I have no idea why this todo is still here?
@@ -238,6 +427,15 @@ where
         });
         */
         temp_horizontal.extend(base);
+
+        let spans = Self::node_spans((second, second_positions), node_names, max_x);
+        if !marked.is_empty() {
+            Self::separate_marked(&mut temp_horizontal, &spans);
+        }
+        if edge_gap > 0 {
+            Self::separate_targets(&mut temp_horizontal, &spans, edge_gap);
+        }
+
         Self(temp_horizontal)
     }
 }
@@ -0,0 +1,106 @@
+use std::hash::Hash;
+
+use crate::{names::NodeNames, Justify};
+
+use super::internalnode::InternalNode;
+
+/// The x-coordinates at which the Entries of a single Level start.
+///
+/// These are computed once per Level, so that looking up the position of an Entry does not need to
+/// sum up the widths of all the Entries before it.
+pub struct LevelPositions {
+    starts: Vec<usize>,
+    /// The x-coordinate right after the last Entry
+    end: usize,
+}
+
+impl LevelPositions {
+    /// # Params
+    /// * `padding`: The number of blank columns between two adjacent Entries
+    /// * `edge_gap`: The minimum number of blank columns next to a Dummy, as their Edges run
+    ///   vertically through the Level
+    pub fn construct<'g, ID>(
+        nodes: &[InternalNode<'g, ID>],
+        node_names: &NodeNames<'g, ID>,
+        padding: usize,
+        edge_gap: usize,
+    ) -> Self
+    where
+        ID: Hash + Eq,
+    {
+        let mut next = 1;
+        let mut previous_user = true;
+        let starts = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let (width, is_user) = match node {
+                    InternalNode::User(id) => (node_names.width(id), true),
+                    _ => (1, false),
+                };
+
+                let start = match index {
+                    0 => next,
+                    _ if previous_user && is_user => next + padding,
+                    _ => next + padding.max(edge_gap),
+                };
+
+                next = start + width;
+                previous_user = is_user;
+                start
+            })
+            .collect();
+
+        Self { starts, end: next }
+    }
+
+    /// Combines the Positions of consecutive parts of a Level, where every part is moved to the
+    /// right by its Offset
+    pub fn concat<I>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, Self)>,
+    {
+        let mut starts = Vec::new();
+        let mut end = 1;
+        for (offset, part) in parts {
+            starts.extend(part.starts.iter().map(|start| start + offset));
+            if !part.starts.is_empty() {
+                end = offset + part.end;
+            }
+        }
+
+        Self { starts, end }
+    }
+
+    /// The x-coordinate right after the last Entry
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Moves the Entries to distribute them across the available width
+    pub fn justify(&mut self, justify: Justify, available: usize) {
+        let extra = available.saturating_sub(self.end);
+        if extra == 0 || self.starts.is_empty() {
+            return;
+        }
+
+        let gaps = self.starts.len() - 1;
+        let shift = |index: usize| match justify {
+            Justify::Left => 0,
+            Justify::Center => extra / 2,
+            // A single Entry has nothing to be spaced from, so it is centered instead
+            Justify::SpaceBetween if gaps == 0 => extra / 2,
+            Justify::SpaceBetween => extra * index / gaps,
+        };
+
+        for (index, start) in self.starts.iter_mut().enumerate() {
+            *start += shift(index);
+        }
+        self.end += shift(gaps);
+    }
+
+    /// The x-coordinate at which the Entry with the given Index starts
+    pub fn start(&self, index: usize) -> usize {
+        self.starts[index]
+    }
+}
@@ -0,0 +1,603 @@
+//! Renders the Graph to an Image and displays it using one of the Graphics-Protocols supported by
+//! some Terminals, which is only available with the `images` Feature.
+//!
+//! The Image is rasterized from the textual Layout, so the Nodes and Edges are placed like in the
+//! normal output, but the Nodes are drawn with a Box around their Labels and the Edges as
+//! connected Lines in their Colors. If the Terminal does not support any of the Protocols, or a
+//! Label contains Characters the built-in Font can not draw, the Graph is displayed as Text
+//! instead.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{Config, DirectedGraph, IDFormatter};
+//!
+//! let config = Config::new(IDFormatter::new(), 3);
+//! let mut graph = DirectedGraph::new();
+//! graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+//! graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+//!
+//! termgraph::image::display(&graph, &config);
+//! ```
+
+use std::{hash::Hash, io::IsTerminal};
+
+use crate::{Color, Config, DirectedGraph, Layout, LineGlyphBuilder, LineGlyphs, RenderBuffer};
+
+/// The Graphics-Protocols the Image can be sent with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// The Graphics-Protocol of the kitty Terminal, also supported by WezTerm and Ghostty
+    Kitty,
+    /// The Sixel-Format of DEC Terminals, supported by xterm, mlterm, foot and others
+    Sixel,
+}
+
+impl ImageProtocol {
+    /// Guesses the Protocol supported by the current Terminal based on the Environment, returns
+    /// `None` if no Protocol is known to be supported or the Output is not a Terminal.
+    pub fn detect() -> Option<Self> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+
+        if term == "xterm-kitty"
+            || std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || matches!(program.as_str(), "WezTerm" | "ghostty")
+        {
+            return Some(Self::Kitty);
+        }
+        if term.contains("sixel") || matches!(term.as_str(), "mlterm" | "foot" | "foot-extra") {
+            return Some(Self::Sixel);
+        }
+        None
+    }
+}
+
+/// The Width of a single Character-Cell in Pixels
+const CELL_WIDTH: usize = 12;
+/// The Height of a single Character-Cell in Pixels
+const CELL_HEIGHT: usize = 20;
+/// The Factor by which the Glyphs of the Font are scaled up
+const SCALE: usize = 2;
+
+/// The Color of the Background of the Image
+const BACKGROUND: [u8; 3] = [255, 255, 255];
+/// The Color of everything that is not colored otherwise
+const FOREGROUND: [u8; 3] = [30, 30, 30];
+
+/// Displays the Graph as an Image, if the Terminal supports one of the [`ImageProtocol`]s, or as
+/// Text otherwise
+pub fn display<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>)
+where
+    ID: Hash + Eq,
+{
+    fdisplay(
+        graph,
+        config,
+        ImageProtocol::detect(),
+        std::io::stdout().lock(),
+    );
+}
+
+/// Writes the Graph to the given Output Target as an Image using the given Protocol, or as Text if
+/// no Protocol is given or a Label can not be drawn
+pub fn fdisplay<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    protocol: Option<ImageProtocol>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    let layout = Layout::compute(graph, config);
+    let bitmap = protocol.and_then(|protocol| {
+        let buffer = layout.render_buffer(config);
+        let boxes = layout.node_positions(config).into_values().map(|p| {
+            let color = buffer
+                .get(p.column, p.row)
+                .and_then(|cell| cell.color.clone());
+            (p.column, p.row, p.width, p.height, color)
+        });
+        let boxes: Vec<_> = boxes.collect();
+        Some((protocol, Bitmap::rasterize(&buffer, &boxes, config)?))
+    });
+    let Some((protocol, bitmap)) = bitmap else {
+        layout.fdisplay(config, dest);
+        return;
+    };
+
+    let _ = match protocol {
+        ImageProtocol::Kitty => bitmap.write_kitty(&mut dest),
+        ImageProtocol::Sixel => bitmap.write_sixel(&mut dest),
+    };
+    let _ = writeln!(dest);
+}
+
+/// The Shape an Edge-Glyph is drawn as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stroke {
+    Vertical,
+    Horizontal,
+    Crossing,
+    ArrowDown,
+    ArrowUp,
+    ArrowBoth,
+}
+
+impl Stroke {
+    /// The Shape of the Glyph, if it is one of the Line-Glyphs of the Config or of the presets
+    fn of(c: char, config: &Config<impl Sized, impl Sized>) -> Option<Self> {
+        let presets = [
+            LineGlyphBuilder::ascii(),
+            LineGlyphBuilder::unicode(),
+            LineGlyphBuilder::heavy(),
+            LineGlyphBuilder::double(),
+            LineGlyphBuilder::dashed(),
+            LineGlyphBuilder::dotted(),
+        ]
+        .map(LineGlyphBuilder::finish);
+        let glyphs = std::iter::once(&config.line_glyphs)
+            .chain(config.patterns.iter())
+            .chain(presets.iter());
+
+        let matching = |glyphs: &LineGlyphs| {
+            if c == glyphs.arrow_both {
+                Some(Self::ArrowBoth)
+            } else if c == glyphs.arrow_down {
+                Some(Self::ArrowDown)
+            } else if c == glyphs.arrow_up {
+                Some(Self::ArrowUp)
+            } else if c == glyphs.crossing {
+                Some(Self::Crossing)
+            } else if [glyphs.vertical, glyphs.passthrough].contains(&c) {
+                Some(Self::Vertical)
+            } else if [glyphs.horizontal, glyphs.arrow_left, glyphs.arrow_right].contains(&c) {
+                Some(Self::Horizontal)
+            } else {
+                None
+            }
+        };
+        glyphs.into_iter().find_map(matching)
+    }
+}
+
+/// An Image, where every Pixel is either the Background or has a Color
+struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Option<[u8; 3]>>,
+    /// The Color used for the Pixels that are currently drawn
+    pen: [u8; 3],
+}
+
+impl Bitmap {
+    /// Draws every Cell of the Buffer into its own Area of the Image and a Box around every Node,
+    /// which is given by its Column, Row, Width, Height and Color.
+    ///
+    /// Returns `None` if a Label contains a Character, which is not part of the Font.
+    fn rasterize<ID, T>(
+        buffer: &RenderBuffer<'_, ID>,
+        boxes: &[(usize, usize, usize, usize, Option<Color>)],
+        config: &Config<ID, T>,
+    ) -> Option<Self> {
+        let width = buffer.width().max(1) * CELL_WIDTH;
+        let height = buffer.height().max(1) * CELL_HEIGHT;
+        let mut bitmap = Self {
+            width,
+            height,
+            pixels: vec![None; width * height],
+            pen: FOREGROUND,
+        };
+
+        for (row, cells) in buffer.rows().iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                let (x, y) = (column * CELL_WIDTH, row * CELL_HEIGHT);
+                bitmap.pen = cell.color.clone().map_or(FOREGROUND, rgb);
+                match (cell.node.is_some(), Stroke::of(cell.glyph, config)) {
+                    (false, Some(stroke)) => bitmap.draw_stroke(x, y, stroke),
+                    _ if cell.glyph == ' ' => {}
+                    _ => bitmap.draw_glyph(x, y, cell.glyph)?,
+                }
+            }
+        }
+
+        for (column, row, columns, rows, color) in boxes {
+            bitmap.pen = color.clone().map_or(FOREGROUND, rgb);
+            bitmap.draw_box(
+                column * CELL_WIDTH,
+                row * CELL_HEIGHT,
+                columns * CELL_WIDTH,
+                rows * CELL_HEIGHT,
+            );
+        }
+        Some(bitmap)
+    }
+
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                self.pixels[py * self.width + px] = Some(self.pen);
+            }
+        }
+    }
+
+    /// Draws the Outline of the Area, where the Lines of the Edges end on its upper and lower Side
+    fn draw_box(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        let (x, y) = (x.saturating_sub(2), y.saturating_sub(2));
+        let (width, height) = (width + 4, height + 4);
+        self.fill(x, y, width, 2);
+        self.fill(x, y + height - 2, width, 2);
+        self.fill(x, y, 2, height);
+        self.fill(x + width - 2, y, 2, height);
+    }
+
+    /// Draws the Shape into the Cell starting at the given Coordinates. The Lines span the entire
+    /// Cell, so that they connect with their neighbours.
+    fn draw_stroke(&mut self, x: usize, y: usize, stroke: Stroke) {
+        let (center_x, center_y) = (x + CELL_WIDTH / 2 - 1, y + CELL_HEIGHT / 2 - 1);
+        match stroke {
+            Stroke::Vertical => self.fill(center_x, y, 2, CELL_HEIGHT),
+            Stroke::Horizontal => self.fill(x, center_y, CELL_WIDTH, 2),
+            Stroke::Crossing => {
+                self.fill(center_x, y, 2, CELL_HEIGHT);
+                self.fill(x, center_y, CELL_WIDTH, 2);
+            }
+            Stroke::ArrowDown => self.draw_arrow(x, y, true),
+            Stroke::ArrowUp => self.draw_arrow(x, y, false),
+            Stroke::ArrowBoth => {
+                self.draw_arrow(x, y, true);
+                self.draw_arrow(x, y, false);
+            }
+        }
+    }
+
+    /// Draws an Arrow pointing up or down, which connects to the Line above or below it
+    fn draw_arrow(&mut self, x: usize, y: usize, down: bool) {
+        let center_x = x + CELL_WIDTH / 2 - 1;
+        let half = CELL_HEIGHT / 2;
+        let stem_y = if down { y } else { y + half };
+        self.fill(center_x, stem_y, 2, half);
+
+        let tip_y = if down { y + half } else { y + 2 };
+        for step in 0..CELL_WIDTH / 2 {
+            let row = match down {
+                true => tip_y + half - 2 - step.min(half - 2),
+                false => tip_y + step.min(half - 2),
+            };
+            let spread = step;
+            self.fill(center_x.saturating_sub(spread), row, 2 + 2 * spread, 1);
+        }
+    }
+
+    /// Draws the Character using the built-in 5x7 Font, returns `None` if it is not part of it
+    fn draw_glyph(&mut self, x: usize, y: usize, c: char) -> Option<()> {
+        let c = match c {
+            '…' => '~',
+            c if c.is_ascii_graphic() => c,
+            _ => return None,
+        };
+        let glyph = FONT[c as usize - 0x20];
+
+        let (offset_x, offset_y) = ((CELL_WIDTH - 5 * SCALE) / 2, (CELL_HEIGHT - 7 * SCALE) / 2);
+        for (column, bits) in glyph.iter().enumerate() {
+            for row in 0..7 {
+                if bits & (1 << row) != 0 {
+                    self.fill(
+                        x + offset_x + column * SCALE,
+                        y + offset_y + row * SCALE,
+                        SCALE,
+                        SCALE,
+                    );
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Sends the Image as RGB-Data using the kitty Graphics-Protocol
+    fn write_kitty<W>(&self, dest: &mut W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        let data: Vec<u8> = self
+            .pixels
+            .iter()
+            .flat_map(|pixel| pixel.unwrap_or(BACKGROUND))
+            .collect();
+        let encoded = base64(&data);
+
+        // The Data has to be split into Chunks of at most 4096 Bytes
+        let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(4096).collect();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(index + 1 < chunks.len());
+            let chunk = std::str::from_utf8(chunk).expect("Base64 is always valid UTF-8");
+            match index {
+                0 => write!(
+                    dest,
+                    "\x1b_Ga=T,f=24,s={},v={},m={more};{chunk}\x1b\\",
+                    self.width, self.height
+                )?,
+                _ => write!(dest, "\x1b_Gm={more};{chunk}\x1b\\")?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the Image using the Sixel-Format, where every Color of the Image, including the
+    /// Background, is registered in the Palette
+    fn write_sixel<W>(&self, dest: &mut W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        write!(dest, "\x1bPq\"1;1;{};{}", self.width, self.height)?;
+
+        let mut palette: Vec<Option<[u8; 3]>> = vec![None];
+        for pixel in self.pixels.iter() {
+            if !palette.contains(pixel) {
+                palette.push(*pixel);
+            }
+        }
+        for (index, color) in palette.iter().enumerate() {
+            let [r, g, b] = color
+                .unwrap_or(BACKGROUND)
+                .map(|c| u16::from(c) * 100 / 255);
+            write!(dest, "#{index};2;{r};{g};{b}")?;
+        }
+
+        // Every Sixel encodes a column of 6 Pixels
+        for band in (0..self.height).step_by(6) {
+            for (color, pixel) in palette.iter().enumerate() {
+                let sixels: Vec<u8> = (0..self.width)
+                    .map(|x| {
+                        let bits = (0..6)
+                            .filter(|row| band + row < self.height)
+                            .filter(|row| self.pixels[(band + row) * self.width + x] == *pixel)
+                            .fold(0, |bits, row| bits | (1 << row));
+                        63 + bits
+                    })
+                    .collect();
+                if sixels.iter().all(|sixel| *sixel == 63) {
+                    continue;
+                }
+
+                write!(dest, "#{color}")?;
+                for run in sixels.chunk_by(|a, b| a == b) {
+                    let sixel = run[0] as char;
+                    match run.len() {
+                        1..=3 => write!(dest, "{}", sixel.to_string().repeat(run.len()))?,
+                        length => write!(dest, "!{length}{sixel}")?,
+                    }
+                }
+                write!(dest, "$")?;
+            }
+            write!(dest, "-")?;
+        }
+
+        write!(dest, "\x1b\\")
+    }
+}
+
+/// The RGB-Values of the ANSI-Color, where other Codes use the Foreground-Color
+fn rgb(color: Color) -> [u8; 3] {
+    match usize::from(color) {
+        30 | 90 => [0, 0, 0],
+        31 | 91 => [205, 49, 49],
+        32 | 92 => [13, 160, 48],
+        33 | 93 => [200, 150, 0],
+        34 | 94 => [36, 114, 200],
+        35 | 95 => [188, 63, 188],
+        36 | 96 => [17, 168, 205],
+        37 | 97 => [150, 150, 150],
+        _ => FOREGROUND,
+    }
+}
+
+/// Encodes the Data using the standard Base64-Alphabet with Padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let combined = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => {
+                    let value = (combined >> (18 - 6 * index)) & 0x3F;
+                    result.push(ALPHABET[value as usize] as char);
+                }
+                false => result.push('='),
+            }
+        }
+    }
+    result
+}
+
+/// A 5x7 Font for the printable ASCII-Characters, starting at the Space. Every Glyph consists of 5
+/// Columns, where the lowest Bit is the top Row.
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x14, 0x08, 0x3E, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9v", base64(b"foo"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+    }
+
+    #[test]
+    fn protocols() {
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second")]);
+        graph.add_edges([(0, 1)]);
+
+        let render = |protocol| {
+            let mut output = Vec::new();
+            fdisplay(&graph, &config, protocol, &mut output);
+            String::from_utf8(output).unwrap()
+        };
+
+        let kitty = render(Some(ImageProtocol::Kitty));
+        assert!(kitty.starts_with("\x1b_Ga=T,f=24,"));
+        assert!(kitty.trim_end().ends_with("\x1b\\"));
+
+        let sixel = render(Some(ImageProtocol::Sixel));
+        assert!(sixel.starts_with("\x1bPq\""));
+        assert!(sixel.trim_end().ends_with("-\x1b\\"));
+
+        assert!(render(None).contains("(0)"));
+    }
+
+    #[test]
+    fn colors_and_boxes() {
+        let config = Config::new(IDFormatter::new(), 3).default_colors();
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second")]);
+        graph.add_edges([(0, 1)]);
+
+        let layout = Layout::compute(&graph, &config);
+        let buffer = layout.render_buffer(&config);
+        let position = layout.node_positions(&config)[&0];
+        let bitmap = Bitmap::rasterize(
+            &buffer,
+            &[(position.column, position.row, position.width, 1, None)],
+            &config,
+        )
+        .unwrap();
+
+        // The Edge is drawn in its Color and not only in the Foreground-Color
+        let edge = layout.edge_cells(&config)[0];
+        let color = buffer.get(edge.column, edge.row).unwrap().color.clone();
+        assert!(bitmap.pixels.contains(&Some(rgb(color.unwrap()))));
+
+        // The Box is drawn just outside of the Label
+        let (x, y) = (
+            position.column * CELL_WIDTH + CELL_WIDTH,
+            (position.row * CELL_HEIGHT).saturating_sub(1),
+        );
+        assert_eq!(Some(FOREGROUND), bitmap.pixels[y * bitmap.width + x]);
+    }
+
+    #[test]
+    fn fallback_to_text() {
+        let config = Config::new(crate::ValueFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "grüße")]);
+
+        let mut output = Vec::new();
+        fdisplay(&graph, &config, Some(ImageProtocol::Kitty), &mut output);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("grüße"), "{output}");
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fmt::Display, hash::Hash, io::Read};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{DirectedGraph, GraphError};
+
+/// The Schema of [`DirectedGraph::from_json_adjacency`]
+#[derive(Deserialize)]
+#[serde(bound = "ID: DeserializeOwned")]
+struct Adjacency<ID> {
+    nodes: Vec<ID>,
+    #[serde(default)]
+    edges: Vec<(ID, ID)>,
+}
+
+/// The Error returned when loading a Graph from JSON
+#[derive(Debug)]
+pub enum JsonError<ID> {
+    /// The Input is not valid JSON or does not match the Schema
+    Parse(serde_json::Error),
+    /// The Edges refer to Nodes, that are not listed in the Input
+    Graph(GraphError<ID>),
+}
+
+impl<ID> Display for JsonError<ID>
+where
+    ID: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "{error}"),
+            Self::Graph(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl<ID> std::error::Error for JsonError<ID> where ID: std::fmt::Debug + Display {}
+
+impl<ID> DirectedGraph<ID, ()>
+where
+    ID: Hash + Eq + DeserializeOwned,
+{
+    /// Loads a Graph from JSON in the form of `{ "nodes": [...], "edges": [[from, to], ...] }`,
+    /// which is available with the `json` Feature.
+    ///
+    /// The IDs can be anything that can be deserialized, like Strings or Numbers, and every Edge
+    /// has to refer to Nodes that are listed in `nodes`. The `edges` can be left out for Graphs
+    /// without any Edges.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter};
+    ///
+    /// let json = r#"{ "nodes": ["a", "b", "c"], "edges": [["a", "b"], ["a", "c"]] }"#;
+    /// let graph = DirectedGraph::<String, ()>::from_json_adjacency(json.as_bytes()).unwrap();
+    /// assert_eq!(2, graph.successors(&"a".to_string()).count());
+    ///
+    /// termgraph::display(&graph, &Config::new(IDFormatter::new(), 3));
+    /// ```
+    pub fn from_json_adjacency<R>(reader: R) -> Result<Self, JsonError<ID>>
+    where
+        R: Read,
+    {
+        let adjacency: Adjacency<ID> = serde_json::from_reader(reader).map_err(JsonError::Parse)?;
+
+        let mut graph = Self::with_capacity(adjacency.nodes.len(), adjacency.edges.len());
+        graph.add_nodes(adjacency.nodes.into_iter().map(|id| (id, ())));
+        graph
+            .try_add_edges(adjacency.edges)
+            .map_err(JsonError::Graph)?;
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacency() {
+        let json = r#"{ "nodes": [0, 1, 2], "edges": [[0, 1], [1, 2], [2, 0]] }"#;
+        let graph = DirectedGraph::<usize, ()>::from_json_adjacency(json.as_bytes()).unwrap();
+        assert_eq!(3, graph.nodes().count());
+        assert_eq!(3, graph.edges().count());
+
+        let graph = DirectedGraph::<usize, ()>::from_json_adjacency(&b"{ \"nodes\": [0] }"[..]);
+        assert_eq!(0, graph.unwrap().edges().count());
+
+        let json = r#"{ "nodes": [0], "edges": [[0, 1]] }"#;
+        assert!(matches!(
+            DirectedGraph::<usize, ()>::from_json_adjacency(json.as_bytes()),
+            Err(JsonError::Graph(GraphError::MissingEdgeNodes(edges))) if edges == vec![(0, 1)]
+        ));
+
+        let json = r#"{ "edges": [] }"#;
+        assert!(matches!(
+            DirectedGraph::<usize, ()>::from_json_adjacency(json.as_bytes()),
+            Err(JsonError::Parse(_))
+        ));
+    }
+}
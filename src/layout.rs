@@ -0,0 +1,1299 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    time::Instant,
+};
+
+use crate::{
+    acyclic::AcyclicDirectedGraph,
+    config::{BackEdgeStyle, OrderFn},
+    grid::{Canvas, Entry, EntryNode, Grid, TextCanvas},
+    levels::{GraphLevels, Level, Levels},
+    names::{self, NodeNames},
+    ordering,
+    record::Recording,
+    Clusters, Config, DirectedGraph, DisplayOptions, LayoutWarning, NodeContext,
+};
+
+/// The Parts of a [`Layout`] that only depend on the Nodes and Edges of the Graph, which are kept
+/// by a [`RenderSession`](crate::RenderSession) to display the same Graph again
+#[derive(Debug)]
+pub(crate) struct LayoutShape<ID> {
+    /// The Edges that were reversed or omitted to break the Cycles in the Graph
+    back_edges: Vec<(ID, ID)>,
+    /// The ordered Nodes of every Level
+    levels: Vec<Vec<ID>>,
+    /// The Index of the shorter Name used by every shortened Node
+    shortened: HashMap<ID, usize>,
+}
+
+/// The Area a Node occupies in the Output of a [`Layout`], see [`Layout::node_positions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodePosition {
+    /// The Column of the first Glyph of the Label
+    pub column: usize,
+    /// The Row of the first Line of the Label
+    pub row: usize,
+    /// The number of Columns reserved for the Label
+    pub width: usize,
+    /// The number of Lines of the Label
+    pub height: usize,
+}
+
+impl NodePosition {
+    /// Checks if the given Column and Row are part of the Area
+    pub fn contains(&self, column: usize, row: usize) -> bool {
+        (self.column..self.column + self.width).contains(&column)
+            && (self.row..self.row + self.height).contains(&row)
+    }
+}
+
+/// A single Glyph of the Line of an Edge in the Output of a [`Layout`], see
+/// [`Layout::edge_cells`]
+#[derive(Debug, PartialEq, Eq)]
+pub struct EdgeCell<'g, ID> {
+    /// The Column of the Glyph
+    pub column: usize,
+    /// The Row of the Glyph
+    pub row: usize,
+    /// The Source of the Edge, which is `None` if Edges from multiple Sources share the Glyph
+    pub src: Option<&'g ID>,
+    /// The Target of the Edge, which is `None` if the Glyph is shared by Edges to multiple
+    /// Targets
+    pub target: Option<&'g ID>,
+}
+
+impl<ID> Clone for EdgeCell<'_, ID> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<ID> Copy for EdgeCell<'_, ID> {}
+
+/// Summarizes a rendered [`Layout`], see [`Layout::report`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderReport {
+    /// The number of Levels the Nodes were placed on
+    pub levels: usize,
+    /// The number of Columns the Graph spans, without the Line-Prefix or Gutter
+    pub max_width: usize,
+    /// The number of Edges that were reversed or omitted to break the Cycles
+    pub reversed_edges: usize,
+    /// The number of Labels that were cut off at the [max label width](Config::max_label_width)
+    pub truncated_labels: usize,
+    /// The number of Glyphs at which Edges without a common Source or Target cross each other
+    pub crossings: usize,
+}
+
+/// Numbers describing the Quality of a [`Layout`], see [`Layout::metrics`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutMetrics {
+    /// The number of Glyphs at which Edges without a common Source or Target cross each other
+    pub crossings: usize,
+    /// The number of Glyphs covered by the Lines of all the Edges. Lines shared by multiple Edges
+    /// of the same Source or Target are only counted once
+    pub edge_length: usize,
+    /// The Width of the Graph divided by its Height, both measured in Glyphs
+    pub aspect_ratio: f64,
+}
+
+/// The computed Layout of a Graph, which can be displayed any number of times without having to
+/// compute it again.
+///
+/// This is mostly useful if the Graph is displayed repeatedly, while only the Labels of the Nodes
+/// change, as the Labels can be updated using [`Layout::relabel`] without recomputing the entire
+/// Layout.
+///
+/// # Note
+/// The Layout borrows the Graph it was computed for, so a Graph that is updated in place should
+/// be laid out as a Snapshot, while the Labels are taken from the updated Graph when relabeling.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, Layout, NodeFormat};
+///
+/// struct CounterFormatter {}
+///
+/// impl NodeFormat<&str, usize> for CounterFormatter {
+///     fn format_node(&self, id: &&str, value: &usize) -> String {
+///         format!("({id}: {value})")
+///     }
+/// }
+///
+/// let config = Config::new(CounterFormatter {}, 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([("requests", 9), ("errors", 0)]);
+/// graph.add_edges([("requests", "errors")]);
+///
+/// let snapshot = graph.clone();
+/// let mut layout = Layout::compute(&snapshot, &config);
+/// layout.fdisplay(&config, std::io::stdout().lock());
+///
+/// // Update the Value of a Node and only relabel the Layout
+/// graph.add_nodes([("requests", 10)]);
+/// layout.relabel(&graph, &config);
+/// layout.fdisplay(&config, std::io::stdout().lock());
+/// ```
+pub struct Layout<'g, ID, T>
+where
+    ID: Hash + Eq,
+{
+    agraph: AcyclicDirectedGraph<'g, ID, T>,
+    names: NodeNames<'g, ID>,
+    grid: Grid<'g, ID>,
+    /// The Edges that were reversed or omitted to break the Cycles in the Graph
+    back_edges: Vec<(&'g ID, &'g ID)>,
+    /// The Level every Node was placed on
+    node_levels: HashMap<&'g ID, usize>,
+    /// The ordered Nodes of every Level
+    levels: Vec<Level<'g, ID>>,
+    /// The Edges that were reversed and are routed back up the Graph
+    reved_edges: Vec<(&'g ID, &'g ID)>,
+    /// The Labels used instead of formatting the Nodes, like for collapsed Components
+    labels: HashMap<&'g ID, String>,
+    /// The Nodes with Edges to Nodes that are not part of the Layout, which are marked with a Stub
+    stubs: HashSet<&'g ID>,
+    /// The number of Labels that were cut off
+    truncated: usize,
+    /// The Index of the shorter Name used by every shortened Node, see [`Config::shorten_labels`]
+    shortened: HashMap<&'g ID, usize>,
+    /// The Labels of the Edges, in the Orientation they are drawn in
+    edge_labels: HashMap<(&'g ID, &'g ID), &'g str>,
+}
+
+impl<'g, ID, T> Layout<'g, ID, T>
+where
+    ID: Hash + Eq,
+{
+    /// Computes the Layout for the given Graph using the provided Config
+    pub fn compute(graph: &'g DirectedGraph<ID, T>, config: &Config<ID, T>) -> Self {
+        Self::compute_with(graph, config, &DisplayOptions::new())
+    }
+
+    /// Computes the Layout for the given Graph, while only including the Nodes allowed by the
+    /// Filter of the Options
+    pub fn compute_with(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+    ) -> Self {
+        let started = Instant::now();
+
+        let filter = |id: &ID, value: &T| options.filter.as_ref().is_none_or(|f| f(id, value));
+        let omit = config.back_edge_style == BackEdgeStyle::Omit;
+
+        let (agraph, back_edges, labels) = match config.chain_length {
+            Some(min_length) => {
+                // Every long enough Chain is represented by its first Node
+                let mut representatives: HashMap<&'g ID, &'g ID> = HashMap::new();
+                let mut labels: HashMap<&'g ID, String> = HashMap::new();
+                for chain in graph.chains(filter) {
+                    if chain.len() < min_length.max(3) {
+                        continue;
+                    }
+
+                    let (first, last) = (chain[0], chain[chain.len() - 1]);
+                    let id = |id| config.id_label(id).unwrap_or_default();
+                    let label = format!(
+                        "({} → … {} nodes … → {})",
+                        id(first),
+                        chain.len() - 2,
+                        id(last)
+                    );
+                    labels.insert(first, label);
+                    for id in chain {
+                        representatives.insert(id, first);
+                    }
+                }
+
+                let (agraph, back_edges) = graph.to_merged(
+                    &representatives,
+                    filter,
+                    !omit,
+                    config.rank.as_deref(),
+                    config.reversal_cost.as_deref(),
+                    config.deadline(started),
+                );
+                (agraph, back_edges, labels)
+            }
+            None => {
+                let (agraph, back_edges) = graph.to_acyclic_with(
+                    filter,
+                    !omit,
+                    config.rank.as_deref(),
+                    config.reversal_cost.as_deref(),
+                    None,
+                    config.deadline(started),
+                );
+                (agraph, back_edges, HashMap::new())
+            }
+        };
+        let reved_edges = Self::reversed(&agraph, &back_edges, omit);
+
+        let edge_labels = Self::edge_labels(graph, &agraph);
+        Self::from_acyclic(
+            agraph,
+            back_edges,
+            reved_edges,
+            labels,
+            HashSet::new(),
+            config,
+            started,
+            None,
+            edge_labels,
+        )
+    }
+
+    /// Computes only the Level every Node would be placed on, without placing the Nodes in the
+    /// Grid or routing the Edges, where the first Level is `0`.
+    ///
+    /// The Levels take the Config into account, like the
+    /// [maximum number of Nodes per Layer](Config::max_per_layer) or the [Ranks](Config::rank),
+    /// but Chains are never [collapsed](Config::collapse_chains).
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter, Layout};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+    ///
+    /// let levels = Layout::compute_levels(&graph, &config);
+    /// assert_eq!(2, levels[&2]);
+    /// ```
+    pub fn compute_levels(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+    ) -> HashMap<&'g ID, usize> {
+        Levels::compute(graph, config).node_levels()
+    }
+
+    /// Computes the Levels of the Graph, like they would be computed for the Layout itself, where
+    /// the `order` makes them independent of the Order the Nodes are stored in
+    pub(crate) fn graph_levels(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        order: Option<&OrderFn<'_, ID>>,
+    ) -> Vec<Level<'g, ID>> {
+        let omit = config.back_edge_style == BackEdgeStyle::Omit;
+        let (agraph, _) = graph.to_acyclic_with(
+            |_, _| true,
+            !omit,
+            config.rank.as_deref(),
+            config.reversal_cost.as_deref(),
+            order,
+            None,
+        );
+
+        let (names, _) = Self::format_names(
+            &agraph,
+            config,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashSet::new(),
+            false,
+        );
+        GraphLevels::construct(&agraph, config, &names, order).0
+    }
+
+    /// Computes the Layout for the given Graph, where the Nodes are placed on the given Levels
+    /// instead of computing the Levels from the Edges, see [`Levels`] for more details.
+    ///
+    /// Edges leading to a higher Level are handled like the Edges closing a Cycle, see
+    /// [`BackEdgeStyle`](crate::BackEdgeStyle). Edges between Nodes on the same Level can not be
+    /// drawn and are only listed below the Graph, when the Back-Edges are omitted. Nodes that are
+    /// not part of any Level are placed on an additional Level at the bottom.
+    pub fn compute_with_levels(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        levels: &Levels<'g, ID>,
+    ) -> Self {
+        let started = Instant::now();
+        let omit = config.back_edge_style == BackEdgeStyle::Omit;
+
+        let node_levels = levels.node_levels();
+        let rank = |id: &ID, _: &T| node_levels.get(id).copied().unwrap_or(levels.len());
+        let (agraph, back_edges) = graph.to_acyclic_with(
+            |_, _| true,
+            !omit,
+            Some(&rank),
+            None,
+            None,
+            config.deadline(started),
+        );
+        let reved_edges = Self::reversed(&agraph, &back_edges, omit);
+        let placed = levels.placed(&agraph);
+
+        let edge_labels = Self::edge_labels(graph, &agraph);
+        Self::from_acyclic(
+            agraph,
+            back_edges,
+            reved_edges,
+            HashMap::new(),
+            HashSet::new(),
+            config,
+            started,
+            Some(placed),
+            edge_labels,
+        )
+    }
+
+    /// Computes the Layout for the Part of the given Graph, that is reachable from the Roots
+    /// within `depth` Edges.
+    ///
+    /// The Nodes with Edges leading out of the displayed Part are marked with a Stub, like
+    /// `(node) → …`.
+    pub fn compute_reachable<I>(
+        graph: &'g DirectedGraph<ID, T>,
+        roots: I,
+        depth: usize,
+        config: &Config<ID, T>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = ID>,
+    {
+        let started = Instant::now();
+
+        // Breadth-First-Search, where every Node is reached through a shortest Path
+        let mut reached: HashSet<&'g ID> = HashSet::new();
+        let mut current: Vec<&'g ID> = roots
+            .into_iter()
+            .filter_map(|root| graph.get_key(&root))
+            .filter(|root| reached.insert(*root))
+            .collect();
+        for _ in 0..depth {
+            current = current
+                .into_iter()
+                .flat_map(|id| graph.successors(id))
+                .filter(|succ| reached.insert(*succ))
+                .collect();
+        }
+
+        Self::from_reached(graph, reached, config, started)
+    }
+
+    /// Computes the Layout for the Neighborhood of the given Node, which contains all the Nodes
+    /// within `depth` Edges of it in both Directions, see [`DirectedGraph::neighborhood`].
+    ///
+    /// Like with [`Layout::compute_reachable`], the Nodes with Edges leading out of the displayed
+    /// Part are marked with a Stub.
+    pub fn compute_neighborhood(
+        graph: &'g DirectedGraph<ID, T>,
+        id: &ID,
+        depth: usize,
+        config: &Config<ID, T>,
+    ) -> Self {
+        let started = Instant::now();
+        let reached = graph.neighborhood(id, depth).into_iter().collect();
+        Self::from_reached(graph, reached, config, started)
+    }
+
+    /// Computes the Layout for the reached Nodes and the Edges between them, where the Nodes with
+    /// Edges to other Nodes are marked with a Stub
+    pub(crate) fn from_reached(
+        graph: &'g DirectedGraph<ID, T>,
+        reached: HashSet<&'g ID>,
+        config: &Config<ID, T>,
+        started: Instant,
+    ) -> Self {
+        let stubs = reached
+            .iter()
+            .copied()
+            .filter(|id| graph.successors(id).any(|succ| !reached.contains(succ)))
+            .collect();
+
+        let omit = config.back_edge_style == BackEdgeStyle::Omit;
+        let (agraph, back_edges) = graph.to_acyclic_with(
+            |id, _| reached.contains(id),
+            !omit,
+            config.rank.as_deref(),
+            config.reversal_cost.as_deref(),
+            None,
+            config.deadline(started),
+        );
+        let reved_edges = Self::reversed(&agraph, &back_edges, omit);
+
+        let edge_labels = Self::edge_labels(graph, &agraph);
+        Self::from_acyclic(
+            agraph,
+            back_edges,
+            reved_edges,
+            HashMap::new(),
+            stubs,
+            config,
+            started,
+            None,
+            edge_labels,
+        )
+    }
+
+    /// Computes the Layout for the Condensation of the given Graph, in which every Strongly
+    /// Connected Component is collapsed into a single Node.
+    ///
+    /// The Components with more than one Node are labeled with their number of Nodes, like
+    /// `[3 nodes]`, while the other Nodes are formatted as usual.
+    pub fn compute_condensed(graph: &'g DirectedGraph<ID, T>, config: &Config<ID, T>) -> Self {
+        let started = Instant::now();
+
+        let (agraph, sizes) = graph.to_condensed(config.rank.as_deref());
+        let labels = sizes
+            .into_iter()
+            .filter(|(_, size)| *size > 1)
+            .map(|(id, size)| (id, format!("[{size} nodes]")))
+            .collect();
+
+        Self::from_acyclic(
+            agraph,
+            Vec::new(),
+            Vec::new(),
+            labels,
+            HashSet::new(),
+            config,
+            started,
+            None,
+            HashMap::new(),
+        )
+    }
+
+    /// Computes the Layout for the given Graph, in which every collapsed Cluster is displayed as a
+    /// single summary Node, see [`Clusters`]
+    pub fn compute_clustered(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        clusters: &Clusters<ID, T>,
+    ) -> Self {
+        let started = Instant::now();
+
+        // Every collapsed Cluster is represented by the first of its Nodes
+        let mut summaries: HashMap<String, (&'g ID, usize)> = HashMap::new();
+        let mut representatives: HashMap<&'g ID, &'g ID> = HashMap::new();
+        for (id, value) in graph.nodes() {
+            let Some(name) = clusters.cluster_of(id, value) else {
+                continue;
+            };
+            if !clusters.is_collapsed(&name) {
+                continue;
+            }
+
+            let summary = summaries.entry(name).or_insert((id, 0));
+            summary.1 += 1;
+            representatives.insert(id, summary.0);
+        }
+        let labels = summaries
+            .into_iter()
+            .map(|(name, (id, size))| (id, format!("[{name}: {size} nodes]")))
+            .collect();
+
+        let omit = config.back_edge_style == BackEdgeStyle::Omit;
+        let (agraph, back_edges) = graph.to_merged(
+            &representatives,
+            |_, _| true,
+            !omit,
+            config.rank.as_deref(),
+            config.reversal_cost.as_deref(),
+            config.deadline(started),
+        );
+        let reved_edges = Self::reversed(&agraph, &back_edges, omit);
+
+        let edge_labels = Self::edge_labels(graph, &agraph);
+        Self::from_acyclic(
+            agraph,
+            back_edges,
+            reved_edges,
+            labels,
+            HashSet::new(),
+            config,
+            started,
+            None,
+            edge_labels,
+        )
+    }
+
+    /// The Labels of the Edges of the Graph, that are part of the Layout. Reversed Edges are
+    /// labeled in the Orientation they are drawn in.
+    fn edge_labels(
+        graph: &'g DirectedGraph<ID, T>,
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+    ) -> HashMap<(&'g ID, &'g ID), &'g str> {
+        let drawn =
+            |src: &ID, target: &ID| agraph.successors(src).is_some_and(|s| s.contains(target));
+
+        let mut labels = HashMap::new();
+        let mut reversed = Vec::new();
+        for (src, target, label) in graph.edge_label_entries() {
+            if drawn(src, target) {
+                labels.insert((src, target), label);
+            } else if drawn(target, src) {
+                reversed.push(((target, src), label));
+            }
+        }
+        // The Labels of the Edges drawn in their own Orientation take precedence
+        for (edge, label) in reversed {
+            labels.entry(edge).or_insert(label);
+        }
+        labels
+    }
+
+    /// The Back-Edges, which were reversed instead of being removed. Edges between Nodes of the
+    /// same [Rank](Config::rank) are removed, even if the Back-Edges are not omitted.
+    fn reversed(
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        back_edges: &[(&'g ID, &'g ID)],
+        omit: bool,
+    ) -> Vec<(&'g ID, &'g ID)> {
+        match omit {
+            true => Vec::new(),
+            false => back_edges
+                .iter()
+                .filter(|(src, target)| agraph.successors(target).is_some_and(|s| s.contains(src)))
+                .copied()
+                .collect(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn from_acyclic(
+        agraph: AcyclicDirectedGraph<'g, ID, T>,
+        back_edges: Vec<(&'g ID, &'g ID)>,
+        reved_edges: Vec<(&'g ID, &'g ID)>,
+        labels: HashMap<&'g ID, String>,
+        stubs: HashSet<&'g ID>,
+        config: &Config<ID, T>,
+        started: Instant,
+        levels: Option<Vec<Level<'g, ID>>>,
+        edge_labels: HashMap<(&'g ID, &'g ID), &'g str>,
+    ) -> Self {
+        for (src, target) in back_edges.iter().copied() {
+            config.warn(match src == target {
+                true => LayoutWarning::DroppedSelfLoop { node: src },
+                false => LayoutWarning::BrokenCycle { src, target },
+            });
+        }
+
+        // The Names are formatted again once the Levels are known, in which case only the final
+        // Names report their Warnings
+        let reformat = config.formatter.needs_context() || config.level_hook.is_some();
+        let (names, truncated) =
+            Self::format_names(&agraph, config, &HashMap::new(), &labels, &stubs, !reformat);
+
+        let fixed = levels.is_some() || config.sibling_order.is_some();
+        let (mut levels, shortened) = match levels {
+            Some(levels) => (GraphLevels(levels), HashMap::new()),
+            None => {
+                let short = Self::short_names(&agraph, config, &names, &labels, &stubs);
+                let widths = short
+                    .iter()
+                    .map(|(id, forms)| (*id, forms.iter().map(|f| names::width(f)).collect()))
+                    .collect();
+                let deadline = config.deadline(started);
+                GraphLevels::construct_shortening(&agraph, config, &names, &widths, None, deadline)
+            }
+        };
+        let node_levels: HashMap<&'g ID, usize> = levels
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(index, level)| level.nodes.iter().map(move |id| (*id, index)))
+            .collect();
+
+        // The preliminary Names did not know the Levels of the Nodes yet
+        let (mut names, truncated) = match reformat {
+            true => Self::format_names(&agraph, config, &node_levels, &labels, &stubs, true),
+            false => (names, truncated),
+        };
+        Self::shorten(&agraph, config, &mut names, &labels, &stubs, &shortened);
+
+        let exhausted = config
+            .deadline(started)
+            .is_some_and(|d| Instant::now() >= d);
+        if config.refine_ordering && !fixed && !exhausted {
+            ordering::refine(&agraph, &mut levels.0, config.layout_weight.as_ref());
+        }
+        let grid = match fixed {
+            true => Grid::construct(
+                &agraph,
+                &levels.0,
+                reved_edges.clone(),
+                config,
+                &names,
+                started,
+            ),
+            false => Self::best_grid(
+                &agraph,
+                &mut levels.0,
+                &reved_edges,
+                config,
+                &names,
+                started,
+            ),
+        };
+
+        Self {
+            agraph,
+            names,
+            grid,
+            back_edges,
+            node_levels,
+            levels: levels.0,
+            reved_edges,
+            labels,
+            stubs,
+            truncated,
+            shortened,
+            edge_labels,
+        }
+    }
+
+    /// Replaces the Names of the shortened Nodes with the shorter Name that was chosen for them
+    fn shorten(
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        config: &Config<ID, T>,
+        names: &mut NodeNames<'g, ID>,
+        labels: &HashMap<&'g ID, String>,
+        stubs: &HashSet<&'g ID>,
+        shortened: &HashMap<&'g ID, usize>,
+    ) {
+        if shortened.is_empty() {
+            return;
+        }
+        let mut short = Self::short_names(agraph, config, names, labels, stubs);
+        for (id, form) in shortened {
+            let Some(mut forms) = short.remove(id) else {
+                continue;
+            };
+            // The Labels may have changed, so there might be fewer shorter Names than before
+            let form = (*form).min(forms.len() - 1);
+            names.set(id, forms.swap_remove(form));
+        }
+    }
+
+    /// Tries different Orderings of the Nodes within their Levels, see [`Config::best_of`], and
+    /// returns the Grid with the fewest Crossings, while updating the Levels accordingly.
+    ///
+    /// Only the Grids that contain all the Nodes are compared, as Grids that were cut short by a
+    /// Limit are missing the Crossings of the omitted Levels. Every tried Grid gets the full
+    /// [render time](Config::max_render_time), as the Search has its own Budget.
+    fn best_grid(
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        levels: &mut [Level<'g, ID>],
+        reved_edges: &[(&'g ID, &'g ID)],
+        config: &Config<ID, T>,
+        names: &NodeNames<'g, ID>,
+        started: Instant,
+    ) -> Grid<'g, ID> {
+        let construct = |levels: &[Level<'g, ID>], started: Instant| {
+            Grid::construct(agraph, levels, reved_edges.to_vec(), config, names, started)
+        };
+
+        let mut best = construct(levels, started);
+        let mut best_crossings = best.crossings();
+        let search_started = Instant::now();
+        let mut rng = ordering::Rng::new(config.layout_seed);
+        let mut candidate = levels.to_vec();
+        let weight = config.layout_weight.as_ref();
+        for attempt in 1..config.layout_attempts {
+            let exhausted = config
+                .layout_budget
+                .is_some_and(|budget| search_started.elapsed() >= budget);
+            if (best_crossings == 0 && best.omitted() == 0) || exhausted {
+                break;
+            }
+
+            match attempt {
+                1 => ordering::barycenter(agraph, &mut candidate, true, weight),
+                2 => ordering::barycenter(agraph, &mut candidate, false, weight),
+                _ => {
+                    candidate = levels.to_vec();
+                    for level in candidate.iter_mut() {
+                        rng.shuffle(&mut level.nodes);
+                    }
+                    ordering::barycenter(agraph, &mut candidate, true, weight);
+                }
+            }
+            if config.refine_ordering {
+                ordering::refine(agraph, &mut candidate, weight);
+            }
+
+            let grid = construct(&candidate, Instant::now());
+            if grid.omitted() > 0 {
+                continue;
+            }
+            let crossings = grid.crossings();
+            if best.omitted() > 0 || crossings < best_crossings {
+                (best, best_crossings) = (grid, crossings);
+                levels.clone_from_slice(&candidate);
+            }
+        }
+        best
+    }
+
+    fn format_names(
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        config: &Config<ID, T>,
+        node_levels: &HashMap<&'g ID, usize>,
+        labels: &HashMap<&'g ID, String>,
+        stubs: &HashSet<&'g ID>,
+        warn: bool,
+    ) -> (NodeNames<'g, ID>, usize) {
+        let in_degrees = match config.formatter.needs_context() {
+            true => agraph.in_degrees(),
+            false => HashMap::new(),
+        };
+
+        let mut truncated_labels = 0;
+        let names = NodeNames::construct(agraph.nodes.keys().copied(), |id| {
+            let value = agraph.nodes[id];
+            let level = node_levels.get(id).copied();
+            let context = || NodeContext {
+                in_degree: in_degrees.get(id).copied().unwrap_or(0),
+                out_degree: agraph.successors(id).map_or(0, |s| s.len()),
+                level: level.unwrap_or(0),
+            };
+
+            let mut label = match labels.get(id) {
+                Some(label) => label.clone(),
+                None => config
+                    .formatter
+                    .format_node_lines(id, value, context)
+                    .join("\n"),
+            };
+            for issue in config.formatter.take_issues().into_iter().filter(|_| warn) {
+                config.warn(LayoutWarning::InvalidLabel { node: id, issue });
+            }
+            // The Hook is only applied, once the actual Levels are known
+            if let (Some(hook), Some(level)) = (config.level_hook.as_ref(), level) {
+                label = hook(level, id, value, label);
+            }
+
+            let (label, truncated) = Self::finish_label(config, &label, stubs.contains(id));
+            truncated_labels += usize::from(truncated);
+            if let Some(width) = config.max_label_width.filter(|_| truncated && warn) {
+                config.warn(LayoutWarning::TruncatedLabel { node: id, width });
+            }
+            label
+        });
+        (names, truncated_labels)
+    }
+
+    /// Limits the Width of the Lines of the Label and appends the Stub, returns the final Label and
+    /// whether it was cut off
+    fn finish_label(config: &Config<ID, T>, label: &str, stub: bool) -> (String, bool) {
+        let mut truncated = false;
+        let mut lines: Vec<String> = label
+            .split('\n')
+            .map(|line| {
+                let mut line = line.to_string();
+                if let Some(max_width) = config.max_label_width {
+                    truncated |= names::width(&line) > max_width;
+                    line = names::truncate(line, max_width);
+                }
+                if let Some(width) = config.wrap_labels {
+                    line = names::wrap(line, width);
+                }
+                line
+            })
+            .collect();
+        // The Stub is added after limiting the Width, so it is never cut off
+        if let Some(last) = lines.last_mut().filter(|_| stub) {
+            last.push_str(" → …");
+        }
+        (lines.join("\n"), truncated)
+    }
+
+    /// The shorter Names of the Nodes from the longest to the shortest one, which are used if the
+    /// Names do not fit onto their Level, see [`Config::shorten_labels`]
+    fn short_names(
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        config: &Config<ID, T>,
+        names: &NodeNames<'g, ID>,
+        labels: &HashMap<&'g ID, String>,
+        stubs: &HashSet<&'g ID>,
+    ) -> HashMap<&'g ID, Vec<String>> {
+        if !config.shorten_labels {
+            return HashMap::new();
+        }
+        let in_degrees = match config.formatter.needs_context() {
+            true => agraph.in_degrees(),
+            false => HashMap::new(),
+        };
+
+        agraph
+            .nodes
+            .iter()
+            // The Labels provided by the Layout itself, like the Summary of a Cluster, are kept
+            .filter(|(id, _)| !labels.contains_key(*id))
+            .map(|(id, value)| {
+                let context = || NodeContext {
+                    in_degree: in_degrees.get(id).copied().unwrap_or(0),
+                    out_degree: agraph.successors(id).map_or(0, |s| s.len()),
+                    level: 0,
+                };
+                let candidates = [
+                    config.formatter.format_short(id, value, context),
+                    config.id_label(id).map(|label| format!("({label})")),
+                ];
+
+                let mut width = names.width(id);
+                let forms: Vec<String> = candidates
+                    .into_iter()
+                    .flatten()
+                    .map(|short| Self::finish_label(config, &short, stubs.contains(id)).0)
+                    .filter(|short| {
+                        let shorter = names::width(short) < width;
+                        width = width.min(names::width(short));
+                        shorter
+                    })
+                    .collect();
+                (*id, forms)
+            })
+            .filter(|(_, forms)| !forms.is_empty())
+            .collect()
+    }
+
+    /// Formats the Labels of all the Nodes again using their Values in the given Graph, while
+    /// keeping the Levels and the Order of the Nodes within them.
+    ///
+    /// The Graph is only used to look up the current Values of the Nodes, so it can be a different
+    /// Graph than the one the Layout was computed for, like the updated Original of a Snapshot.
+    /// Nodes that are missing from the Graph keep their previous Value. If the Size of any Label
+    /// changed, the Nodes are placed again within their Levels, otherwise only the Labels are
+    /// replaced.
+    pub fn relabel(&mut self, graph: &DirectedGraph<ID, T>, config: &Config<ID, T>) {
+        let agraph = self.agraph.with_values(graph);
+        let (mut names, truncated) = Layout::format_names(
+            &agraph,
+            config,
+            &self.node_levels,
+            &self.labels,
+            &self.stubs,
+            true,
+        );
+        Layout::shorten(
+            &agraph,
+            config,
+            &mut names,
+            &self.labels,
+            &self.stubs,
+            &self.shortened,
+        );
+
+        let resized = self.agraph.nodes.keys().any(|id| {
+            names.width(id) != self.names.width(id) || names.height(id) != self.names.height(id)
+        });
+        self.names = NodeNames::construct(self.agraph.nodes.keys().copied(), |id| {
+            names.get(id).unwrap_or_default().to_string()
+        });
+        self.truncated = truncated;
+
+        if resized {
+            self.grid = Grid::construct(
+                &self.agraph,
+                &self.levels,
+                self.reved_edges.clone(),
+                config,
+                &self.names,
+                Instant::now(),
+            );
+        }
+    }
+
+    /// The Parts of the Layout that only depend on the Nodes and Edges of the Graph, or `None` if
+    /// the Layout does not contain every Node of the Graph on its own, like with collapsed Chains
+    pub(crate) fn shape(&self) -> Option<LayoutShape<ID>>
+    where
+        ID: Clone,
+    {
+        if !self.labels.is_empty() || !self.stubs.is_empty() {
+            return None;
+        }
+        Some(LayoutShape {
+            back_edges: self
+                .back_edges
+                .iter()
+                .map(|(src, target)| ((*src).clone(), (*target).clone()))
+                .collect(),
+            levels: self
+                .levels
+                .iter()
+                .map(|level| level.nodes.iter().map(|id| (*id).clone()).collect())
+                .collect(),
+            shortened: self
+                .shortened
+                .iter()
+                .map(|(id, form)| ((*id).clone(), *form))
+                .collect(),
+        })
+    }
+
+    /// The final Names of all the Nodes and the number of Labels that were cut off
+    pub(crate) fn owned_names(&self) -> (HashMap<ID, String>, usize)
+    where
+        ID: Clone,
+    {
+        let names = self
+            .agraph
+            .nodes
+            .keys()
+            .map(|id| {
+                (
+                    (*id).clone(),
+                    self.names.get(id).unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+        (names, self.truncated)
+    }
+
+    /// Computes the Layout from the Shape of a previous Layout of a Graph with the same Nodes and
+    /// Edges, which only places the Nodes again within their Levels.
+    ///
+    /// The Cycles are not broken again and the Levels are not distributed again. If the `names`
+    /// are given, they are used instead of formatting the Nodes, together with the number of Labels
+    /// that were cut off.
+    pub(crate) fn from_shape(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        shape: &LayoutShape<ID>,
+        names: Option<(&HashMap<ID, String>, usize)>,
+    ) -> Self {
+        let started = Instant::now();
+        let omit = config.back_edge_style == BackEdgeStyle::Omit;
+
+        let back_edges: Vec<(&'g ID, &'g ID)> = shape
+            .back_edges
+            .iter()
+            .filter_map(|(src, target)| Some((graph.get_key(src)?, graph.get_key(target)?)))
+            .collect();
+        let mut edges: HashMap<&'g ID, HashSet<&'g ID>> = HashMap::new();
+        for (src, target) in graph.edges() {
+            edges.entry(src).or_default().insert(target);
+        }
+        for (src, target) in back_edges.iter().copied() {
+            edges.entry(src).or_default().remove(target);
+            if !omit && src != target {
+                edges.entry(target).or_default().insert(src);
+            }
+        }
+        let agraph = AcyclicDirectedGraph::new(graph.nodes().collect(), edges);
+
+        for (src, target) in back_edges.iter().copied() {
+            config.warn(match src == target {
+                true => LayoutWarning::DroppedSelfLoop { node: src },
+                false => LayoutWarning::BrokenCycle { src, target },
+            });
+        }
+
+        let levels: Vec<Level<'g, ID>> = shape
+            .levels
+            .iter()
+            .map(|level| Level {
+                nodes: level.iter().filter_map(|id| graph.get_key(id)).collect(),
+            })
+            .collect();
+        let node_levels: HashMap<&'g ID, usize> = levels
+            .iter()
+            .enumerate()
+            .flat_map(|(index, level)| level.nodes.iter().map(move |id| (*id, index)))
+            .collect();
+        let shortened: HashMap<&'g ID, usize> = shape
+            .shortened
+            .iter()
+            .filter_map(|(id, form)| Some((graph.get_key(id)?, *form)))
+            .collect();
+
+        let (labels, stubs) = (HashMap::new(), HashSet::new());
+        let (names, truncated) = match names {
+            Some((names, truncated)) => (
+                NodeNames::construct(agraph.nodes.keys().copied(), |id| {
+                    names.get(id).cloned().unwrap_or_default()
+                }),
+                truncated,
+            ),
+            None => {
+                let (mut names, truncated) =
+                    Self::format_names(&agraph, config, &node_levels, &labels, &stubs, true);
+                Self::shorten(&agraph, config, &mut names, &labels, &stubs, &shortened);
+                (names, truncated)
+            }
+        };
+
+        let reved_edges = Self::reversed(&agraph, &back_edges, omit);
+        let grid = Grid::construct(
+            &agraph,
+            &levels,
+            reved_edges.clone(),
+            config,
+            &names,
+            started,
+        );
+        let edge_labels = Self::edge_labels(graph, &agraph);
+
+        Self {
+            agraph,
+            names,
+            grid,
+            back_edges,
+            node_levels,
+            levels,
+            reved_edges,
+            labels,
+            stubs,
+            truncated,
+            shortened,
+            edge_labels,
+        }
+    }
+
+    /// The Area every Node occupies in the Output, when the Layout is displayed using the given
+    /// Config.
+    ///
+    /// The Coordinates are counted in Glyphs from the start of the Output and include the
+    /// [Line-Prefix](Config::line_prefix), the Gutter and the Header of the Swimlanes, so they can
+    /// be used to map Positions on the Terminal back to the Nodes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter, Layout};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second")]);
+    /// graph.add_edges([(0, 1)]);
+    ///
+    /// let layout = Layout::compute(&graph, &config);
+    /// let positions = layout.node_positions(&config);
+    /// assert!(positions[&0].row < positions[&1].row);
+    /// ```
+    pub fn node_positions(&self, config: &Config<ID, T>) -> HashMap<&'g ID, NodePosition> {
+        let (origin_x, origin_y) = self.grid.origin(config);
+
+        let mut positions: HashMap<&'g ID, NodePosition> = HashMap::new();
+        for (x, y, entry) in self.grid.entries() {
+            let id = match entry {
+                Entry::Node(EntryNode::User(id) | EntryNode::UserLine(id, _), _) => *id,
+                _ => continue,
+            };
+            let (column, row) = (origin_x + x, origin_y + y);
+            let position = positions.entry(id).or_insert(NodePosition {
+                column,
+                row,
+                width: 0,
+                height: 0,
+            });
+            position.width = position.width.max(column + 1 - position.column);
+            position.height = position.height.max(row + 1 - position.row);
+        }
+        positions
+    }
+
+    /// The Node displayed at the given Column and Row of the Output, using the same Coordinates
+    /// as [`Layout::node_positions`]
+    pub fn node_at(&self, config: &Config<ID, T>, column: usize, row: usize) -> Option<&'g ID> {
+        let (origin_x, origin_y) = self.grid.origin(config);
+        let (x, y) = (column.checked_sub(origin_x)?, row.checked_sub(origin_y)?);
+
+        self.grid
+            .entries()
+            .find(|(ex, ey, _)| (*ex, *ey) == (x, y))
+            .and_then(|(_, _, entry)| match entry {
+                Entry::Node(EntryNode::User(id) | EntryNode::UserLine(id, _), _) => Some(*id),
+                _ => None,
+            })
+    }
+
+    /// The Glyphs of the Lines of all the Edges in the Output, using the same Coordinates as
+    /// [`Layout::node_positions`]
+    pub fn edge_cells(&self, config: &Config<ID, T>) -> Vec<EdgeCell<'g, ID>> {
+        let (origin_x, origin_y) = self.grid.origin(config);
+
+        self.grid
+            .entries()
+            .filter_map(|(x, y, entry)| {
+                let owner = entry.owner()?;
+                Some(EdgeCell {
+                    column: origin_x + x,
+                    row: origin_y + y,
+                    src: owner.src,
+                    target: owner.target,
+                })
+            })
+            .collect()
+    }
+
+    /// Summarizes the Layout, which is useful to keep track of how readable the Output of a Graph
+    /// is, see [`fdisplay_report`](crate::fdisplay_report)
+    pub fn report(&self) -> RenderReport {
+        RenderReport {
+            levels: self.levels.len(),
+            max_width: self.grid.width(),
+            reversed_edges: self.back_edges.len(),
+            truncated_labels: self.truncated,
+            crossings: self.grid.crossings(),
+        }
+    }
+
+    /// Measures the Quality of the Layout, which allows comparing different Settings, like the
+    /// [Order of Siblings](Config::sort_siblings), on real Graphs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, IDFormatter, Layout};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+    ///
+    /// let metrics = Layout::compute(&graph, &config).metrics();
+    /// assert_eq!(0, metrics.crossings);
+    /// ```
+    pub fn metrics(&self) -> LayoutMetrics {
+        let height = self.grid.height();
+        LayoutMetrics {
+            crossings: self.grid.crossings(),
+            edge_length: self.grid.edge_length(),
+            aspect_ratio: match height {
+                0 => 0.0,
+                height => self.grid.width() as f64 / height as f64,
+            },
+        }
+    }
+
+    /// Writes the Layout to the given Output Target, using the Colors and Glyphs of the Config
+    pub fn fdisplay<W>(&self, config: &Config<ID, T>, dest: W)
+    where
+        W: std::io::Write,
+    {
+        self.fdisplay_with(config, &DisplayOptions::new(), dest);
+    }
+
+    /// Writes the Layout to the given Output Target, like [`Layout::fdisplay`], but with the
+    /// Colors and Highlights of the Options applied on top of the Config
+    ///
+    /// # Note
+    /// The Filter of the Options is only applied when computing the Layout, see
+    /// [`Layout::compute_with`]
+    pub fn fdisplay_with<W>(
+        &self,
+        config: &Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+        mut dest: W,
+    ) where
+        W: std::io::Write,
+    {
+        self.draw(
+            config,
+            options,
+            &mut TextCanvas::new(&mut dest, config, options),
+        );
+    }
+
+    /// Draws the Layout onto the Canvas, which is shared by the Text Output and the
+    /// [`RenderBuffer`](crate::RenderBuffer)
+    pub(crate) fn draw<C>(
+        &self,
+        config: &Config<ID, T>,
+        options: &DisplayOptions<ID, T>,
+        canvas: &mut C,
+    ) where
+        C: Canvas<'g, ID>,
+    {
+        // Do nothing if the graph is empty
+        if self.agraph.nodes.is_empty() {
+            return;
+        }
+
+        let links: HashMap<&ID, String> = match config.hyperlinks.as_ref() {
+            Some(link) => self
+                .agraph
+                .nodes
+                .iter()
+                .filter_map(|(id, value)| Some((*id, link(id, value)?)))
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        self.grid.draw(
+            &self.names,
+            &links,
+            &self.edge_labels,
+            config,
+            options,
+            canvas,
+        );
+        // The trailing empty Line has no Prefix, as it would only consist of trailing whitespace
+        canvas.newline();
+
+        let kind = match config.back_edge_style {
+            BackEdgeStyle::Omit => "back edge",
+            _ if config.debug_layout => "reversed edge",
+            _ => return,
+        };
+        for (src, target) in &self.back_edges {
+            let src = self.names.get(src).unwrap_or_default();
+            let target = self.names.get(target).unwrap_or_default();
+            let line = format!("{} {}: {} -> {}", config.line_prefix, kind, src, target);
+            canvas.draw(&line, None, None, None);
+            canvas.newline();
+        }
+    }
+}
+
+impl<'g, ID, T> Layout<'g, ID, T>
+where
+    ID: Hash + Eq + Display,
+{
+    /// Captures the intermediate Results of the Layout together with its uncolored Output, so it
+    /// can be replayed later on, see [`Recording`] for more details.
+    pub fn record(&self, config: &Config<ID, T>) -> Recording {
+        let key = |id: &ID| id.to_string();
+
+        let nodes = self
+            .agraph
+            .nodes
+            .keys()
+            .map(|id| (key(id), self.names.get(id).unwrap_or_default().to_string()))
+            .collect();
+        let edges = self
+            .agraph
+            .nodes
+            .keys()
+            .flat_map(|src| {
+                let targets = self.agraph.successors(src).into_iter().flatten();
+                targets.map(move |target| (key(src), key(target)))
+            })
+            .collect();
+        let reversed = self
+            .reved_edges
+            .iter()
+            .map(|(src, target)| (key(src), key(target)))
+            .collect();
+        let levels = self
+            .levels
+            .iter()
+            .map(|level| level.nodes.iter().map(|id| key(id)).collect())
+            .collect();
+
+        let mut output = Vec::new();
+        self.grid.fdisplay(
+            &self.names,
+            &HashMap::new(),
+            &HashMap::new(),
+            config,
+            &DisplayOptions::new().colors(false),
+            &mut output,
+        );
+
+        Recording {
+            nodes,
+            edges,
+            reversed,
+            levels,
+            output: String::from_utf8_lossy(&output).into_owned(),
+        }
+    }
+}
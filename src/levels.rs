@@ -1,10 +1,160 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::Instant,
+};
 
 use crate::{
     acyclic::{AcyclicDirectedGraph, MinimalAcyclicDirectedGraph},
-    Config,
+    config::{EdgeWeight, NodeRank, OrderFn},
+    names::NodeNames,
+    Config, DirectedGraph, Layout,
 };
 
+/// The Nodes of a Graph grouped into the Levels they are displayed on, from top to bottom, where
+/// the Nodes of every Level are ordered from left to right.
+///
+/// The Levels can be inspected and edited, before they are handed back to compute the Layout with
+/// [`Layout::compute_with_levels`].
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter, Layout, Levels};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+/// graph.add_edges([(0, 1), (0, 2), (0, 3)]);
+///
+/// let mut levels = Levels::compute(&graph, &config);
+/// assert_eq!(Some(0), levels.level_of(&0));
+///
+/// // Move one of the Children further down
+/// levels.move_node(&3, 2);
+///
+/// let layout = Layout::compute_with_levels(&graph, &config, &levels);
+/// layout.fdisplay(&config, std::io::stdout().lock());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Levels<'g, ID> {
+    levels: Vec<Vec<&'g ID>>,
+}
+
+impl<'g, ID> Levels<'g, ID>
+where
+    ID: Hash + Eq,
+{
+    /// Computes the Levels of the Graph, like they would be computed for its Layout
+    pub fn compute<T>(graph: &'g DirectedGraph<ID, T>, config: &Config<ID, T>) -> Self {
+        Self::compute_ordered(graph, config, None)
+    }
+
+    /// Computes the Levels like [`Levels::compute`], but the Cycles are broken and the Nodes are
+    /// distributed in the given Order, whenever several Nodes are equally suited. This makes the
+    /// Levels independent of the Order the Nodes are stored in.
+    pub(crate) fn compute_ordered<T>(
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        order: Option<&OrderFn<'_, ID>>,
+    ) -> Self {
+        let levels = Layout::graph_levels(graph, config, order);
+        Self {
+            levels: levels.into_iter().map(|level| level.nodes).collect(),
+        }
+    }
+
+    /// The number of Levels
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Whether there are no Levels at all, which is only the case for an empty Graph
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Returns the ordered Nodes of the Level
+    pub fn get(&self, level: usize) -> Option<&[&'g ID]> {
+        self.levels.get(level).map(Vec::as_slice)
+    }
+
+    /// Returns the ordered Nodes of the Level, which can be reordered
+    pub fn get_mut(&mut self, level: usize) -> Option<&mut [&'g ID]> {
+        self.levels.get_mut(level).map(Vec::as_mut_slice)
+    }
+
+    /// Iterates over the ordered Nodes of every Level, from the top to the bottom
+    pub fn iter(&self) -> impl Iterator<Item = &[&'g ID]> + '_ {
+        self.levels.iter().map(Vec::as_slice)
+    }
+
+    /// The Level the Node is placed on
+    pub fn level_of(&self, id: &ID) -> Option<usize> {
+        self.levels.iter().position(|level| level.contains(&id))
+    }
+
+    /// The Level of every Node
+    pub fn node_levels(&self) -> HashMap<&'g ID, usize> {
+        self.levels
+            .iter()
+            .enumerate()
+            .flat_map(|(index, level)| level.iter().map(move |id| (*id, index)))
+            .collect()
+    }
+
+    /// Moves the Node to the end of the given Level, or to a new Level at the bottom, if the
+    /// Level does not exist yet. Levels that end up empty are removed.
+    ///
+    /// Returns `false` if the Node is not part of any Level.
+    pub fn move_node(&mut self, id: &ID, level: usize) -> bool {
+        let Some(current) = self.level_of(id) else {
+            return false;
+        };
+        let position = self.levels[current]
+            .iter()
+            .position(|other| *other == id)
+            .expect("The Node is part of its Level");
+        let node = self.levels[current].remove(position);
+
+        match self.levels.get_mut(level) {
+            Some(target) => target.push(node),
+            None => self.levels.push(vec![node]),
+        }
+        self.levels.retain(|level| !level.is_empty());
+        true
+    }
+
+    /// Converts the Levels into the internal Levels for the given Graph. Nodes that are not part
+    /// of the Graph are skipped, while the Nodes that are not part of any Level are placed on an
+    /// additional Level at the bottom.
+    pub(crate) fn placed<T>(&self, agraph: &AcyclicDirectedGraph<'g, ID, T>) -> Vec<Level<'g, ID>> {
+        let mut levels: Vec<Level<'g, ID>> = self
+            .levels
+            .iter()
+            .map(|level| Level {
+                nodes: level
+                    .iter()
+                    .filter(|id| agraph.nodes.contains_key(*id))
+                    .copied()
+                    .collect(),
+            })
+            .filter(|level| !level.nodes.is_empty())
+            .collect();
+
+        let listed: HashSet<&ID> = self.levels.iter().flatten().copied().collect();
+        let missing: Vec<&'g ID> = agraph
+            .nodes
+            .keys()
+            .filter(|id| !listed.contains(*id))
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            levels.push(Level { nodes: missing });
+        }
+        levels
+    }
+}
+
 /// A Level contains a list of all the Nodes that should be displayed on the same logical y-level
 #[derive(Debug)]
 pub struct Level<'g, ID> {
@@ -23,39 +173,191 @@ pub struct GraphLevels<'g, ID>(pub Vec<Level<'g, ID>>);
 
 impl<'g, ID> GraphLevels<'g, ID> {
     /// Constructs the [`GraphLevels`] from the provided Graph and Config
+    ///
+    /// If an `order` is given, the Nodes are distributed in that Order, whenever the Edges allow
+    /// several ones, so the Levels do not depend on the Order the Nodes are stored in.
     pub fn construct<T>(
         agraph: &AcyclicDirectedGraph<'g, ID, T>,
         config: &Config<ID, T>,
-        node_names: &HashMap<&'g ID, String>,
+        node_names: &NodeNames<'g, ID>,
+        order: Option<&OrderFn<'_, ID>>,
     ) -> GraphLevels<'g, ID>
     where
         ID: Hash + Eq,
     {
-        // Reduce the Graph to remove transitive Edges
-        let reduced = agraph.transitive_reduction();
+        Self::construct_shortening(agraph, config, node_names, &HashMap::new(), order, None).0
+    }
+
+    /// Constructs the [`GraphLevels`] like [`GraphLevels::construct`], but a Node whose Name does
+    /// not fit onto a Level uses the first of its shorter Names that does, given by their Widths,
+    /// before it is moved to a later Level.
+    ///
+    /// Returns the Index of the shorter Name used by every shortened Node as well.
+    ///
+    /// Once the `deadline` has passed, the optional Steps are skipped, like removing the
+    /// transitive Edges first or moving the Nodes according to the
+    /// [Layout-Weights](Config::layout_weight).
+    pub fn construct_shortening<T>(
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        config: &Config<ID, T>,
+        node_names: &NodeNames<'g, ID>,
+        shorter: &HashMap<&'g ID, Vec<usize>>,
+        order: Option<&OrderFn<'_, ID>>,
+        deadline: Option<Instant>,
+    ) -> (GraphLevels<'g, ID>, HashMap<&'g ID, usize>)
+    where
+        ID: Hash + Eq,
+    {
+        let passed = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        let (mut levels, shortened) = match config.rank.as_ref() {
+            Some(rank) => (Self::ranked(agraph, rank), HashMap::new()),
+            None => {
+                // Reduce the Graph to remove transitive Edges, which do not change the Levels of
+                // the Nodes, but make distributing them slower
+                let reduced = match passed() {
+                    true => agraph.unreduced(),
+                    false => agraph.transitive_reduction(),
+                };
+
+                // Sort the Nodes in the Graph for a better distribution across the levels
+                let ordering = reduced.topological_sort_by(order);
+
+                let (mut levels, shortened) =
+                    Self::distribute_nodes(ordering, &reduced, config, node_names, shorter);
+                if let Some(weight) = config.layout_weight.as_ref().filter(|_| !passed()) {
+                    let widths = |id: &'g ID| match shortened.get(id) {
+                        Some(form) => shorter[id][*form],
+                        None => node_names.width(id),
+                    };
+                    levels.pull_weighted(agraph, config, weight, widths);
+                }
+                (levels, shortened)
+            }
+        };
+
+        if let Some(order) = config.sibling_order.as_ref() {
+            for level in levels.0.iter_mut() {
+                level.nodes.sort_by(|a, b| order(a, b));
+            }
+        }
+        (levels, shortened)
+    }
+
+    /// Places the Nodes on the Levels given by their Ranks, skipping the Ranks without Nodes
+    fn ranked<T>(agraph: &AcyclicDirectedGraph<'g, ID, T>, rank: &NodeRank<ID, T>) -> Self
+    where
+        ID: Hash + Eq,
+    {
+        let mut ranked: Vec<(usize, &'g ID)> = agraph
+            .nodes
+            .iter()
+            .map(|(id, value)| (rank(id, value), *id))
+            .collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+
+        let mut levels: Vec<Level<'g, ID>> = Vec::new();
+        let mut current = None;
+        for (rank, id) in ranked {
+            if current != Some(rank) {
+                levels.push(Level { nodes: Vec::new() });
+                current = Some(rank);
+            }
+            levels
+                .last_mut()
+                .expect("A Level was pushed for the current Rank")
+                .nodes
+                .push(id);
+        }
+
+        GraphLevels(levels)
+    }
+
+    /// Moves every Node up towards its Sources, if the Weights of its incoming Edges outweigh the
+    /// ones of its outgoing Edges, as far as there is room on the Levels above.
+    ///
+    /// The Nodes are distributed as far down as possible, so moving them up is the only way to
+    /// shorten their incoming Edges. The Levels are visited from top to bottom, so a Node that
+    /// was moved up makes room for its Targets to follow it.
+    fn pull_weighted<T, W>(
+        &mut self,
+        agraph: &AcyclicDirectedGraph<'g, ID, T>,
+        config: &Config<ID, T>,
+        weight: &EdgeWeight<ID>,
+        width: W,
+    ) where
+        ID: Hash + Eq,
+        W: Fn(&'g ID) -> usize,
+    {
+        let mut levels: HashMap<&'g ID, usize> = HashMap::new();
+        let mut used: Vec<usize> = Vec::with_capacity(self.0.len());
+        for (index, level) in self.0.iter().enumerate() {
+            levels.extend(level.nodes.iter().map(|id| (*id, index)));
+            used.push(
+                level
+                    .nodes
+                    .iter()
+                    .map(|id| width(id) + config.node_padding)
+                    .sum(),
+            );
+        }
+        let mut incoming: HashMap<&'g ID, Vec<&'g ID>> = HashMap::new();
+        for (src, target) in agraph.edges() {
+            incoming.entry(target).or_default().push(src);
+        }
 
-        // Sort the Nodes in the Graph for a better distribution across the levels
-        let ordering = reduced.topological_sort();
+        for index in 0..self.0.len() {
+            for id in self.0[index].nodes.clone() {
+                let sources = incoming.get(id).into_iter().flatten();
+                let pull: u64 = sources.clone().map(|src| weight(src, id)).sum();
+                let targets = agraph.successors(id).into_iter().flatten();
+                let push: u64 = targets.map(|target| weight(id, target)).sum();
+                if pull <= push {
+                    continue;
+                }
+
+                // The Node has to stay below all of its Sources
+                let highest = sources.map(|src| levels[src] + 1).max().unwrap_or(0);
+                let node_width = width(id) + config.node_padding;
+                let fits = |level: usize| {
+                    let upper_bound = config.glyph_width().saturating_sub(node_width + 1);
+                    self.0[level].nodes.len() < config.max_per_layer
+                        && (used[level] == 0 || used[level] < upper_bound)
+                };
+                let Some(target) = (highest..index).find(|level| fits(*level)) else {
+                    continue;
+                };
 
-        Self::distribute_nodes(ordering, &reduced, config, node_names)
+                self.0[index].nodes.retain(|other| other != &id);
+                self.0[target].nodes.push(id);
+                used[index] -= node_width;
+                used[target] += node_width;
+                levels.insert(id, target);
+            }
+        }
+        self.0.retain(|level| !level.nodes.is_empty());
     }
 
     fn distribute_nodes<T>(
         ordering: Vec<&'g ID>,
         graph: &MinimalAcyclicDirectedGraph<'g, ID, T>,
         config: &Config<ID, T>,
-        node_names: &HashMap<&'g ID, String>,
-    ) -> GraphLevels<'g, ID>
+        node_names: &NodeNames<'g, ID>,
+        shorter: &HashMap<&'g ID, Vec<usize>>,
+    ) -> (GraphLevels<'g, ID>, HashMap<&'g ID, usize>)
     where
         ID: Hash + Eq,
     {
         // The size we use here is just a rough guess as to how many levels we might need and is just
         // there to hopefully reduce the number of reallocations needed
-        let mut levels: Vec<Level<'g, ID>> =
-            Vec::with_capacity(graph.inner.nodes.len() / config.max_per_layer);
+        let mut occupancy = Occupancy::with_capacity(
+            graph.inner.nodes.len() / config.max_per_layer.max(1),
+            config.glyph_width(),
+            config.max_per_layer,
+        );
         // We know that every Node will be in this map, so we can preallocate the exact space needed
         let mut vertex_levels: HashMap<&'g ID, usize> =
             HashMap::with_capacity(graph.inner.nodes.len());
+        let mut shortened: HashMap<&'g ID, usize> = HashMap::new();
 
         for v in ordering.into_iter().rev() {
             let initial_level = match graph.outgoing(v) {
@@ -66,48 +368,152 @@ impl<'g, ID> GraphLevels<'g, ID> {
                 None => 0,
             };
 
-            for v_level in initial_level..usize::MAX {
-                let level = match levels.get_mut(v_level) {
-                    Some(l) => l,
-                    None => {
-                        levels.extend(
-                            (0..(v_level + 1).saturating_sub(levels.len()))
-                                .map(|_| Level { nodes: Vec::new() }),
-                        );
-
-                        levels.get_mut(v_level).expect(
-                            "We previously made sure that there are enough entries in the list",
-                        )
-                    }
-                };
+            // The Width of the Name followed by the Widths of the shorter Names
+            let widths: Vec<usize> = std::iter::once(node_names.width(v))
+                .chain(shorter.get(v).into_iter().flatten().copied())
+                .collect();
+            // A Node needs one more Glyph of Room than its padded Width
+            let need = |width: usize| width + config.node_padding + 1;
 
-                // Check for max nodes per layer
-                if level.nodes.len() >= config.max_per_layer {
-                    continue;
-                }
+            // Levels that are full or too narrow for even the shortest Name are skipped in
+            // logarithmic time. Nodes that are too wide on their own are still placed on the next
+            // empty level
+            let shortest = widths.iter().copied().min().unwrap_or(0);
+            let v_level = occupancy.first_fitting(initial_level, need(shortest));
+            let form = widths
+                .iter()
+                .position(|w| occupancy.room(v_level) > need(*w))
+                .expect("The shortest Name fits onto the Level");
+            let node_width = widths[form];
+            if form > 0 {
+                shortened.insert(v, form - 1);
+            }
 
-                // Check for max glyphs per layer
-                let current_glyph_width: usize = level
-                    .nodes
-                    .iter()
-                    .map(|n| node_names.get(n).map_or(0, String::len) + 2)
-                    .sum();
-                let current_node_width = node_names.get(v).map_or(0, String::len);
-                let upper_bound = config.glyph_width().saturating_sub(current_node_width + 3);
-                if current_glyph_width >= upper_bound {
-                    debug_assert!(current_node_width < config.glyph_width() - 3);
-                    continue;
-                }
+            occupancy.insert(v_level, v, node_width + config.node_padding);
+            vertex_levels.insert(v, v_level);
+        }
+
+        let mut levels = occupancy.levels;
+        levels.reverse();
+        (GraphLevels(levels), shortened)
+    }
+}
+
+/// Keeps track of the Nodes and the glyph width of every Level, while the Nodes are distributed
+struct Occupancy<'g, ID> {
+    levels: Vec<Level<'g, ID>>,
+    /// The current glyph width of each level
+    widths: Vec<usize>,
+    /// A Max-Segment-Tree over the [Room](Occupancy::room) of the Levels, where the Leaves start
+    /// at half its Length and the Levels that do not exist yet have no Room
+    tree: Vec<usize>,
+    glyph_width: usize,
+    max_nodes: usize,
+}
+
+impl<'g, ID> Occupancy<'g, ID> {
+    fn with_capacity(capacity: usize, glyph_width: usize, max_nodes: usize) -> Self {
+        let leaves = capacity.max(1).next_power_of_two();
+        Self {
+            levels: Vec::with_capacity(capacity),
+            widths: Vec::with_capacity(capacity),
+            tree: vec![0; 2 * leaves],
+            glyph_width,
+            max_nodes: max_nodes.max(1),
+        }
+    }
+
+    /// The number of Glyphs left on the Level, which is 0 for full Levels and unlimited for empty
+    /// ones, so that even Nodes that are too wide on their own can be placed
+    fn room(&self, level: usize) -> usize {
+        match self.levels.get(level) {
+            Some(current) if current.nodes.len() >= self.max_nodes => 0,
+            Some(_) if self.widths[level] == 0 => usize::MAX,
+            Some(_) => self.glyph_width.saturating_sub(self.widths[level]),
+            None => 0,
+        }
+    }
 
-                level.nodes.push(v);
-                vertex_levels.insert(v, v_level);
+    /// Updates the Room of the Level in the Tree
+    fn update(&mut self, level: usize) {
+        let mut index = self.tree.len() / 2 + level;
+        self.tree[index] = self.room(level);
+        while index > 1 {
+            index /= 2;
+            self.tree[index] = self.tree[2 * index].max(self.tree[2 * index + 1]);
+        }
+    }
+
+    /// Makes sure that the given level and all levels below it exist
+    fn ensure(&mut self, level: usize) {
+        if level < self.levels.len() {
+            return;
+        }
 
-                break;
+        let existing = self.levels.len();
+        while self.levels.len() <= level {
+            self.levels.push(Level { nodes: Vec::new() });
+            self.widths.push(0);
+        }
+
+        // The Tree is rebuilt with twice the Leaves, once the Levels outgrow it
+        if level < self.tree.len() / 2 {
+            for index in existing..=level {
+                self.update(index);
             }
+            return;
+        }
+        let leaves = (level + 1).next_power_of_two();
+        self.tree = vec![0; 2 * leaves];
+        for index in 0..self.levels.len() {
+            self.tree[leaves + index] = self.room(index);
+        }
+        for index in (1..leaves).rev() {
+            self.tree[index] = self.tree[2 * index].max(self.tree[2 * index + 1]);
         }
+    }
 
-        levels.reverse();
-        GraphLevels(levels)
+    /// Finds the first level, starting at `level`, with more than `need` Glyphs of Room left
+    fn first_fitting(&mut self, level: usize, need: usize) -> usize {
+        self.ensure(level);
+        let leaves = self.tree.len() / 2;
+        match self.find(1, 0, leaves, level, need) {
+            Some(found) => found,
+            None => {
+                // Every existing Level is too full, so a new empty one is added at the bottom
+                let new = self.levels.len();
+                self.ensure(new);
+                new
+            }
+        }
+    }
+
+    /// Searches the Subtree at the given Index, which covers the `len` Levels starting at `start`
+    fn find(
+        &self,
+        index: usize,
+        start: usize,
+        len: usize,
+        from: usize,
+        need: usize,
+    ) -> Option<usize> {
+        if start + len <= from || self.tree[index] <= need {
+            return None;
+        }
+        if len == 1 {
+            return Some(start);
+        }
+
+        let half = len / 2;
+        self.find(2 * index, start, half, from, need)
+            .or_else(|| self.find(2 * index + 1, start + half, half, from, need))
+    }
+
+    fn insert(&mut self, level: usize, id: &'g ID, width: usize) {
+        self.ensure(level);
+        self.levels[level].nodes.push(id);
+        self.widths[level] += width;
+        self.update(level);
     }
 }
 
@@ -124,10 +530,10 @@ mod tests {
         graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
         graph.add_edges([(0, 1), (0, 2)]);
 
-        let names: HashMap<_, _> = [].into_iter().collect();
+        let names: NodeNames<_> = [].into_iter().collect();
 
         let (agraph, _) = graph.to_acyclic();
-        let result_levels = GraphLevels::construct(&agraph, &config, &names).0;
+        let result_levels = GraphLevels::construct(&agraph, &config, &names, None).0;
 
         assert_eq!(3, result_levels.len());
         assert_eq!(1, result_levels[0].nodes.len());
@@ -142,7 +548,7 @@ mod tests {
         graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
         graph.add_edges([(0, 1), (0, 2), (0, 3)]);
 
-        let names: HashMap<_, _> = [
+        let names: NodeNames<_> = [
             (&0, "(0)".to_string()),
             (&1, "(1)".to_string()),
             (&2, "(2)".to_string()),
@@ -152,11 +558,45 @@ mod tests {
         .collect();
 
         let (agraph, _) = graph.to_acyclic();
-        let result_levels = GraphLevels::construct(&agraph, &config, &names).0;
+        let result_levels = GraphLevels::construct(&agraph, &config, &names, None).0;
 
         assert_eq!(3, result_levels.len());
         assert_eq!(1, result_levels[0].nodes.len());
         assert_eq!(1, result_levels[1].nodes.len());
         assert_eq!(2, result_levels[2].nodes.len());
     }
+
+    #[test]
+    fn occupancy_skips_narrow_levels() {
+        let ids = [0, 1, 2, 3];
+        let mut occupancy = Occupancy::with_capacity(1, 20, 2);
+        occupancy.insert(0, &ids[0], 15);
+        occupancy.insert(1, &ids[1], 15);
+        occupancy.insert(2, &ids[2], 2);
+        occupancy.insert(2, &ids[3], 2);
+
+        assert_eq!(0, occupancy.first_fitting(0, 3));
+        assert_eq!(1, occupancy.first_fitting(1, 3));
+        // The third Level would be wide enough, but already holds the max number of Nodes
+        assert_eq!(3, occupancy.first_fitting(0, 6));
+        assert_eq!(5, occupancy.first_fitting(5, 100));
+        assert_eq!(3, occupancy.first_fitting(0, 100));
+    }
+
+    #[test]
+    fn assign_levels_many_levels() {
+        let config = Config::new(IDFormatter::new(), 2);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..50).map(|i| (i, "node")));
+        graph.add_edges((0..49).map(|i| (i, i + 1)));
+        graph.add_edges((1..50).map(|i| (0, i)));
+
+        let names: NodeNames<_> = [].into_iter().collect();
+
+        let (agraph, _) = graph.to_acyclic();
+        let result_levels = GraphLevels::construct(&agraph, &config, &names, None).0;
+
+        assert_eq!(50, result_levels.len());
+        assert!(result_levels.iter().all(|l| l.nodes.len() == 1));
+    }
 }
@@ -18,21 +18,94 @@
 #![warn(missing_docs)]
 
 mod graph;
-use std::{collections::HashMap, fmt::Display, hash::Hash};
+use std::hash::Hash;
 
-pub use graph::DirectedGraph;
+pub use graph::{DirectedGraph, GraphError};
 
 mod acyclic;
 
 mod grid;
 
 mod formatter;
-pub use formatter::{IDFormatter, NodeFormat, ValueFormatter};
+pub use formatter::{
+    Cached, ContextNodeFormat, DebugFormatter, DefaultFormatter, IDFormatter, IDValueFormatter,
+    Join, LabelCache, LabelIssue, NodeContext, NodeFormat, PadTo, StripMarkup, Truncate, Validate,
+    ValueFormatter,
+};
 
 mod config;
-pub use config::{Color, Config, LineGlyphBuilder, LineGlyphs};
+pub use config::{
+    BackEdgeStyle, Color, ColorBy, Config, Justify, LayoutWarning, LineGlyphBuilder, LineGlyphs,
+    LineStyle, WeightTiers,
+};
 
 mod levels;
+pub use levels::Levels;
+
+mod names;
+
+mod ordering;
+
+mod layout;
+pub use layout::{EdgeCell, Layout, LayoutMetrics, NodePosition, RenderReport};
+
+mod options;
+pub use options::DisplayOptions;
+
+mod explorer;
+pub use explorer::{Explorer, ExplorerAction};
+
+mod watch;
+pub use watch::{watch, Watcher};
+
+mod session;
+pub use session::RenderSession;
+
+mod tee;
+pub use tee::{fdisplay_multi, StripColors};
+
+mod buffer;
+pub use buffer::{render_buffer, Cell, CellStyle, RenderBuffer};
+
+mod animate;
+pub use animate::{animate, fanimate};
+
+mod cluster;
+pub use cluster::Clusters;
+
+pub mod analysis;
+
+pub mod matrix;
+
+pub mod outline;
+
+pub mod record;
+
+pub mod graphviz;
+
+pub mod log;
+
+#[cfg(feature = "images")]
+pub mod image;
+
+#[cfg(feature = "gen")]
+pub mod gen;
+
+#[cfg(feature = "dot")]
+pub mod dot;
+
+mod edgelist;
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "json")]
+pub use json::JsonError;
+
+mod overlay;
+pub use overlay::{overlay, Overlaid, Overlay, Source};
+
+mod side_by_side;
+pub use side_by_side::{display_side_by_side, fdisplay_side_by_side};
 
 /// This is used to output the given Graph to the Terminal
 ///
@@ -53,7 +126,7 @@ mod levels;
 /// ```
 pub fn display<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>)
 where
-    ID: Hash + Eq + Display,
+    ID: Hash + Eq,
 {
     fdisplay(graph, config, std::io::stdout().lock());
 }
@@ -76,29 +149,355 @@ where
 /// ```
 pub fn fdisplay<ID, T, W>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>, mut dest: W)
 where
-    ID: Hash + Eq + Display,
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    Layout::compute(graph, config).fdisplay(config, &mut dest);
+}
+
+/// This function is the same as [`fdisplay`], but allows you to override parts of the Config for
+/// this single call using [`DisplayOptions`].
+///
+/// # Example
+/// Only display the Nodes with an even ID and highlight the first Node
+/// ```rust
+/// use termgraph::{DirectedGraph, DisplayOptions, IDFormatter, Config};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// graph.add_edges([(0, 1), (0,2), (1, 2)]);
+///
+/// let options = DisplayOptions::new().highlight([0]).filter(|id, _| id % 2 == 0);
+///
+/// let mut target = Vec::new();
+/// termgraph::fdisplay_with(&graph, &config, &options, &mut target);
+/// ```
+pub fn fdisplay_with<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    options: &DisplayOptions<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    Layout::compute_with(graph, config, options).fdisplay_with(config, options, &mut dest);
+}
+
+/// The same as [`fdisplay`], but also returns a [`RenderReport`] summarizing the Output, like the
+/// number of Edges crossing each other.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{DirectedGraph, IDFormatter, Config};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// graph.add_edges([(0, 1), (0,2), (1, 2)]);
+///
+/// let report = termgraph::fdisplay_report(&graph, &config, std::io::stdout().lock());
+/// if report.crossings > 20 {
+///     eprintln!("the graph is getting hard to read");
+/// }
+/// ```
+pub fn fdisplay_report<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    mut dest: W,
+) -> RenderReport
+where
+    ID: Hash + Eq,
     W: std::io::Write,
 {
-    // Do nothing if the graph is empty
-    if graph.is_empty() {
-        return;
+    let layout = Layout::compute(graph, config);
+    layout.fdisplay(config, &mut dest);
+    layout.report()
+}
+
+/// Renders the Graph into a String, like [`fdisplay`] would write it, which is useful for embedding
+/// the Graph into Error-Messages or Logs.
+///
+/// Unlike [`render_to_lines`], the Output is not changed in any way, so it still contains the
+/// Colors of the Config. For Messages that might not be written to a Terminal, like Errors that
+/// are logged or returned to a Caller, the Colors can be disabled using [`render_with`] and
+/// [`DisplayOptions::colors`], which is what [`log::render`] does as well.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1), (1, 0)]);
+///
+/// let rendered = termgraph::render(&graph, &config);
+/// eprintln!("found a cycle in the dependencies:\n{rendered}");
+/// ```
+pub fn render<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>) -> String
+where
+    ID: Hash + Eq,
+{
+    render_with(graph, config, &DisplayOptions::new())
+}
+
+/// The same as [`render`], but allows you to override parts of the Config for this single call
+/// using [`DisplayOptions`], like [`fdisplay_with`].
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, DisplayOptions, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3).default_colors();
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1), (1, 0)]);
+///
+/// let rendered = termgraph::render_with(&graph, &config, &DisplayOptions::new().colors(false));
+/// assert!(!rendered.contains('\x1b'));
+/// ```
+pub fn render_with<ID, T>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    options: &DisplayOptions<ID, T>,
+) -> String
+where
+    ID: Hash + Eq,
+{
+    let mut output = Vec::new();
+    fdisplay_with(graph, config, options, &mut output);
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// Renders the Graph into its Lines of plain Text, which is meant to be used for asserting on the
+/// Output of a Graph in Tests.
+///
+/// The Lines contain no Colors, Hyperlinks or other Escape-Sequences and no trailing Whitespace,
+/// and there are no empty Lines at the end. The Cycles are broken and the Nodes are distributed
+/// onto the Levels in the Order of their IDs, which also orders the Nodes on every Level. So the
+/// Output does not depend on the arbitrary Order the Nodes are stored in and stays the same
+/// across multiple runs.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// graph.add_edges([(0, 1), (0, 2)]);
+///
+/// let lines = termgraph::render_to_lines(&graph, &config);
+/// assert_eq!(" (0)", lines[0]);
+/// assert_eq!(" (1)  (2)", lines.last().unwrap());
+/// ```
+pub fn render_to_lines<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>) -> Vec<String>
+where
+    ID: Hash + Eq + Ord,
+{
+    let mut levels = Levels::compute_ordered(graph, config, Some(&|a: &ID, b: &ID| a.cmp(b)));
+    for index in 0..levels.len() {
+        if let Some(level) = levels.get_mut(index) {
+            level.sort();
+        }
     }
 
-    let (agraph, reved_edges) = graph.to_acyclic();
+    let mut output = Vec::new();
+    Layout::compute_with_levels(graph, config, &levels).fdisplay_with(
+        config,
+        &DisplayOptions::new().colors(false),
+        &mut output,
+    );
 
-    let names: HashMap<&ID, String> = agraph
-        .nodes
-        .iter()
-        .map(|(id, value)| (*id, config.formatter.format_node(*id, value)))
+    let mut lines: Vec<String> = String::from_utf8_lossy(&output)
+        .lines()
+        .map(|line| formatter::strip_ansi(line).trim_end().to_string())
         .collect();
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
 
-    let levels = levels::GraphLevels::construct(&agraph, config, &names);
+/// Displays the Graph made up of the given Edges, without having to construct a
+/// [`DirectedGraph`] first. Every Node mentioned by an Edge is part of the Graph and has the Value
+/// `()`, so the Formatter should only rely on the IDs.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// termgraph::display_edges([(0, 1), (0, 2), (1, 2)], &config);
+/// ```
+pub fn display_edges<ID, I>(edges: I, config: &Config<ID, ()>)
+where
+    ID: Hash + Eq + Clone,
+    I: IntoIterator<Item = (ID, ID)>,
+{
+    fdisplay_edges(edges, config, std::io::stdout().lock());
+}
 
-    let grid = grid::Grid::construct(&agraph, levels.0.clone(), reved_edges, config, names);
-    grid.fdisplay(
-        config.color_palette.as_ref(),
-        &config.line_glyphs,
-        &mut dest,
-    );
-    let _ = writeln!(dest);
+/// The same as [`display_edges`], but allows you to specify the Output Target
+pub fn fdisplay_edges<ID, I, W>(edges: I, config: &Config<ID, ()>, mut dest: W)
+where
+    ID: Hash + Eq + Clone,
+    I: IntoIterator<Item = (ID, ID)>,
+    W: std::io::Write,
+{
+    let mut graph = DirectedGraph::new();
+    for (src, target) in edges {
+        graph.add_nodes([(src.clone(), ()), (target.clone(), ())]);
+        graph.add_edges([(src, target)]);
+    }
+    fdisplay(&graph, config, &mut dest);
+}
+
+/// Displays the Condensation of the given Graph, in which every Strongly Connected Component is
+/// collapsed into a single Node labeled with its number of Nodes, like `[3 nodes]`.
+///
+/// This makes Graphs with many Cycles easier to read, as only the acyclic Structure between the
+/// Components remains.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{DirectedGraph, IDFormatter, Config};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+/// graph.add_edges([(0, 1), (1, 2), (2, 1), (2, 3)]);
+///
+/// termgraph::display_condensed(&graph, &config);
+/// ```
+pub fn display_condensed<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>)
+where
+    ID: Hash + Eq,
+{
+    fdisplay_condensed(graph, config, std::io::stdout().lock());
+}
+
+/// The same as [`display_condensed`], but allows you to specify the Output Target
+pub fn fdisplay_condensed<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    Layout::compute_condensed(graph, config).fdisplay(config, &mut dest);
+}
+
+/// Displays only the Part of the given Graph, that is reachable from the Roots within `depth`
+/// Edges. The Nodes with Edges leading out of the displayed Part are marked with a Stub, like
+/// `(node) → …`.
+///
+/// # Example
+/// ```rust
+/// # use termgraph::{DirectedGraph, IDFormatter, Config};
+/// #
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+/// graph.add_edges([(0, 1), (1, 2), (2, 3)]);
+///
+/// // Displays the first and second Node, where the second one has a Stub
+/// termgraph::display_reachable(&graph, [0], 1, &config);
+/// ```
+pub fn display_reachable<ID, T, I>(
+    graph: &DirectedGraph<ID, T>,
+    roots: I,
+    depth: usize,
+    config: &Config<ID, T>,
+) where
+    ID: Hash + Eq,
+    I: IntoIterator<Item = ID>,
+{
+    fdisplay_reachable(graph, roots, depth, config, std::io::stdout().lock());
+}
+
+/// The same as [`display_reachable`], but allows you to specify the Output Target
+pub fn fdisplay_reachable<ID, T, I, W>(
+    graph: &DirectedGraph<ID, T>,
+    roots: I,
+    depth: usize,
+    config: &Config<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    I: IntoIterator<Item = ID>,
+    W: std::io::Write,
+{
+    Layout::compute_reachable(graph, roots, depth, config).fdisplay(config, &mut dest);
+}
+
+/// Displays only the Nodes within `depth` Edges of the given Node, following the Edges in both
+/// Directions, which makes it easier to inspect a single Node of a large Graph. The Node itself is
+/// highlighted and the Nodes with Edges leading out of the displayed Part are marked with a Stub.
+///
+/// # Example
+/// ```rust
+/// # use termgraph::{DirectedGraph, IDFormatter, Config};
+/// #
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes((0..100).map(|i| (i, ())));
+/// graph.add_edges((0..99).map(|i| (i, i + 1)));
+///
+/// // Displays the Nodes 48 to 52
+/// termgraph::display_neighborhood(&graph, &50, 2, &config);
+/// ```
+pub fn display_neighborhood<ID, T>(
+    graph: &DirectedGraph<ID, T>,
+    id: &ID,
+    depth: usize,
+    config: &Config<ID, T>,
+) where
+    ID: Hash + Eq + Clone,
+{
+    fdisplay_neighborhood(graph, id, depth, config, std::io::stdout().lock());
+}
+
+/// The same as [`display_neighborhood`], but allows you to specify the Output Target
+pub fn fdisplay_neighborhood<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    id: &ID,
+    depth: usize,
+    config: &Config<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq + Clone,
+    W: std::io::Write,
+{
+    let options = DisplayOptions::new().highlight([id.clone()]);
+    Layout::compute_neighborhood(graph, id, depth, config)
+        .fdisplay_with(config, &options, &mut dest);
+}
+
+/// Displays the given Graph, in which every collapsed Cluster is displayed as a single summary
+/// Node, like `[parser: 14 nodes]`. See [`Clusters`] for more details.
+pub fn display_clustered<ID, T>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    clusters: &Clusters<ID, T>,
+) where
+    ID: Hash + Eq,
+{
+    fdisplay_clustered(graph, config, clusters, std::io::stdout().lock());
+}
+
+/// The same as [`display_clustered`], but allows you to specify the Output Target
+pub fn fdisplay_clustered<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    clusters: &Clusters<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    Layout::compute_clustered(graph, config, clusters).fdisplay(config, &mut dest);
 }
@@ -0,0 +1,117 @@
+//! Helpers to emit Graphs through the `log` or `tracing` Crates, which are available with the
+//! `log` and `tracing` Features respectively.
+//!
+//! The Graph is rendered into a single multi-line Message like [`render`](crate::render), but
+//! without any Colors, as most Loggers do not write to a Terminal and would keep the
+//! Escape-Sequences in their Files. The Message starts with a Line-Break, so the Graph is not
+//! shifted by the Prefix of the Logger. The Graph is only rendered if the Level is actually
+//! enabled.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{Config, DirectedGraph, IDFormatter};
+//!
+//! let config = Config::new(IDFormatter::new(), 3);
+//! let mut graph = DirectedGraph::new();
+//! graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+//! graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+//!
+//! let message = termgraph::log::render(&graph, &config);
+//! assert!(message.contains("(0)"));
+//! ```
+
+use std::hash::Hash;
+
+use crate::{Config, DirectedGraph, DisplayOptions};
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use ::tracing as __tracing;
+
+/// Renders the Graph into the Message used for Logs, which is the Output of
+/// [`render`](crate::render) without the Colors of the Config, starting with a Line-Break and
+/// without the trailing empty Lines
+pub fn render<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>) -> String
+where
+    ID: Hash + Eq,
+{
+    let rendered = crate::render_with(graph, config, &DisplayOptions::new().colors(false));
+    format!("\n{}", rendered.trim_end())
+}
+
+/// Emits the Graph using [`log`](::log) with the given Target and Level
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+///
+/// termgraph::log::emit(&graph, &config, "my_service::deps", log::Level::Debug);
+/// ```
+#[cfg(feature = "log")]
+pub fn emit<ID, T>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    target: &str,
+    level: ::log::Level,
+) where
+    ID: Hash + Eq,
+{
+    if ::log::log_enabled!(target: target, level) {
+        ::log::log!(target: target, level, "{}", render(graph, config));
+    }
+}
+
+/// Emits the Graph as a `tracing` Event, with the given Target and Level.
+///
+/// The Target and Level have to be constant, like for every other `tracing` Event.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+///
+/// termgraph::trace_graph!(target: "my_service::deps", tracing::Level::DEBUG, &graph, &config);
+/// ```
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! trace_graph {
+    (target: $target:expr, $level:expr, $graph:expr, $config:expr) => {
+        $crate::log::__tracing::event!(
+            target: $target,
+            $level,
+            "{}",
+            $crate::log::render($graph, $config)
+        )
+    };
+    ($level:expr, $graph:expr, $config:expr) => {
+        $crate::trace_graph!(target: module_path!(), $level, $graph, $config)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn uncolored() {
+        let config = Config::new(IDFormatter::new(), 3).default_colors();
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second")]);
+        graph.add_edges([(0, 1)]);
+
+        let message = render(&graph, &config);
+        assert!(message.starts_with("\n (0)"));
+        assert!(!message.contains('\x1b'));
+        assert!(!message.ends_with('\n'));
+    }
+}
@@ -0,0 +1,338 @@
+//! Displays a Graph read from a File or Stdin, which is available with the `cli` Feature.
+//!
+//! ```text
+//! cargo install termgraph --features cli
+//! echo 'digraph { a -> b; a -> c }' | termgraph
+//! ```
+
+use std::{
+    fmt::Display,
+    io::{IsTerminal, Read},
+    process::ExitCode,
+};
+
+use serde::{Deserialize, Deserializer};
+use termgraph::{dot, Config, DirectedGraph, LineGlyphBuilder, ValueFormatter};
+
+const USAGE: &str = "\
+Usage: termgraph [OPTIONS] [FILE]
+
+Displays the Graph in FILE, or read from Stdin if no FILE is given.
+
+Options:
+  -f, --format <FORMAT>  The Format of the Input: dot, json or edges (src,dst per line),
+                         which is detected from the File-Extension or Content by default
+  -c, --colors <WHEN>    Colors the Edges: auto, always or never [default: auto]
+  -g, --glyphs <GLYPHS>  The Glyphs for the Edges: ascii, unicode, heavy or double
+                         [default: ascii]
+  -w, --width <COLUMNS>  The maximum Width of a Layer, which defaults to the Width of the
+                         Terminal
+      --header           Skips the first Row of an Edge-List, which names its Columns
+  -h, --help             Prints this Help
+";
+
+/// The supported Formats of the Input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Dot,
+    Json,
+    Edges,
+}
+
+impl Format {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "dot" | "gv" => Some(Self::Dot),
+            "json" => Some(Self::Json),
+            "edges" | "csv" | "txt" => Some(Self::Edges),
+            _ => None,
+        }
+    }
+
+    /// Guesses the Format based on the Content, if the File-Extension is not known
+    fn detect(input: &str) -> Self {
+        let start = input.trim_start();
+        let keyword = start
+            .split(|c: char| !c.is_alphanumeric())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if start.starts_with('{') {
+            Self::Json
+        } else if matches!(keyword.as_str(), "strict" | "graph" | "digraph")
+            || start.starts_with("//")
+            || start.starts_with("/*")
+        {
+            Self::Dot
+        } else {
+            Self::Edges
+        }
+    }
+}
+
+/// The parsed Command-Line Arguments
+struct Args {
+    file: Option<String>,
+    format: Option<Format>,
+    colors: Option<bool>,
+    glyphs: LineGlyphBuilder,
+    width: Option<usize>,
+    header: bool,
+}
+
+impl Args {
+    fn parse<I>(mut args: I) -> Result<Option<Self>, String>
+    where
+        I: Iterator<Item = String>,
+    {
+        let mut parsed = Self {
+            file: None,
+            format: None,
+            colors: None,
+            glyphs: LineGlyphBuilder::ascii(),
+            width: None,
+            header: false,
+        };
+
+        while let Some(arg) = args.next() {
+            // Supports both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with('-') => (flag.to_string(), Some(value)),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline
+                    .map(str::to_string)
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for {flag}"))
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-f" | "--format" => {
+                    let format = value()?;
+                    parsed.format = Some(
+                        Format::parse(&format)
+                            .ok_or_else(|| format!("unknown format {format:?}"))?,
+                    );
+                }
+                "-c" | "--colors" => {
+                    parsed.colors = match value()?.as_str() {
+                        "auto" => None,
+                        "always" => Some(true),
+                        "never" => Some(false),
+                        other => return Err(format!("unknown color mode {other:?}")),
+                    };
+                }
+                "-g" | "--glyphs" => {
+                    parsed.glyphs = match value()?.as_str() {
+                        "ascii" => LineGlyphBuilder::ascii(),
+                        "unicode" => LineGlyphBuilder::unicode(),
+                        "heavy" => LineGlyphBuilder::heavy(),
+                        "double" => LineGlyphBuilder::double(),
+                        other => return Err(format!("unknown glyphs {other:?}")),
+                    };
+                }
+                "-w" | "--width" => {
+                    let width = value()?;
+                    parsed.width = Some(
+                        width
+                            .parse()
+                            .ok()
+                            .filter(|w| *w > 0)
+                            .ok_or_else(|| format!("invalid width {width:?}"))?,
+                    );
+                }
+                "--header" if inline.is_none() => parsed.header = true,
+                "-" if parsed.file.is_none() => {}
+                flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+                _ if parsed.file.is_some() => return Err(format!("unexpected argument {arg:?}")),
+                _ => parsed.file = Some(arg),
+            }
+        }
+
+        Ok(Some(parsed))
+    }
+}
+
+/// An ID in the JSON Input, where Numbers and other Values are converted to their JSON Text
+#[derive(PartialEq, Eq, Hash)]
+struct JsonId(String);
+
+impl<'de> Deserialize<'de> for JsonId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(JsonId(
+            match serde_json::Value::deserialize(deserializer)? {
+                serde_json::Value::String(text) => text,
+                other => other.to_string(),
+            },
+        ))
+    }
+}
+
+impl Display for JsonId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Converts a Graph without Values into one, where every Node is mapped to its Name
+fn named<ID>(
+    graph: DirectedGraph<ID, ()>,
+    name: impl Fn(&ID) -> String,
+) -> DirectedGraph<String, String>
+where
+    ID: std::hash::Hash + Eq,
+{
+    let mut result = DirectedGraph::new();
+    result.add_nodes(graph.nodes().map(|(id, _)| (name(id), name(id))));
+    result.add_edges(graph.edges().map(|(from, to)| (name(from), name(to))));
+    result
+}
+
+fn load(
+    input: &str,
+    format: Format,
+    header: bool,
+) -> Result<DirectedGraph<String, String>, String> {
+    match format {
+        Format::Dot => dot::parse(input).map_err(|e| e.to_string()),
+        Format::Json => DirectedGraph::<JsonId, ()>::from_json_adjacency(input.as_bytes())
+            .map(|graph| named(graph, |id| id.0.clone()))
+            .map_err(|e| e.to_string()),
+        Format::Edges => match header {
+            true => DirectedGraph::from_edge_list_with_header(input.as_bytes()),
+            false => DirectedGraph::from_edge_list(input.as_bytes()),
+        }
+        .map(|graph| named(graph, String::clone))
+        .map_err(|e| e.to_string()),
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let mut input = String::new();
+    let format_hint = match &args.file {
+        Some(path) => {
+            input = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+            std::path::Path::new(path)
+                .extension()
+                .and_then(|e| Format::parse(&e.to_string_lossy()))
+        }
+        None => {
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("stdin: {e}"))?;
+            None
+        }
+    };
+    let format = args
+        .format
+        .or(format_hint)
+        .unwrap_or_else(|| Format::detect(&input));
+    let graph = load(&input, format, args.header)?;
+
+    let config = match args.width {
+        Some(width) => {
+            Config::new(ValueFormatter::new(), (width / 8).max(1)).max_glyphs_per_layer(width)
+        }
+        None => Config::for_terminal(ValueFormatter::new()),
+    };
+    let config = config.line_glyphs(args.glyphs);
+    let config = if args
+        .colors
+        .unwrap_or_else(|| std::io::stdout().is_terminal())
+    {
+        config.default_colors()
+    } else {
+        config
+    };
+
+    termgraph::display(&graph, &config);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("termgraph: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("termgraph: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn args() {
+        let args = parse(&["-f", "dot", "--colors=never", "--width", "40", "graph.txt"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(Some("graph.txt".to_string()), args.file);
+        assert_eq!(Some(Format::Dot), args.format);
+        assert_eq!(Some(false), args.colors);
+        assert_eq!(Some(40), args.width);
+        assert!(!args.header);
+
+        let args = parse(&["-", "--header", "--glyphs=unicode"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(None, args.file);
+        assert_eq!(None, args.format);
+        assert!(args.header);
+
+        assert!(parse(&["input", "--help"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_args() {
+        let error = |args: &[&str]| parse(args).err().unwrap();
+        assert_eq!("missing value for --format", error(&["--format"]));
+        assert_eq!("unknown format \"xml\"", error(&["-f=xml"]));
+        assert_eq!(
+            "unknown color mode \"sometimes\"",
+            error(&["-c", "sometimes"])
+        );
+        assert_eq!("unknown glyphs \"round\"", error(&["-g", "round"]));
+        assert_eq!("invalid width \"0\"", error(&["-w", "0"]));
+        assert_eq!("unknown option --verbose", error(&["--verbose"]));
+        assert_eq!("unexpected argument \"b\"", error(&["a", "b"]));
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(Format::Json, Format::detect("  {\"a\": [\"b\"]}"));
+        assert_eq!(Format::Dot, Format::detect("digraph { a -> b }"));
+        assert_eq!(Format::Dot, Format::detect("Strict Graph {}"));
+        assert_eq!(Format::Dot, Format::detect("// comment\ngraph {}"));
+        assert_eq!(Format::Dot, Format::detect("/* comment */ digraph {}"));
+        assert_eq!(Format::Edges, Format::detect("a,b\nb,c"));
+        assert_eq!(Format::Edges, Format::detect("graphs,nodes"));
+        assert_eq!(Format::Edges, Format::detect(""));
+
+        assert_eq!(Some(Format::Edges), Format::parse("csv"));
+        assert_eq!(Some(Format::Dot), Format::parse("gv"));
+        assert_eq!(None, Format::parse("xml"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Displays a Graph as an Adjacency-Matrix instead of drawing its Nodes and Edges.
+//!
+//! This is mostly useful for very dense Graphs, where the Edges would cover most of the output.
+//! Every Row and Column belongs to one Node and the Cell at their intersection shows whether there
+//! is an Edge from the Node of the Row to the Node of the Column.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{Config, DirectedGraph, IDFormatter};
+//!
+//! let config = Config::new(IDFormatter::new(), 3);
+//! let mut graph = DirectedGraph::new();
+//! graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+//! graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+//!
+//! termgraph::matrix::display(&graph, &config);
+//! ```
+//!
+//! Which displays:
+//! ```text
+//!        0 1 2
+//! 0 (0)  . x x
+//! 1 (1)  . . x
+//! 2 (2)  . . .
+//! ```
+
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{config::default_palette, names, Config, DirectedGraph, DisplayOptions, NodeContext};
+
+/// The Glyph for a Cell with an Edge
+const EDGE: char = 'x';
+/// The Glyph for a Cell without an Edge
+const EMPTY: char = '.';
+
+/// Displays the Adjacency-Matrix of the given Graph.
+///
+/// The Nodes are ordered topologically where possible, so most Edges end up above the diagonal.
+/// The Rows are labeled with the Index and formatted Label of their Node, while the Columns are
+/// only labeled with the Index of their Node to keep the Matrix compact. If the Config has a
+/// Color-Palette, the Edges in every Row are colored with the Color of their Source.
+pub fn display<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>)
+where
+    ID: Hash + Eq,
+{
+    fdisplay(graph, config, std::io::stdout().lock());
+}
+
+/// The same as [`display`], but allows you to specify the Output Target
+pub fn fdisplay<ID, T, W>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>, dest: W)
+where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    fdisplay_with(graph, config, &DisplayOptions::new(), dest);
+}
+
+/// The same as [`fdisplay`], but with the Colors and Filter of the Options applied
+pub fn fdisplay_with<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    options: &DisplayOptions<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    let (agraph, _) = graph.to_acyclic_with(
+        |id, value| options.filter.as_ref().is_none_or(|f| f(id, value)),
+        false,
+        None,
+        config.reversal_cost.as_deref(),
+        None,
+        None,
+    );
+    let order = agraph.transitive_reduction().topological_sort();
+    if order.is_empty() {
+        return;
+    }
+
+    let in_degrees = match config.formatter.needs_context() {
+        true => agraph.in_degrees(),
+        false => Default::default(),
+    };
+    let labels: Vec<String> = order
+        .iter()
+        .map(|id| {
+            let context = || NodeContext {
+                in_degree: in_degrees.get(id).copied().unwrap_or(0),
+                out_degree: graph.successors(id).count(),
+                level: 0,
+            };
+            names::single_line(config, id, agraph.nodes[id], context)
+        })
+        .collect();
+
+    let palette = match (options.colors, config.color_palette.as_ref()) {
+        (Some(false), _) | (None, None) => Vec::new(),
+        (_, Some(palette)) => palette.clone(),
+        (Some(true), None) => default_palette(),
+    };
+    let prefixes: Vec<String> = palette.iter().map(|c| (config.color_start)(c)).collect();
+
+    // The Edges are looked up in the original Graph, as the Cycles were broken for the Ordering
+    let edges: HashSet<(&ID, &ID)> = graph.edges().collect();
+
+    let index_width = (order.len() - 1).to_string().len();
+    let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let row_width = index_width + 1 + label_width;
+
+    let _ = write!(dest, "{}{:row_width$} ", config.line_prefix, "");
+    for index in 0..order.len() {
+        let _ = write!(dest, " {index:>index_width$}");
+    }
+    let _ = writeln!(dest);
+
+    for (row, (src, label)) in order.iter().zip(labels.iter()).enumerate() {
+        let padding = label_width - label.chars().count();
+        let _ = write!(
+            dest,
+            "{}{row:>index_width$} {label}{:padding$} ",
+            config.line_prefix, ""
+        );
+
+        for target in order.iter() {
+            let _ = write!(dest, " {:>width$}", "", width = index_width - 1);
+            match (edges.contains(&(*src, *target)), prefixes.is_empty()) {
+                (true, true) => {
+                    let _ = write!(dest, "{EDGE}");
+                }
+                (true, false) => {
+                    let prefix = &prefixes[row % prefixes.len()];
+                    let _ = write!(dest, "{prefix}{EDGE}{}", config.color_end);
+                }
+                (false, _) => {
+                    let _ = write!(dest, "{EMPTY}");
+                }
+            }
+        }
+        let _ = writeln!(dest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn chain() {
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1), (1, 2), (2, 0)]);
+
+        let mut output = Vec::new();
+        fdisplay(&graph, &config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(4, lines.len());
+        assert_eq!("       0 1 2", lines[0]);
+        // Every Node has exactly one outgoing Edge
+        for line in &lines[1..] {
+            assert_eq!(1, line.matches(EDGE).count());
+            assert_eq!(2, line.matches(EMPTY).count());
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{Config, LayoutWarning, NodeContext};
+
+/// The formatted Names of the Nodes in a Graph
+///
+/// Identical Names are interned and share the same allocation, which keeps graphs with many
+/// repeated labels small and makes cloning the Names cheap.
+#[derive(Debug)]
+pub struct NodeNames<'g, ID> {
+    names: HashMap<&'g ID, Rc<str>>,
+}
+
+impl<'g, ID> NodeNames<'g, ID>
+where
+    ID: Hash + Eq,
+{
+    /// Creates the Names by calling `format` for every one of the given Nodes
+    pub fn construct<I, F>(nodes: I, mut format: F) -> Self
+    where
+        I: IntoIterator<Item = &'g ID>,
+        F: FnMut(&'g ID) -> String,
+    {
+        let nodes = nodes.into_iter();
+        let mut interned: HashSet<Rc<str>> = HashSet::new();
+        let mut names = HashMap::with_capacity(nodes.size_hint().0);
+
+        for id in nodes {
+            let name = format(id);
+            let name = match interned.get(name.as_str()) {
+                Some(existing) => existing.clone(),
+                None => {
+                    let name: Rc<str> = Rc::from(name);
+                    interned.insert(name.clone());
+                    name
+                }
+            };
+
+            names.insert(id, name);
+        }
+
+        Self { names }
+    }
+
+    /// Replaces the Name of the given Node
+    pub fn set(&mut self, id: &'g ID, name: String) {
+        self.names.insert(id, Rc::from(name));
+    }
+
+    /// Returns the Name of the given Node
+    pub fn get(&self, id: &ID) -> Option<&str> {
+        self.names.get(id).map(|n| n.as_ref())
+    }
+
+    /// Returns the given Line of the Name of the Node
+    pub fn line(&self, id: &ID, line: usize) -> Option<&str> {
+        self.get(id).and_then(|n| n.split('\n').nth(line))
+    }
+
+    /// Returns the number of Glyphs needed to display the Name of the given Node, or 0 if the Node
+    /// has no Name
+    ///
+    /// For Names spanning multiple Lines, this is the width of the longest Line
+    pub fn width(&self, id: &ID) -> usize {
+        self.get(id).map_or(0, width)
+    }
+
+    /// Returns the number of Lines of the Name of the given Node, which is at least 1
+    pub fn height(&self, id: &ID) -> usize {
+        self.get(id).map_or(1, |n| n.split('\n').count())
+    }
+}
+
+/// Returns the number of Columns needed to display the Name, which is the width of its longest
+/// Line
+pub fn width(name: &str) -> usize {
+    name.split('\n').map(|l| l.width()).max().unwrap_or(0)
+}
+
+/// The Characters after which a Name is preferably wrapped onto the next Line
+const WRAP_AFTER: &[char] = &[' ', '/', '\\', ':', '.', ',', '-', '_'];
+
+/// Wraps the Name onto multiple Lines, where every Line is at most `width` Columns wide.
+///
+/// The Lines are preferably broken after separators, like spaces or the `/` in a Path, and only
+/// split in the middle of a word if there is no separator in the Line. A wide Character is never
+/// split, so it is placed on its own Line if it does not fit into `width` Columns at all.
+pub fn wrap(name: String, width: usize) -> String {
+    let width = width.max(1);
+    if name.split('\n').all(|l| l.width() <= width) {
+        return name;
+    }
+
+    let mut lines = Vec::new();
+    for line in name.split('\n') {
+        let mut rest = line;
+        // A single Character is kept as is, even if it is wider than the Line
+        while rest.width() > width && rest.chars().nth(1).is_some() {
+            let mut fitting = take_columns(rest, width);
+            if fitting.is_empty() {
+                fitting = rest.chars().take(1).collect();
+            }
+            let split = fitting
+                .char_indices()
+                .rfind(|(_, c)| WRAP_AFTER.contains(c))
+                .map_or(fitting.len(), |(i, c)| i + c.len_utf8());
+
+            lines.push(rest[..split].trim_end().to_string());
+            rest = &rest[split..];
+        }
+        lines.push(rest.to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// Shortens the Name to at most `max_width` Columns, replacing the end of the Name with `…` if it
+/// needed to be cut off. Nothing is left of the Name, if there is no room for any Glyph.
+pub fn truncate(name: String, max_width: usize) -> String {
+    if name.width() <= max_width {
+        return name;
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut result = take_columns(&name, max_width - 1);
+    result.push('…');
+    result
+}
+
+/// Returns the longest Prefix of the Text, which fits into `columns` Columns. A wide Character
+/// that would only partially fit is left out.
+pub fn take_columns(text: &str, columns: usize) -> String {
+    let mut used = 0;
+    text.chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= columns
+        })
+        .collect()
+}
+
+/// Formats the Label of a Node as a single Line, for the Views that do not place the Nodes in the
+/// Grid
+pub fn single_line<ID, T, C>(config: &Config<ID, T>, id: &ID, value: &T, context: C) -> String
+where
+    C: FnOnce() -> NodeContext,
+{
+    let label = config
+        .formatter
+        .format_node_lines(id, value, context)
+        .join(" ");
+    for issue in config.formatter.take_issues() {
+        config.warn(LayoutWarning::InvalidLabel { node: id, issue });
+    }
+    match config.max_label_width {
+        Some(max_width) => truncate(label, max_width),
+        None => label,
+    }
+}
+
+impl<'g, ID> FromIterator<(&'g ID, String)> for NodeNames<'g, ID>
+where
+    ID: Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = (&'g ID, String)>>(iter: I) -> Self {
+        let names: HashMap<&'g ID, String> = iter.into_iter().collect();
+        Self::construct(names.keys().copied(), |id| names[id].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_identical_names() {
+        let ids = [0, 1, 2];
+        let names = NodeNames::construct(ids.iter(), |id| {
+            if *id < 2 {
+                "same".to_string()
+            } else {
+                "other".to_string()
+            }
+        });
+
+        let first = names.names.get(&0).unwrap();
+        let second = names.names.get(&1).unwrap();
+        assert!(Rc::ptr_eq(first, second));
+        assert_eq!(Some("other"), names.get(&2));
+        assert_eq!(4, names.width(&0));
+        assert_eq!(0, names.width(&3));
+    }
+
+    #[test]
+    fn wrap_names() {
+        assert_eq!("short", wrap("short".to_string(), 5));
+        assert_eq!("/usr/\nlocal/\nbin", wrap("/usr/local/bin".to_string(), 6));
+        assert_eq!("some\nlong\nname", wrap("some long name".to_string(), 5));
+        assert_eq!("abcd\nefgh\nij", wrap("abcdefghij".to_string(), 4));
+
+        // Wide Characters take up two Columns each
+        assert_eq!(
+            "日本語\nの名前\n日本語",
+            wrap("日本語の名前日本語".to_string(), 6)
+        );
+        assert_eq!("日本\n語", wrap("日本語".to_string(), 5));
+        assert_eq!("日\n本", wrap("日本".to_string(), 1));
+    }
+
+    #[test]
+    fn multiline_names() {
+        let ids = [0, 1];
+        let names = NodeNames::construct(ids.iter(), |id| {
+            if *id == 0 {
+                "first\nsecond line".to_string()
+            } else {
+                "single".to_string()
+            }
+        });
+
+        assert_eq!(11, names.width(&0));
+        assert_eq!(2, names.height(&0));
+        assert_eq!(Some("second line"), names.line(&0, 1));
+        assert_eq!(1, names.height(&1));
+        assert_eq!(None, names.line(&1, 1));
+    }
+
+    #[test]
+    fn truncate_names() {
+        assert_eq!("short", truncate("short".to_string(), 5));
+        assert_eq!("some…", truncate("some::long::Name".to_string(), 5));
+        assert_eq!("äöü…", truncate("äöüäöü".to_string(), 4));
+        assert_eq!("", truncate("name".to_string(), 0));
+        assert_eq!("…", truncate("name".to_string(), 1));
+
+        // Wide Characters take up two Columns each
+        assert_eq!("日本…", truncate("日本語の名前".to_string(), 6));
+        assert_eq!("日…", truncate("日本語の名前".to_string(), 4));
+        assert_eq!(5, width(&truncate("日本語の名前".to_string(), 6)));
+        assert_eq!(4, width("🦀🦀"));
+    }
+}
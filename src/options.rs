@@ -0,0 +1,184 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::Color;
+
+/// A Filter deciding whether or not a Node should be displayed
+type NodeFilter<ID, T> = Box<dyn Fn(&ID, &T) -> bool>;
+
+/// Options for a single Display call, that override parts of the shared [`Config`](crate::Config)
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, DisplayOptions, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3).default_colors();
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+///
+/// let options = DisplayOptions::new()
+///     .colors(false)
+///     .highlight([1])
+///     .filter(|id, _| *id != 2);
+///
+/// let mut target = Vec::new();
+/// termgraph::fdisplay_with(&graph, &config, &options, &mut target);
+/// ```
+pub struct DisplayOptions<ID, T> {
+    pub(crate) colors: Option<bool>,
+    pub(crate) highlight: HashSet<ID>,
+    pub(crate) highlight_color: Color,
+    pub(crate) filter: Option<NodeFilter<ID, T>>,
+    pub(crate) path: Vec<ID>,
+    pub(crate) node_colors: HashMap<ID, Color>,
+    pub(crate) edge_colors: HashMap<ID, HashMap<ID, Color>>,
+    /// Uses the plain ANSI Escape-Sequences instead of the Markup of the Config, so the Output can
+    /// be mapped back to its Colors, see [`RenderBuffer`](crate::RenderBuffer)
+    pub(crate) ansi_markup: bool,
+}
+
+impl<ID, T> DisplayOptions<ID, T> {
+    /// Creates a new set of Options, which does not override anything in the Config
+    ///
+    /// # Default Values
+    /// * Highlight-Color: Inverted (`Color::Custom(7)`)
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            colors: None,
+            highlight: HashSet::new(),
+            highlight_color: Color::Custom(7),
+            filter: None,
+            path: Vec::new(),
+            node_colors: HashMap::new(),
+            edge_colors: HashMap::new(),
+            ansi_markup: false,
+        }
+    }
+
+    /// Enables or disables the Colors for the Edges.
+    ///
+    /// If the Colors are enabled, but the Config has no Color-Palette configured, the default
+    /// Color-Palette is used.
+    #[must_use]
+    pub fn colors(mut self, enabled: bool) -> Self {
+        self.colors = Some(enabled);
+        self
+    }
+
+    /// Highlights the Labels of the given Nodes using the Highlight-Color.
+    ///
+    /// The Highlight is applied independent of whether or not the Colors are enabled.
+    #[must_use]
+    pub fn highlight<I>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = ID>,
+        ID: Hash + Eq,
+    {
+        self.highlight.extend(ids);
+        self
+    }
+
+    /// Sets the Color used to highlight Nodes
+    #[must_use]
+    pub fn highlight_color(mut self, color: Color) -> Self {
+        self.highlight_color = color;
+        self
+    }
+
+    /// Highlights the Nodes of the given Path and the Edges between them using the
+    /// Highlight-Color, while all the other Nodes and Edges are dimmed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Config, DirectedGraph, DisplayOptions, IDFormatter};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, 3), (1, 5), (2, 1)]);
+    /// graph.add_edges([(0, 1), (0, 2)]);
+    ///
+    /// let path: Vec<usize> = graph.critical_path(|_, cost| *cost, |_, _| 0).into_iter().copied().collect();
+    /// let options = DisplayOptions::new().highlight_path(path);
+    ///
+    /// termgraph::fdisplay_with(&graph, &config, &options, std::io::stdout().lock());
+    /// ```
+    #[must_use]
+    pub fn highlight_path<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator<Item = ID>,
+    {
+        self.path = path.into_iter().collect();
+        self
+    }
+
+    /// Colors the Labels of the given Nodes, unless they are highlighted.
+    ///
+    /// These Colors are applied even if the Config has no Color-Palette, but not if the Colors
+    /// are [disabled](DisplayOptions::colors).
+    #[must_use]
+    pub fn node_colors<I>(mut self, colors: I) -> Self
+    where
+        I: IntoIterator<Item = (ID, Color)>,
+        ID: Hash + Eq,
+    {
+        self.node_colors.extend(colors);
+        self
+    }
+
+    /// Colors the given Edges, identified by their Source and Target, instead of using the
+    /// Color-Palette.
+    ///
+    /// These Colors are applied even if the Config has no Color-Palette, but not if the Colors
+    /// are [disabled](DisplayOptions::colors).
+    ///
+    /// # Example
+    /// ```rust
+    /// use termgraph::{Color, Config, DirectedGraph, DisplayOptions, IDFormatter};
+    ///
+    /// let config = Config::new(IDFormatter::new(), 3);
+    /// let mut graph = DirectedGraph::new();
+    /// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    /// graph.add_edges([(0, 1), (0, 2)]);
+    ///
+    /// let options = DisplayOptions::new()
+    ///     .node_colors([(2, Color::Red)])
+    ///     .edge_colors([((0, 2), Color::Red)]);
+    ///
+    /// termgraph::fdisplay_with(&graph, &config, &options, std::io::stdout().lock());
+    /// ```
+    #[must_use]
+    pub fn edge_colors<I>(mut self, colors: I) -> Self
+    where
+        I: IntoIterator<Item = ((ID, ID), Color)>,
+        ID: Hash + Eq,
+    {
+        for ((src, target), color) in colors {
+            self.edge_colors
+                .entry(src)
+                .or_default()
+                .insert(target, color);
+        }
+        self
+    }
+
+    /// Only displays the Nodes for which the given Filter returns true, as well as the Edges
+    /// between them.
+    #[must_use]
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&ID, &T) -> bool + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+}
+
+impl<ID, T> Default for DisplayOptions<ID, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
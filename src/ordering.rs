@@ -0,0 +1,269 @@
+//! Reorders the Nodes within their Levels, to reduce the number of Edges crossing each other
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{acyclic::AcyclicDirectedGraph, config::EdgeWeight, levels::Level};
+
+/// A small Random Number Generator (xorshift64*), so that random Orderings can be reproduced
+/// using a Seed
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // The State must never be zero
+        Self((seed ^ 0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A Number in the Range `0..bound`, where the Bound must not be 0
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// Returns `true` with the given Probability
+    #[cfg_attr(not(feature = "gen"), allow(dead_code))]
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // The upper 53 Bits are exactly representable as a Fraction
+        let fraction = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        fraction < probability
+    }
+
+    /// Shuffles the Items using the Fisher-Yates Algorithm
+    pub fn shuffle<E>(&mut self, items: &mut [E]) {
+        for index in (1..items.len()).rev() {
+            let other = self.below(index + 1);
+            items.swap(index, other);
+        }
+    }
+}
+
+/// Sorts the Nodes of every Level by the average relative Position of their Neighbours on the
+/// Levels that were already sorted. Sweeping downwards uses the Sources of the Edges leading to a
+/// Node, sweeping upwards uses the Targets of its Edges.
+///
+/// Nodes without any Neighbours on the sorted Levels keep their current relative Position. With
+/// [Layout-Weights](crate::Config::layout_weights), the Positions of the Neighbours are weighted
+/// by the Weights of the Edges leading to them.
+pub(crate) fn barycenter<'g, ID, T>(
+    agraph: &AcyclicDirectedGraph<'g, ID, T>,
+    levels: &mut [Level<'g, ID>],
+    downwards: bool,
+    weight: Option<&EdgeWeight<ID>>,
+) where
+    ID: Hash + Eq,
+{
+    let mut neighbours: HashMap<&'g ID, Vec<(&'g ID, f64)>> = HashMap::new();
+    for (src, target) in agraph.edges() {
+        let weight = weight.map_or(1.0, |w| w(src, target) as f64);
+        match downwards {
+            true => neighbours.entry(target).or_default().push((src, weight)),
+            false => neighbours.entry(src).or_default().push((target, weight)),
+        }
+    }
+
+    let mut positions: HashMap<&'g ID, f64> = HashMap::new();
+    let relative = |level: &Level<'g, ID>, positions: &mut HashMap<&'g ID, f64>| {
+        for (index, id) in level.nodes.iter().enumerate() {
+            positions.insert(*id, (index as f64 + 0.5) / level.nodes.len() as f64);
+        }
+    };
+
+    let order: Vec<usize> = match downwards {
+        true => (0..levels.len()).collect(),
+        false => (0..levels.len()).rev().collect(),
+    };
+    for (step, index) in order.into_iter().enumerate() {
+        let level = &mut levels[index];
+        if step > 0 {
+            let count = level.nodes.len() as f64;
+            let mut keyed: Vec<(f64, &'g ID)> = level
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(current, id)| {
+                    let (total, weights) = neighbours
+                        .get(id)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|(n, w)| Some((positions.get(n)? * w, *w)))
+                        .fold((0.0, 0.0), |(t, ws), (p, w)| (t + p, ws + w));
+                    let key = match weights > 0.0 {
+                        true => total / weights,
+                        false => (current as f64 + 0.5) / count,
+                    };
+                    (key, *id)
+                })
+                .collect();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+            level.nodes = keyed.into_iter().map(|(_, id)| id).collect();
+        }
+        relative(level, &mut positions);
+    }
+}
+
+/// Swaps neighbouring Nodes within their Levels, as long as this reduces the number of Edges
+/// crossing each other. Only Edges leading to the same Level are compared, which does not account
+/// for the Routing of the Edges, but is cheap enough to be repeated until nothing changes anymore.
+///
+/// With [Layout-Weights](crate::Config::layout_weights), every Crossing counts as the Product of
+/// the Weights of both Edges.
+pub(crate) fn refine<'g, ID, T>(
+    agraph: &AcyclicDirectedGraph<'g, ID, T>,
+    levels: &mut [Level<'g, ID>],
+    weight: Option<&EdgeWeight<ID>>,
+) where
+    ID: Hash + Eq,
+{
+    let mut neighbours: HashMap<&'g ID, Vec<(&'g ID, u64)>> = HashMap::new();
+    for (src, target) in agraph.edges() {
+        let weight = weight.map_or(1, |w| w(src, target));
+        neighbours.entry(src).or_default().push((target, weight));
+        neighbours.entry(target).or_default().push((src, weight));
+    }
+
+    // The Level and Index of every Node
+    let mut positions: HashMap<&'g ID, (usize, usize)> = levels
+        .iter()
+        .enumerate()
+        .flat_map(|(l, level)| {
+            level
+                .nodes
+                .iter()
+                .enumerate()
+                .map(move |(i, id)| (*id, (l, i)))
+        })
+        .collect();
+
+    // The number of Crossings between the Edges of both Nodes, if `left` is placed left of `right`
+    let crossings = |left: &ID, right: &ID, positions: &HashMap<&'g ID, (usize, usize)>| {
+        let edges = |id: &ID| {
+            neighbours
+                .get(id)
+                .into_iter()
+                .flatten()
+                .filter_map(|(n, w)| Some((positions.get(n)?, *w)))
+        };
+        edges(left)
+            .flat_map(|a| edges(right).map(move |b| (a, b)))
+            .filter(|(((la, ia), _), ((lb, ib), _))| la == lb && ia > ib)
+            .map(|((_, wa), (_, wb))| wa.saturating_mul(wb))
+            .fold(0u64, u64::saturating_add)
+    };
+
+    // Every Swap reduces the number of Crossings, so this eventually stops
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for level in levels.iter_mut() {
+            for index in 1..level.nodes.len() {
+                let (left, right) = (level.nodes[index - 1], level.nodes[index]);
+                if crossings(right, left, &positions) < crossings(left, right, &positions) {
+                    level.nodes.swap(index - 1, index);
+                    positions.entry(left).and_modify(|(_, i)| *i += 1);
+                    positions.entry(right).and_modify(|(_, i)| *i -= 1);
+                    improved = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn uncrosses_pair() {
+        let nodes: HashMap<&i32, &()> = [(&0, &()), (&1, &()), (&2, &()), (&3, &())].into();
+        let edges: HashMap<&i32, HashSet<&i32>> =
+            [(&0, [&3].into()), (&1, [&2].into())].into_iter().collect();
+        let agraph = AcyclicDirectedGraph::new(nodes, edges);
+
+        let mut levels = vec![
+            Level {
+                nodes: vec![&0, &1],
+            },
+            Level {
+                nodes: vec![&2, &3],
+            },
+        ];
+        barycenter(&agraph, &mut levels, true, None);
+        assert_eq!(vec![&3, &2], levels[1].nodes);
+
+        barycenter(&agraph, &mut levels, false, None);
+        assert_eq!(vec![&0, &1], levels[0].nodes);
+    }
+
+    #[test]
+    fn refine_swaps() {
+        let ids = [0, 1, 2, 3, 4];
+        let nodes: HashMap<&i32, &()> = ids.iter().map(|id| (id, &())).collect();
+        let edges: HashMap<&i32, HashSet<&i32>> = [(&0, [&3, &4].into()), (&1, [&2].into())]
+            .into_iter()
+            .collect();
+        let agraph = AcyclicDirectedGraph::new(nodes, edges);
+
+        let mut levels = vec![
+            Level {
+                nodes: vec![&0, &1],
+            },
+            Level {
+                nodes: vec![&2, &3, &4],
+            },
+        ];
+        refine(&agraph, &mut levels, None);
+        assert_eq!(vec![&1, &0], levels[0].nodes);
+        assert_eq!(vec![&2, &3, &4], levels[1].nodes);
+    }
+
+    #[test]
+    fn weighted_refine() {
+        let ids = [0, 1, 2, 3];
+        let nodes: HashMap<&i32, &()> = ids.iter().map(|id| (id, &())).collect();
+        let edges: HashMap<&i32, HashSet<&i32>> = [(&0, [&2, &3].into()), (&1, [&2, &3].into())]
+            .into_iter()
+            .collect();
+        let agraph = AcyclicDirectedGraph::new(nodes, edges);
+        let refined = |weight: Option<&EdgeWeight<i32>>| {
+            let mut levels = vec![
+                Level {
+                    nodes: vec![&0, &1],
+                },
+                Level {
+                    nodes: vec![&2, &3],
+                },
+            ];
+            refine(&agraph, &mut levels, weight);
+            levels[0].nodes.clone()
+        };
+
+        // One Crossing is unavoidable, but it should not involve the heavy Edge
+        assert_eq!(vec![&0, &1], refined(None));
+        let weight: EdgeWeight<i32> = Box::new(|src, target| match (*src, *target) {
+            (1, 2) => 5,
+            _ => 1,
+        });
+        assert_eq!(vec![&1, &0], refined(Some(&weight)));
+    }
+
+    #[test]
+    fn seeded_shuffle() {
+        let shuffled = |seed| {
+            let mut items: Vec<usize> = (0..10).collect();
+            Rng::new(seed).shuffle(&mut items);
+            items
+        };
+        assert_eq!(shuffled(7), shuffled(7));
+
+        let mut sorted = shuffled(7);
+        sorted.sort();
+        assert_eq!((0..10).collect::<Vec<_>>(), sorted);
+    }
+}
@@ -0,0 +1,180 @@
+//! Displays a Graph as an indented Outline, similar to `cargo tree`, instead of placing its Nodes
+//! in a Grid.
+//!
+//! Every Node is listed below the Node it is reached from, with its Successors indented below it.
+//! Nodes that are reached more than once are only expanded the first time and are marked with
+//! `(*)` afterwards. This is much cheaper to compute than the Grid and easier to read for deep but
+//! narrow Graphs.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{Config, DirectedGraph, IDFormatter};
+//!
+//! let config = Config::new(IDFormatter::new(), 3);
+//! let mut graph = DirectedGraph::new();
+//! graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+//! graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+//!
+//! termgraph::outline::display(&graph, &config);
+//! ```
+//!
+//! Which displays:
+//! ```text
+//! (0)
+//! |-- (1)
+//! |   `-- (2)
+//! `-- (2) (*)
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{names, Config, DirectedGraph, DisplayOptions, NodeContext};
+
+/// Displays the Outline of the given Graph.
+///
+/// The Outline starts at the Nodes without incoming Edges. If some Nodes can not be reached from
+/// them, because they are part of a Cycle, the Outline continues at one of those Nodes. The
+/// Successors of every Node are sorted by their Labels.
+pub fn display<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>)
+where
+    ID: Hash + Eq,
+{
+    fdisplay(graph, config, std::io::stdout().lock());
+}
+
+/// The same as [`display`], but allows you to specify the Output Target
+pub fn fdisplay<ID, T, W>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>, dest: W)
+where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    fdisplay_with(graph, config, &DisplayOptions::new(), dest);
+}
+
+/// The same as [`fdisplay`], but only includes the Nodes allowed by the Filter of the Options
+pub fn fdisplay_with<ID, T, W>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    options: &DisplayOptions<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    let included = |id: &ID, value: &T| options.filter.as_ref().is_none_or(|f| f(id, value));
+    let nodes: HashMap<&ID, &T> = graph
+        .nodes()
+        .filter(|(id, value)| included(id, value))
+        .collect();
+
+    let mut in_degrees: HashMap<&ID, usize> = nodes.keys().map(|id| (*id, 0)).collect();
+    let mut successors: HashMap<&ID, Vec<&ID>> = HashMap::with_capacity(nodes.len());
+    for (src, target) in graph.edges() {
+        if nodes.contains_key(src) && nodes.contains_key(target) {
+            *in_degrees.entry(target).or_default() += 1;
+            successors.entry(src).or_default().push(target);
+        }
+    }
+
+    let labels: HashMap<&ID, String> = nodes
+        .iter()
+        .map(|(id, value)| {
+            let context = || NodeContext {
+                in_degree: in_degrees[id],
+                out_degree: successors.get(id).map_or(0, Vec::len),
+                level: 0,
+            };
+            (*id, names::single_line(config, id, value, context))
+        })
+        .collect();
+
+    let by_label = |ids: &mut Vec<&ID>| ids.sort_by(|a, b| labels[a].cmp(&labels[b]));
+    for targets in successors.values_mut() {
+        by_label(targets);
+    }
+    let mut starts: Vec<&ID> = nodes.keys().copied().collect();
+    by_label(&mut starts);
+    // The Nodes without incoming Edges come first, so the rest is mostly reached from them
+    starts.sort_by_key(|id| in_degrees[id] > 0);
+
+    let mut seen: HashSet<&ID> = HashSet::with_capacity(nodes.len());
+    for start in starts {
+        if !seen.insert(start) {
+            continue;
+        }
+        let _ = writeln!(dest, "{}{}", config.line_prefix, labels[start]);
+
+        // The Successors that still need to be listed for every Node on the current Path
+        let mut stack: Vec<std::slice::Iter<'_, &ID>> =
+            vec![successors.get(start).map(|s| s.iter()).unwrap_or_default()];
+        while let Some(current) = stack.last_mut() {
+            let Some(next) = current.next() else {
+                stack.pop();
+                continue;
+            };
+            let is_last = current.len() == 0;
+
+            let indent: String = stack[..stack.len() - 1]
+                .iter()
+                .map(|rest| if rest.len() == 0 { "    " } else { "|   " })
+                .collect();
+            let branch = if is_last { "`-- " } else { "|-- " };
+            let expanded = seen.insert(*next);
+            let marker = if expanded { "" } else { " (*)" };
+            let _ = writeln!(
+                dest,
+                "{}{indent}{branch}{}{marker}",
+                config.line_prefix, labels[next]
+            );
+
+            if expanded {
+                stack.push(successors.get(next).map(|s| s.iter()).unwrap_or_default());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn shared_nodes() {
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+        graph.add_edges([(0, 1), (0, 2), (1, 2), (2, 3)]);
+
+        let mut output = Vec::new();
+        fdisplay(&graph, &config, &mut output);
+
+        let expected = "\
+(0)
+|-- (1)
+|   `-- (2)
+|       `-- (3)
+`-- (2) (*)
+";
+        assert_eq!(expected, String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn cycles() {
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second")]);
+        graph.add_edges([(0, 1), (1, 0)]);
+
+        let mut output = Vec::new();
+        fdisplay(&graph, &config, &mut output);
+
+        assert_eq!(
+            "(0)\n`-- (1)\n    `-- (0) (*)\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
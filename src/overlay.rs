@@ -0,0 +1,229 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use crate::{Color, Config, DirectedGraph, DisplayOptions, Layout};
+
+/// The Graphs of an [`Overlay`], that a Node or Edge is part of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Only part of the first Graph
+    First,
+    /// Only part of the second Graph
+    Second,
+    /// Part of both Graphs
+    Both,
+}
+
+impl Source {
+    fn of(first: bool, second: bool) -> Option<Self> {
+        match (first, second) {
+            (true, true) => Some(Self::Both),
+            (true, false) => Some(Self::First),
+            (false, true) => Some(Self::Second),
+            (false, false) => None,
+        }
+    }
+}
+
+/// The Value of a Node in an [`Overlay`], which holds the Values of the Node in both Graphs
+#[derive(Debug)]
+pub struct Overlaid<'a, T> {
+    /// The Value in the first Graph
+    pub first: Option<&'a T>,
+    /// The Value in the second Graph
+    pub second: Option<&'a T>,
+}
+
+impl<T> Overlaid<'_, T> {
+    /// The Graphs the Node is part of
+    pub fn source(&self) -> Source {
+        Source::of(self.first.is_some(), self.second.is_some())
+            .expect("Every Node is part of at least one Graph")
+    }
+}
+
+/// Displays the Value of the first Graph, if the Node is part of it, or otherwise the Value of the
+/// second Graph
+impl<T> Display for Overlaid<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.first.or(self.second) {
+            Some(value) => value.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The Union of two Graphs, in which every Node and Edge is annotated with the Graphs it came
+/// from, see [`overlay`]
+pub struct Overlay<'a, ID, T> {
+    graph: DirectedGraph<ID, Overlaid<'a, T>>,
+    edges: HashMap<ID, HashMap<ID, Source>>,
+}
+
+/// Combines the two Graphs into a single one, while keeping track of which of them every Node and
+/// Edge came from.
+///
+/// When displayed, the Nodes and Edges that are only part of one of the Graphs are colored with
+/// the Color of that Graph, while the shared ones are drawn as usual.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let mut before = DirectedGraph::new();
+/// before.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// before.add_edges([(0, 1), (1, 2)]);
+///
+/// let mut after = DirectedGraph::new();
+/// after.add_nodes([(0, "first"), (1, "second"), (3, "fourth")]);
+/// after.add_edges([(0, 1), (0, 3)]);
+///
+/// let overlay = termgraph::overlay(&before, &after);
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// overlay.display(&config);
+/// ```
+pub fn overlay<'a, ID, T>(
+    first: &'a DirectedGraph<ID, T>,
+    second: &'a DirectedGraph<ID, T>,
+) -> Overlay<'a, ID, T>
+where
+    ID: Hash + Eq + Clone,
+{
+    let mut values: HashMap<&'a ID, Overlaid<'a, T>> = first
+        .nodes()
+        .map(|(id, value)| {
+            let overlaid = Overlaid {
+                first: Some(value),
+                second: None,
+            };
+            (id, overlaid)
+        })
+        .collect();
+    for (id, value) in second.nodes() {
+        let entry = values.entry(id).or_insert(Overlaid {
+            first: None,
+            second: None,
+        });
+        entry.second = Some(value);
+    }
+
+    let mut edges: HashMap<ID, HashMap<ID, Source>> = HashMap::new();
+    for (src, target) in first.edges().chain(second.edges()) {
+        let in_first = first.successors(src).any(|t| t == target);
+        let in_second = second.successors(src).any(|t| t == target);
+        if let Some(source) = Source::of(in_first, in_second) {
+            edges
+                .entry(src.clone())
+                .or_default()
+                .insert(target.clone(), source);
+        }
+    }
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes(values.into_iter().map(|(id, value)| (id.clone(), value)));
+    graph.add_edges(
+        edges
+            .iter()
+            .flat_map(|(src, targets)| targets.keys().map(move |t| (src.clone(), t.clone()))),
+    );
+
+    Overlay { graph, edges }
+}
+
+impl<'a, ID, T> Overlay<'a, ID, T>
+where
+    ID: Hash + Eq + Clone,
+{
+    /// The combined Graph, which can be displayed like any other Graph
+    pub fn graph(&self) -> &DirectedGraph<ID, Overlaid<'a, T>> {
+        &self.graph
+    }
+
+    /// The Graphs the given Node is part of, if it is part of any of them
+    pub fn node_source(&self, id: &ID) -> Option<Source> {
+        self.graph.get(id).map(Overlaid::source)
+    }
+
+    /// The Graphs the given Edge is part of, if it is part of any of them
+    pub fn edge_source(&self, src: &ID, target: &ID) -> Option<Source> {
+        self.edges.get(src)?.get(target).copied()
+    }
+
+    /// The Options to display the combined Graph with, which color the Nodes and Edges that are
+    /// only part of one of the Graphs with the given Colors
+    pub fn options(&self, first: Color, second: Color) -> DisplayOptions<ID, Overlaid<'a, T>> {
+        let color = |source: Source| match source {
+            Source::First => Some(first.clone()),
+            Source::Second => Some(second.clone()),
+            Source::Both => None,
+        };
+
+        let nodes = self
+            .graph
+            .nodes()
+            .filter_map(|(id, value)| Some((id.clone(), color(value.source())?)));
+        let edges = self.edges.iter().flat_map(|(src, targets)| {
+            targets.iter().filter_map(move |(target, source)| {
+                Some(((src.clone(), target.clone()), color(*source)?))
+            })
+        });
+
+        DisplayOptions::new().node_colors(nodes).edge_colors(edges)
+    }
+
+    /// Displays the combined Graph, where the Nodes and Edges that are only part of the first
+    /// Graph are red and the ones only part of the second Graph are green
+    pub fn display(&self, config: &Config<ID, Overlaid<'a, T>>) {
+        self.fdisplay(config, std::io::stdout().lock());
+    }
+
+    /// The same as [`Overlay::display`], but allows you to specify the Output Target
+    pub fn fdisplay<W>(&self, config: &Config<ID, Overlaid<'a, T>>, mut dest: W)
+    where
+        W: std::io::Write,
+    {
+        let options = self.options(Color::Red, Color::Green);
+        Layout::compute_with(&self.graph, config, &options)
+            .fdisplay_with(config, &options, &mut dest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn sources() {
+        let mut first = DirectedGraph::new();
+        first.add_nodes([(0, "a"), (1, "b"), (2, "c")]);
+        first.add_edges([(0, 1), (1, 2)]);
+
+        let mut second = DirectedGraph::new();
+        second.add_nodes([(0, "a"), (1, "b"), (3, "d")]);
+        second.add_edges([(0, 1), (0, 3)]);
+
+        let overlay = overlay(&first, &second);
+        assert_eq!(Some(Source::Both), overlay.node_source(&0));
+        assert_eq!(Some(Source::First), overlay.node_source(&2));
+        assert_eq!(Some(Source::Second), overlay.node_source(&3));
+        assert_eq!(None, overlay.node_source(&4));
+
+        assert_eq!(Some(Source::Both), overlay.edge_source(&0, &1));
+        assert_eq!(Some(Source::First), overlay.edge_source(&1, &2));
+        assert_eq!(Some(Source::Second), overlay.edge_source(&0, &3));
+        assert_eq!(None, overlay.edge_source(&1, &0));
+
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut output = Vec::new();
+        overlay.fdisplay(&config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("\x1b[31m(2)\x1b[0m"));
+        assert!(output.contains("\x1b[32m(3)\x1b[0m"));
+        assert!(!output.contains("\x1b[31m(0)"));
+    }
+}
@@ -0,0 +1,278 @@
+//! Records the intermediate Results of a [`Layout`](crate::Layout), so that a broken Layout can be
+//! captured wherever it occurs and replayed later on, without needing the original Graph.
+//!
+//! A [`Recording`] contains the acyclic Graph with the formatted Labels of the Nodes, the Edges
+//! that were reversed, the ordered Nodes of every Level and the Output that was produced from them.
+//! The Nodes are identified by their [`Display`](std::fmt::Display) representation. Replaying
+//! the Recording runs the placement of the Nodes and the routing of the Edges again, so the
+//! Output can be compared to the recorded one.
+//!
+//! # Example
+//! ```rust
+//! use termgraph::{record::Recording, Config, DirectedGraph, IDFormatter, Layout};
+//!
+//! let config = Config::new(IDFormatter::new(), 3);
+//! let mut graph = DirectedGraph::new();
+//! graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+//! graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+//!
+//! // Capture the Layout, for example in a Log
+//! let serialized = Layout::compute(&graph, &config).record(&config).to_string();
+//!
+//! // Replay it in a Test, using the same Settings as the original Config
+//! let recording: Recording = serialized.parse().unwrap();
+//! let config = Config::new(IDFormatter::new(), 3);
+//! assert_eq!(recording.output(), recording.replay(&config));
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+    time::Instant,
+};
+
+use crate::{
+    acyclic::AcyclicDirectedGraph, grid::Grid, levels::Level, names::NodeNames, Config,
+    DisplayOptions,
+};
+
+/// The first Line of every serialized Recording
+const HEADER: &str = "termgraph recording";
+
+/// The captured intermediate Results of a Layout, see the [module level docs](self).
+///
+/// A Recording is serialized using its [`Display`] implementation and can be parsed again using
+/// [`str::parse`], which fails if an Edge or Level references a Node that is not listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    /// The Nodes with their formatted Labels
+    pub(crate) nodes: Vec<(String, String)>,
+    /// The Edges of the acyclic Graph
+    pub(crate) edges: Vec<(String, String)>,
+    /// The Edges that are routed back up the Graph
+    pub(crate) reversed: Vec<(String, String)>,
+    /// The ordered Nodes of every Level
+    pub(crate) levels: Vec<Vec<String>>,
+    /// The uncolored Output of the Layout
+    pub(crate) output: String,
+}
+
+/// The Error returned when parsing a malformed [`Recording`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingError {
+    /// The Line of the Input, starting at 1
+    pub line: usize,
+    /// What is wrong with the Line
+    pub message: String,
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl Recording {
+    /// The Output that was recorded together with the Layout
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// Places the recorded Levels and routes the Edges between them again, using the Spacing and
+    /// Glyphs of the given Config, and returns the uncolored Output.
+    ///
+    /// The Formatter of the Config is not used, as the Labels were already recorded.
+    pub fn replay(&self, config: &Config<String, String>) -> String {
+        let nodes: HashMap<&String, &String> = self.nodes.iter().map(|(id, l)| (id, l)).collect();
+        let mut edges: HashMap<&String, HashSet<&String>> = HashMap::new();
+        for (src, target) in &self.edges {
+            edges.entry(src).or_default().insert(target);
+        }
+        let names = NodeNames::construct(nodes.keys().copied(), |id| nodes[id].clone());
+        let agraph = AcyclicDirectedGraph::new(nodes, edges);
+
+        let levels: Vec<Level<'_, String>> = self
+            .levels
+            .iter()
+            .map(|level| Level {
+                nodes: level.iter().collect(),
+            })
+            .collect();
+        let reversed = self.reversed.iter().map(|(s, t)| (s, t)).collect();
+
+        let grid = Grid::construct(&agraph, &levels, reversed, config, &names, Instant::now());
+        let mut output = Vec::new();
+        grid.fdisplay(
+            &names,
+            &HashMap::new(),
+            &HashMap::new(),
+            config,
+            &DisplayOptions::new().colors(false),
+            &mut output,
+        );
+        String::from_utf8_lossy(&output).into_owned()
+    }
+}
+
+/// Escapes the Separators used by the serialized Format
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('t') => result.push('\t'),
+                Some('n') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            },
+            (c, false) => result.push(c),
+        }
+    }
+    result
+}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        for (id, label) in &self.nodes {
+            writeln!(f, "node\t{}\t{}", escape(id), escape(label))?;
+        }
+        for (kind, edges) in [("edge", &self.edges), ("reversed", &self.reversed)] {
+            for (src, target) in edges {
+                writeln!(f, "{kind}\t{}\t{}", escape(src), escape(target))?;
+            }
+        }
+        for level in &self.levels {
+            write!(f, "level")?;
+            for id in level {
+                write!(f, "\t{}", escape(id))?;
+            }
+            writeln!(f)?;
+        }
+        for line in self.output.lines() {
+            writeln!(f, "output\t{}", escape(line))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = RecordingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(index, line)| (index + 1, line));
+        if lines.next().map(|(_, line)| line) != Some(HEADER) {
+            return Err(RecordingError {
+                line: 1,
+                message: format!("expected the header {HEADER:?}"),
+            });
+        }
+
+        let mut recording = Recording {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            reversed: Vec::new(),
+            levels: Vec::new(),
+            output: String::new(),
+        };
+        // The Lines referencing Nodes, which are checked once all the Nodes are known
+        let mut references: Vec<(usize, String)> = Vec::new();
+        for (line, content) in lines {
+            let mut fields = content.split('\t');
+            let kind = fields.next().unwrap_or_default();
+            let fields: Vec<String> = fields.map(unescape).collect();
+
+            let pair = |fields: Vec<String>| match <[String; 2]>::try_from(fields) {
+                Ok([first, second]) => Ok((first, second)),
+                Err(fields) => Err(RecordingError {
+                    line,
+                    message: format!("expected 2 fields for {kind:?}, found {}", fields.len()),
+                }),
+            };
+            if matches!(kind, "edge" | "reversed" | "level") {
+                references.extend(fields.iter().map(|id| (line, id.clone())));
+            }
+            match kind {
+                "node" => recording.nodes.push(pair(fields)?),
+                "edge" => recording.edges.push(pair(fields)?),
+                "reversed" => recording.reversed.push(pair(fields)?),
+                "level" => recording.levels.push(fields),
+                "output" => {
+                    recording.output.push_str(&fields.join("\t"));
+                    recording.output.push('\n');
+                }
+                "" => {}
+                other => {
+                    return Err(RecordingError {
+                        line,
+                        message: format!("unknown entry {other:?}"),
+                    })
+                }
+            }
+        }
+
+        let nodes: HashSet<&String> = recording.nodes.iter().map(|(id, _)| id).collect();
+        if let Some((line, id)) = references.iter().find(|(_, id)| !nodes.contains(id)) {
+            return Err(RecordingError {
+                line: *line,
+                message: format!("unknown node {id:?}"),
+            });
+        }
+
+        Ok(recording)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DirectedGraph, IDFormatter, Layout};
+
+    #[test]
+    fn roundtrip() {
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+        graph.add_edges([(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)]);
+
+        let recording = Layout::compute(&graph, &config).record(&config);
+        let parsed: Recording = recording.to_string().parse().unwrap();
+        assert_eq!(recording, parsed);
+
+        let config = Config::new(IDFormatter::new(), 3);
+        assert_eq!(recording.output(), parsed.replay(&config));
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!("a\\tb\\nc\\\\", escape("a\tb\nc\\"));
+        assert_eq!("a\tb\nc\\", unescape(&escape("a\tb\nc\\")));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(1, "nodes".parse::<Recording>().unwrap_err().line);
+
+        let error = format!("{HEADER}\nnode\t0\nedge\t0\t1")
+            .parse::<Recording>()
+            .unwrap_err();
+        assert_eq!(2, error.line);
+
+        let error = format!("{HEADER}\nnode\t0\t(0)\nlevel\t0\nedge\t0\t1")
+            .parse::<Recording>()
+            .unwrap_err();
+        assert_eq!(4, error.line);
+        assert_eq!("unknown node \"1\"", error.message);
+    }
+}
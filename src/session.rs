@@ -0,0 +1,199 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{layout::LayoutShape, Config, DirectedGraph, Layout};
+
+/// Displays the same Graph repeatedly, while reusing as much of the previous Work as possible.
+///
+/// Whether the Graph changed is determined by a Generation, which is provided by the user and
+/// should be incremented whenever the Graph is modified. The Session keeps the results of the
+/// previous call:
+/// * As long as the Generation stays the same, the previous Output is written again, so the
+///   Formatter is not called and the Cycles of the Graph are not broken again.
+/// * For a new Generation, where the Graph still has the same Nodes and Edges and only the Values
+///   of the Nodes changed, the broken Cycles and the Levels are reused. Only the Names are
+///   formatted again and the Nodes are placed again within their Levels.
+/// * After [`RenderSession::restyle`], like when the Colors or Glyphs of the Config changed, the
+///   Names of the same Generation are reused as well.
+///
+/// As the Levels are reused, the Session should be [invalidated](RenderSession::invalidate) after
+/// changing how the Nodes are distributed, like the Width of the Levels.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter, RenderSession};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut session = RenderSession::new();
+///
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+///
+/// // The second call only writes the Output of the first one again
+/// session.fdisplay(&graph, &config, 0, std::io::stdout().lock());
+/// session.fdisplay(&graph, &config, 0, std::io::stdout().lock());
+///
+/// // Only the Value changed, so the Levels are reused
+/// graph.add_nodes([(1, "updated")]);
+/// session.fdisplay(&graph, &config, 1, std::io::stdout().lock());
+///
+/// // The Colors only change the Output, so the Names are reused
+/// let config = config.default_colors();
+/// session.restyle();
+/// session.fdisplay(&graph, &config, 1, std::io::stdout().lock());
+/// ```
+#[derive(Debug)]
+pub struct RenderSession<ID> {
+    rendered: Option<(u64, Vec<u8>)>,
+    /// The Names of the last Layout and the number of cut off Labels, keyed by the Generation
+    names: Option<(u64, HashMap<ID, String>, usize)>,
+    shape: Option<CachedShape<ID>>,
+}
+
+/// The Shape of the last Layout, together with the Nodes and Edges it was computed for
+#[derive(Debug)]
+struct CachedShape<ID> {
+    nodes: HashSet<ID>,
+    edges: HashMap<ID, HashSet<ID>>,
+    edge_count: usize,
+    shape: LayoutShape<ID>,
+}
+
+impl<ID> CachedShape<ID>
+where
+    ID: Hash + Eq + Clone,
+{
+    fn new<T>(graph: &DirectedGraph<ID, T>, shape: LayoutShape<ID>) -> Self {
+        let mut edges: HashMap<ID, HashSet<ID>> = HashMap::new();
+        for (src, target) in graph.edges() {
+            edges.entry(src.clone()).or_default().insert(target.clone());
+        }
+        Self {
+            nodes: graph.nodes().map(|(id, _)| id.clone()).collect(),
+            edges,
+            edge_count: graph.edges().count(),
+            shape,
+        }
+    }
+
+    /// Whether the Graph has exactly the same Nodes and Edges
+    fn matches<T>(&self, graph: &DirectedGraph<ID, T>) -> bool {
+        graph.nodes().count() == self.nodes.len()
+            && graph.nodes().all(|(id, _)| self.nodes.contains(id))
+            && graph.edges().count() == self.edge_count
+            && graph
+                .edges()
+                .all(|(src, target)| self.edges.get(src).is_some_and(|t| t.contains(target)))
+    }
+}
+
+impl<ID> Default for RenderSession<ID> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ID> RenderSession<ID> {
+    /// Creates a new Session, for which nothing was rendered yet
+    pub fn new() -> Self {
+        Self {
+            rendered: None,
+            names: None,
+            shape: None,
+        }
+    }
+
+    /// Forgets the previous Output, while keeping the Levels and Names, so the next call with the
+    /// same Generation only places the Nodes again. This is intended for a Config that only
+    /// changes how the Graph is drawn, like its Colors or Glyphs.
+    pub fn restyle(&mut self) {
+        self.rendered = None;
+    }
+
+    /// Forgets everything about the previous calls, so the next call computes the Layout again
+    /// regardless of its Generation
+    pub fn invalidate(&mut self) {
+        self.rendered = None;
+        self.names = None;
+        self.shape = None;
+    }
+}
+
+impl<ID> RenderSession<ID>
+where
+    ID: Hash + Eq + Clone,
+{
+    /// Displays the Graph on the Terminal, see [`RenderSession::fdisplay`]
+    pub fn display<T>(
+        &mut self,
+        graph: &DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        generation: u64,
+    ) {
+        self.fdisplay(graph, config, generation, std::io::stdout().lock());
+    }
+
+    /// Writes the Graph to the given Output Target, which only computes the Parts of its Layout
+    /// that could not be reused from the previous call, see [`RenderSession`]
+    pub fn fdisplay<T, W>(
+        &mut self,
+        graph: &DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        generation: u64,
+        mut dest: W,
+    ) where
+        W: std::io::Write,
+    {
+        let output = match self.rendered.take() {
+            Some((previous, output)) if previous == generation => output,
+            _ => {
+                let mut output = Vec::new();
+                self.layout(graph, config, generation)
+                    .fdisplay(config, &mut output);
+                output
+            }
+        };
+
+        let _ = dest.write_all(&output);
+        self.rendered = Some((generation, output));
+    }
+
+    /// Computes the Layout, by reusing the Shape and Names of the previous Layout if possible
+    fn layout<'g, T>(
+        &mut self,
+        graph: &'g DirectedGraph<ID, T>,
+        config: &Config<ID, T>,
+        generation: u64,
+    ) -> Layout<'g, ID, T> {
+        let shape = self.shape.take().filter(|cached| cached.matches(graph));
+        let names = self
+            .names
+            .take()
+            .filter(|(previous, _, _)| shape.is_some() && *previous == generation);
+
+        let layout = match &shape {
+            Some(cached) => Layout::from_shape(
+                graph,
+                config,
+                &cached.shape,
+                names
+                    .as_ref()
+                    .map(|(_, names, truncated)| (names, *truncated)),
+            ),
+            None => Layout::compute(graph, config),
+        };
+
+        self.shape = shape.or_else(|| layout.shape().map(|shape| CachedShape::new(graph, shape)));
+        self.names = Some(match names {
+            Some(names) => names,
+            None => {
+                let (names, truncated) = layout.owned_names();
+                (generation, names, truncated)
+            }
+        });
+        layout
+    }
+}
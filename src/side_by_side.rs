@@ -0,0 +1,195 @@
+use std::hash::Hash;
+
+use crate::{Config, DirectedGraph, DisplayOptions};
+
+/// Displays two Graphs next to each other on the Terminal, separated by a vertical Line.
+///
+/// This is useful to compare two versions of a Graph, like before and after a transformation.
+///
+/// # Note
+/// The Edges are not colored, so that the Lines of both Graphs can be aligned. If both Graphs
+/// together are wider than the [max glyphs per layer](Config::max_glyphs_per_layer), every Graph
+/// is laid out within its share of the Width, where a narrower Graph leaves the rest of the Width
+/// to the other one. Lines that still do not fit are cut off.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut before = DirectedGraph::new();
+/// before.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// before.add_edges([(0, 1), (0, 2), (1, 2)]);
+///
+/// let mut after = DirectedGraph::new();
+/// after.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// after.add_edges([(0, 1), (1, 2)]);
+///
+/// termgraph::display_side_by_side(&before, &after, &config);
+/// ```
+pub fn display_side_by_side<ID, T>(
+    left: &DirectedGraph<ID, T>,
+    right: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+) where
+    ID: Hash + Eq,
+{
+    fdisplay_side_by_side(left, right, config, std::io::stdout().lock());
+}
+
+/// The same as [`display_side_by_side`], but allows you to specify the Output Target
+pub fn fdisplay_side_by_side<ID, T, W>(
+    left: &DirectedGraph<ID, T>,
+    right: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    mut dest: W,
+) where
+    ID: Hash + Eq,
+    W: std::io::Write,
+{
+    let separator = format!(" {} ", config.line_glyphs.vertical);
+    let available = config
+        .glyph_width()
+        .saturating_sub(separator.chars().count());
+
+    let width = |lines: &[String]| lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let (left_lines, right_lines) = (render_lines(left, config), render_lines(right, config));
+    let (left, right) = match width(&left_lines).saturating_add(width(&right_lines)) > available {
+        // Every Graph is laid out within half of the Width first, so the Width the narrower one
+        // leaves unused can be given to the other one
+        true => {
+            let half = available / 2;
+            let capped =
+                |graph| config.with_glyph_width(half.max(1), || render_lines(graph, config));
+            let (left_capped, right_capped) = (capped(left), capped(right));
+            let rest = |lines: &[String]| available.saturating_sub(width(lines)).max(1);
+            match width(&left_capped) < width(&right_capped) {
+                true => {
+                    let right =
+                        config.with_glyph_width(rest(&left_capped), || render_lines(right, config));
+                    (left_capped, right)
+                }
+                false => {
+                    let left =
+                        config.with_glyph_width(rest(&right_capped), || render_lines(left, config));
+                    (left, right_capped)
+                }
+            }
+        }
+        false => (left_lines, right_lines),
+    };
+    let (left_width, right_width) = (width(&left), width(&right));
+    let (left_width, right_width) = match left_width.saturating_add(right_width) > available {
+        // The narrower Graph keeps its width, if it fits into half of the available width
+        true => {
+            let left_width =
+                left_width.min((available / 2).max(available.saturating_sub(right_width)));
+            (left_width, available - left_width)
+        }
+        false => (left_width, right_width),
+    };
+
+    for row in 0..left.len().max(right.len()) {
+        let left = left
+            .get(row)
+            .map(|l| clip(l, left_width))
+            .unwrap_or_default();
+        let right = right
+            .get(row)
+            .map(|l| clip(l, right_width))
+            .unwrap_or_default();
+        let padding = left_width - left.chars().count();
+
+        let _ = writeln!(
+            dest,
+            "{}{}{:padding$}{}{}",
+            config.line_prefix, left, "", separator, right
+        );
+    }
+}
+
+/// Renders the Graph without Colors and returns its Lines, without the Line-Prefix
+fn render_lines<ID, T>(graph: &DirectedGraph<ID, T>, config: &Config<ID, T>) -> Vec<String>
+where
+    ID: Hash + Eq,
+{
+    let mut output = Vec::new();
+    crate::fdisplay_with(
+        graph,
+        config,
+        &DisplayOptions::new().colors(false),
+        &mut output,
+    );
+
+    String::from_utf8_lossy(&output)
+        .lines()
+        .map(|line| {
+            line.strip_prefix(config.line_prefix.as_str())
+                .unwrap_or(line)
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Cuts the Line off after the given number of Glyphs
+fn clip(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IDFormatter;
+
+    #[test]
+    fn interleave_rows() {
+        let mut left = DirectedGraph::new();
+        left.add_nodes([(0, "first"), (1, "second")]);
+        left.add_edges([(0, 1)]);
+
+        let mut right = DirectedGraph::new();
+        right.add_nodes([(2, "third")]);
+
+        let config = Config::new(IDFormatter::new(), 3);
+        let mut output = Vec::new();
+        fdisplay_side_by_side(&left, &right, &config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(" (0) |  (2)", lines[0]);
+        assert_eq!("  |  | ", lines[1]);
+        assert!(lines.iter().all(|l| l.find('|').is_some()));
+    }
+
+    #[test]
+    fn combined_width_limit() {
+        let mut left = DirectedGraph::new();
+        left.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+
+        let config = Config::new(IDFormatter::new(), 3).max_glyphs_per_layer(20);
+        let mut output = Vec::new();
+        fdisplay_side_by_side(&left, &left, &config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.lines().all(|l| l.chars().count() <= 20));
+    }
+
+    #[test]
+    fn layout_within_share() {
+        let mut wide = DirectedGraph::new();
+        wide.add_nodes((0..6).map(|i| (i, ())));
+
+        let config = Config::new(IDFormatter::new(), 6).max_glyphs_per_layer(40);
+        let mut output = Vec::new();
+        fdisplay_side_by_side(&wide, &wide, &config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        // Both Graphs are laid out within their half, instead of cutting off the Nodes
+        for id in 0..6 {
+            let label = format!("({id})");
+            assert_eq!(2, output.matches(&label).count(), "{output}");
+        }
+        assert!(output.lines().all(|l| l.chars().count() <= 40));
+    }
+}
@@ -0,0 +1,94 @@
+use std::{hash::Hash, io::Write};
+
+use crate::{formatter::strip_ansi, Config, DirectedGraph, Layout};
+
+/// Renders the Graph once and writes the same Output to all the given Writers, like the Terminal
+/// and a Log-File.
+///
+/// The Colors are only stripped for the Writers wrapped in [`StripColors`], so every Writer can
+/// decide on its own whether it receives them.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter, StripColors};
+///
+/// let config = Config::new(IDFormatter::new(), 3).default_colors();
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+/// graph.add_edges([(0, 1), (0, 2)]);
+///
+/// let mut log = StripColors::new(Vec::new());
+/// termgraph::fdisplay_multi(&graph, &config, &mut [&mut std::io::stdout(), &mut log]);
+///
+/// assert!(!log.into_inner().contains(&b'\x1b'));
+/// ```
+pub fn fdisplay_multi<ID, T>(
+    graph: &DirectedGraph<ID, T>,
+    config: &Config<ID, T>,
+    writers: &mut [&mut dyn Write],
+) where
+    ID: Hash + Eq,
+{
+    let mut output = Vec::new();
+    Layout::compute(graph, config).fdisplay(config, &mut output);
+
+    for dest in writers.iter_mut() {
+        let _ = dest.write_all(&output);
+        let _ = dest.flush();
+    }
+}
+
+/// Removes the Colors and other Escape-Sequences, like Hyperlinks, from everything written to the
+/// inner Writer.
+///
+/// The Output is passed on line by line, where the last incomplete Line is only written once the
+/// Writer is flushed or a Newline follows.
+pub struct StripColors<W> {
+    inner: W,
+    pending: Vec<u8>,
+}
+
+impl<W> StripColors<W>
+where
+    W: Write,
+{
+    /// Wraps the given Writer
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Flushes the pending Output and returns the inner Writer
+    pub fn into_inner(mut self) -> W {
+        let _ = self.flush();
+        self.inner
+    }
+
+    /// Writes the pending Output up to and including the given Index
+    fn write_pending(&mut self, end: usize) -> std::io::Result<()> {
+        let line: Vec<u8> = self.pending.drain(..end).collect();
+        let stripped = strip_ansi(&String::from_utf8_lossy(&line));
+        self.inner.write_all(stripped.as_bytes())
+    }
+}
+
+impl<W> Write for StripColors<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        // Escape-Sequences never span multiple Lines, so complete Lines can be stripped on their own
+        if let Some(newline) = self.pending.iter().rposition(|b| *b == b'\n') {
+            self.write_pending(newline + 1)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_pending(self.pending.len())?;
+        self.inner.flush()
+    }
+}
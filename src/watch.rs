@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::Duration,
+};
+
+use crate::{Config, DirectedGraph, DisplayOptions};
+
+/// Clears the Terminal and moves the Cursor to the top left
+pub(crate) const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Displays a Graph repeatedly, while highlighting the Nodes that changed since the previous
+/// Frame.
+///
+/// A Node is considered changed, if it is new, its Value changed or an Edge from or to it was
+/// added or removed. The added Edges are highlighted as well, unless the Colors are disabled. The
+/// Highlight only lasts for the one Frame in which the change was detected.
+///
+/// # Example
+/// ```rust
+/// use termgraph::{Config, DirectedGraph, IDFormatter, Watcher};
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// let mut watcher = Watcher::new();
+///
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+/// watcher.fdisplay(&graph, &config, std::io::stdout().lock());
+///
+/// // The Node 2 is highlighted in the second Frame
+/// graph.add_nodes([(2, "third")]);
+/// watcher.fdisplay(&graph, &config, std::io::stdout().lock());
+/// ```
+pub struct Watcher<ID, T> {
+    previous: Option<Snapshot<ID, T>>,
+}
+
+/// The State of the Graph, that was displayed in the previous Frame
+struct Snapshot<ID, T> {
+    nodes: HashMap<ID, T>,
+    edges: HashSet<(ID, ID)>,
+}
+
+impl<ID, T> Watcher<ID, T>
+where
+    ID: Hash + Eq + Clone,
+    T: PartialEq + Clone,
+{
+    /// Creates a new Watcher, for which nothing was displayed yet
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+
+    /// Compares the Graph to the one from the previous Frame and returns the changed Nodes.
+    ///
+    /// The Graph is then remembered for the next Frame. Nothing is considered changed in the first
+    /// Frame.
+    pub fn changes(&mut self, graph: &DirectedGraph<ID, T>) -> HashSet<ID> {
+        self.diff(graph).0
+    }
+
+    /// Compares the Graph to the one from the previous Frame and returns the changed Nodes as
+    /// well as the added Edges
+    fn diff(&mut self, graph: &DirectedGraph<ID, T>) -> (HashSet<ID>, HashSet<(ID, ID)>) {
+        let current = Snapshot {
+            nodes: graph
+                .nodes()
+                .map(|(id, value)| (id.clone(), value.clone()))
+                .collect(),
+            edges: graph
+                .edges()
+                .map(|(src, target)| (src.clone(), target.clone()))
+                .collect(),
+        };
+
+        let changed = match self.previous.as_ref() {
+            Some(previous) => {
+                let added = current.edges.difference(&previous.edges).cloned().collect();
+
+                let nodes = current
+                    .nodes
+                    .iter()
+                    .filter(|(id, value)| previous.nodes.get(*id) != Some(*value))
+                    .map(|(id, _)| id.clone());
+                let edges = current
+                    .edges
+                    .symmetric_difference(&previous.edges)
+                    .flat_map(|(src, target)| [src.clone(), target.clone()]);
+
+                let nodes = nodes
+                    .chain(edges)
+                    .filter(|id| current.nodes.contains_key(id))
+                    .collect();
+                (nodes, added)
+            }
+            None => (HashSet::new(), HashSet::new()),
+        };
+
+        self.previous = Some(current);
+        changed
+    }
+
+    /// Displays the next Frame, highlighting the Nodes and Edges that changed since the previous
+    /// one
+    pub fn fdisplay<W>(&mut self, graph: &DirectedGraph<ID, T>, config: &Config<ID, T>, dest: W)
+    where
+        W: std::io::Write,
+    {
+        let (nodes, edges) = self.diff(graph);
+        let options = DisplayOptions::new().highlight(nodes);
+        let color = options.highlight_color.clone();
+        let options = options.edge_colors(edges.into_iter().map(|edge| (edge, color.clone())));
+        crate::fdisplay_with(graph, config, &options, dest);
+    }
+}
+
+impl<ID, T> Default for Watcher<ID, T>
+where
+    ID: Hash + Eq + Clone,
+    T: PartialEq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Displays the Graphs returned by `source` on the Terminal, until it returns `None`.
+///
+/// Every Frame clears the Terminal and highlights the Nodes that changed compared to the previous
+/// Frame, see [`Watcher`]. After every Frame this waits for the `interval`. To redraw on a
+/// Notification instead, `source` can block until the next Graph is available and the interval
+/// can be set to zero.
+///
+/// # Example
+/// ```rust
+/// use std::{sync::mpsc, time::Duration};
+/// use termgraph::{Config, DirectedGraph, IDFormatter};
+///
+/// let (sender, receiver) = mpsc::channel();
+/// let mut graph = DirectedGraph::new();
+/// graph.add_nodes([(0, "first"), (1, "second")]);
+/// graph.add_edges([(0, 1)]);
+/// sender.send(graph).unwrap();
+/// drop(sender);
+///
+/// let config = Config::new(IDFormatter::new(), 3);
+/// termgraph::watch(|| receiver.recv().ok(), &config, Duration::ZERO);
+/// ```
+pub fn watch<ID, T, F>(mut source: F, config: &Config<ID, T>, interval: Duration)
+where
+    ID: Hash + Eq + Clone,
+    T: PartialEq + Clone,
+    F: FnMut() -> Option<DirectedGraph<ID, T>>,
+{
+    let mut watcher = Watcher::new();
+    while let Some(graph) = source() {
+        let mut stdout = std::io::stdout().lock();
+        let _ = std::io::Write::write_all(&mut stdout, CLEAR_SCREEN.as_bytes());
+        watcher.fdisplay(&graph, config, &mut stdout);
+        drop(stdout);
+
+        std::thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_changes() {
+        let mut watcher = Watcher::new();
+
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1)]);
+        assert!(watcher.changes(&graph).is_empty());
+        assert!(watcher.changes(&graph).is_empty());
+
+        graph.add_nodes([(1, "changed"), (3, "new")]);
+        graph.add_edges([(2, 0)]);
+        assert_eq!(HashSet::from([0, 1, 2, 3]), watcher.changes(&graph));
+        assert!(watcher.changes(&graph).is_empty());
+    }
+
+    #[test]
+    fn highlight_added_edges() {
+        let config = Config::new(crate::IDFormatter::new(), 3);
+        let mut watcher = Watcher::new();
+
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+        graph.add_edges([(0, 1)]);
+        watcher.fdisplay(&graph, &config, std::io::sink());
+
+        graph.add_edges([(1, 2)]);
+        let (_, edges) = watcher.diff(&graph);
+        assert_eq!(HashSet::from([(1, 2)]), edges);
+
+        graph.add_edges([(0, 2)]);
+        let mut output = Vec::new();
+        watcher.fdisplay(&graph, &config, &mut output);
+        let output = String::from_utf8(output).unwrap();
+        let colored = output
+            .lines()
+            .filter(|line| !line.contains('('))
+            .any(|line| line.contains("\x1b[7m"));
+        assert!(colored, "{output}");
+    }
+}
@@ -1,4 +1,7 @@
-use termgraph::{Config, DirectedGraph, IDFormatter};
+use termgraph::{
+    BackEdgeStyle, Color, ColorBy, Config, DirectedGraph, DisplayOptions, IDFormatter,
+    IDValueFormatter, Justify,
+};
 
 #[test]
 fn display_empty() {
@@ -18,3 +21,1539 @@ fn one_node() {
 
     termgraph::display(&graph, &config);
 }
+
+#[test]
+fn relabel_layout() {
+    use termgraph::{Layout, ValueFormatter};
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, 10), (1, 20)]);
+    graph.add_edges([(0, 1)]);
+    let snapshot = graph.clone();
+
+    let config = Config::new(ValueFormatter::new(), 10);
+
+    let mut layout = Layout::compute(&snapshot, &config);
+
+    let mut first = Vec::new();
+    layout.fdisplay(&config, &mut first);
+    let first = String::from_utf8(first).unwrap();
+    assert!(first.contains("(10)"));
+
+    graph.add_nodes([(0, 99)]);
+    layout.relabel(&graph, &config);
+
+    let mut second = Vec::new();
+    layout.fdisplay(&config, &mut second);
+    let second = String::from_utf8(second).unwrap();
+
+    assert_eq!(first.replace("(10)", "(99)"), second);
+}
+
+#[test]
+fn relabel_wider_label() {
+    use termgraph::{Layout, ValueFormatter};
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, 9), (1, 20), (2, 30)]);
+    graph.add_edges([(0, 1), (1, 2)]);
+    let snapshot = graph.clone();
+
+    let config = Config::new(ValueFormatter::new(), 10);
+    let mut layout = Layout::compute(&snapshot, &config);
+
+    graph.add_nodes([(0, 10)]);
+    layout.relabel(&graph, &config);
+
+    let mut relabeled = Vec::new();
+    layout.fdisplay(&config, &mut relabeled);
+    let relabeled = String::from_utf8(relabeled).unwrap();
+    assert_eq!(termgraph::render(&graph, &config), relabeled);
+}
+
+#[test]
+fn cell_budget_summary() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 10).max_cells(1);
+
+    assert_eq!(
+        vec![" (0)", "  |", "  |", "  V", " … 2 more nodes"],
+        termgraph::render_to_lines(&graph, &config)
+    );
+
+    // A single remaining Node is not pluralized
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+    assert_eq!(
+        vec![" (0)", "  |", "  |", "  V", " … 1 more node"],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn level_limit_summary() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+    graph.add_edges([(0, 1), (0, 2), (1, 3)]);
+
+    let render = |max_levels| {
+        let config = Config::new(IDFormatter::new(), 10).max_levels(max_levels);
+        termgraph::render_to_lines(&graph, &config)
+    };
+
+    let mut expected = vec![
+        " (0)", "  |", "  +---+", "  |   |", "  V   |", " (1)  |", "  |   |", "  +---++",
+        "      ||", "  +---+|", "  |    |", "  V    V",
+    ];
+    let complete = [expected.clone(), vec![" (2)  (3)"]].concat();
+    expected.push(" … 1 more level, 2 more nodes");
+    assert_eq!(expected, render(2));
+    assert_eq!(complete, render(3));
+}
+
+#[test]
+fn line_prefix() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::new(IDFormatter::new(), 10).line_prefix("> ");
+
+    let output = termgraph::render(&graph, &config);
+
+    assert!(output
+        .lines()
+        .filter(|l| !l.is_empty())
+        .all(|l| l.starts_with("> ")));
+    assert_eq!(Some(""), output.lines().last());
+}
+
+#[test]
+fn display_options_overlay() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 10).default_colors();
+    let options = DisplayOptions::new()
+        .colors(false)
+        .highlight([0])
+        .filter(|id, _| *id != 2);
+
+    let output = termgraph::render_with(&graph, &config, &options);
+
+    assert!(output.contains("\x1b[7m(0)\x1b[0m"));
+    assert!(output.contains("(1)"));
+    assert!(!output.contains("(2)"));
+    assert!(!output.contains("\x1b[31m"));
+}
+
+#[test]
+fn unicode_preset() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::unicode(IDFormatter::new(), 10).disable_colors();
+
+    assert_eq!(
+        vec![" (0)", "  │", "  │", "  ▼", " (1)"],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn wrapped_labels() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "/usr/local/bin"), (1, "short")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::new(termgraph::ValueFormatter::new(), 10).wrap_labels(7);
+
+    assert_eq!(
+        vec![" (/usr/", " local/", " bin)", "    |", "    |", "    V", " (short)"],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn back_edge_styles() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (1, 2), (2, 0)]);
+    // Any Edge of the Cycle could be reversed otherwise
+    graph.prefer_back_edge(2, 0);
+
+    let render = |style| {
+        let config = Config::new(IDFormatter::new(), 10).back_edge_style(style);
+        termgraph::render_to_lines(&graph, &config)
+    };
+
+    // The marked Edge gets its own Column next to the other Edge leading to the same Node
+    assert_eq!(
+        vec![
+            " (0)", "  |", "  +---+", "  |   |", "  V   |", " (1)  |", "  |   |", "  |+--+",
+            "  ||", "  V^", " (2)",
+        ],
+        render(BackEdgeStyle::Marked)
+    );
+    assert_eq!(
+        vec![
+            " (0)",
+            "  |",
+            "  |",
+            "  V",
+            " (1)",
+            "  |",
+            "  |",
+            "  V",
+            " (2)",
+            "",
+            " back edge: (2) -> (0)",
+        ],
+        render(BackEdgeStyle::Omit)
+    );
+}
+
+#[test]
+fn edge_gap_separates_verticals() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "a"), (1, "b"), (2, "c"), (3, "d"), (4, "e"), (5, "f")]);
+    graph.add_edges([
+        (0, 2),
+        (1, 3),
+        (0, 3),
+        (1, 2),
+        (2, 4),
+        (3, 4),
+        (0, 5),
+        (1, 5),
+    ]);
+
+    let config = Config::new(IDFormatter::new(), 10)
+        .node_padding(0)
+        .edge_gap(1);
+
+    // Vertical Edges are never placed in directly neighbouring Columns
+    assert_eq!(
+        vec![
+            " (0)(1)",
+            "  |  |",
+            "  +--+--+",
+            "  |  |  |",
+            "  +--+--+-+",
+            "  |  |  | |",
+            "  V  V  | |",
+            " (2)(3) | |",
+            "  |  |  | |",
+            "  +--+  | |",
+            "  |     | |",
+            "  |  +--+ |",
+            "  |  |    |",
+            "  |  +----+",
+            "  |  |",
+            "  V  V",
+            " (4)(5)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn justify_center() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 2), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 10).justify(Justify::Center);
+
+    assert_eq!(
+        vec![
+            " (0)  (1)",
+            "  |    |",
+            "  +-+  |",
+            "    |  |",
+            "    +--+",
+            "    |",
+            "    V",
+            "   (2)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn debug_layout() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (1, 2), (0, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 10).debug_layout(true);
+
+    assert_eq!(
+        vec![
+            "0  (0)",
+            "    |",
+            "    +---+",
+            "    |   |",
+            "    V   |",
+            "1  (1)  ·",
+            "    |   |",
+            "    +---+",
+            "    |",
+            "    V",
+            "2  (2)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn custom_gutter() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let stages = ["parse", "lower"];
+    let config = Config::new(IDFormatter::new(), 10).gutter(move |level| stages[level].to_string());
+
+    assert_eq!(
+        vec![
+            "parse  (0)",
+            "        |",
+            "        |",
+            "        V",
+            "lower  (1)"
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn color_strategies() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+    graph.add_edges([(0, 2), (1, 2), (2, 3)]);
+
+    let render = |strategy| {
+        let config = Config::new(IDFormatter::new(), 10)
+            .custom_colors(vec![Color::Red, Color::Green, Color::Yellow])
+            .color_strategy(strategy);
+
+        termgraph::render(&graph, &config)
+    };
+
+    // Both Edges into the same Target share their Color
+    let output = render(ColorBy::TargetNode);
+    assert_eq!(
+        2,
+        ["\x1b[31m", "\x1b[32m", "\x1b[33m"]
+            .iter()
+            .filter(|c| output.contains(*c))
+            .count()
+    );
+
+    // Every Level uses the next Color of the Palette
+    let output = render(ColorBy::Level);
+    assert!(output.contains("\x1b[31m"));
+    assert!(output.contains("\x1b[32m"));
+    assert!(!output.contains("\x1b[33m"));
+}
+
+#[test]
+fn color_markup() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::new(IDFormatter::new(), 10)
+        .custom_colors(vec![Color::Red])
+        .color_markup(
+            |c| format!("<span class=\"c{}\">", usize::from(c.clone())),
+            "</span>",
+        );
+    let options = DisplayOptions::new().highlight([1]);
+
+    let output = termgraph::render_with(&graph, &config, &options);
+
+    assert!(output.contains("<span class=\"c31\">|</span>"));
+    assert!(output.contains("<span class=\"c7\">(1)</span>"));
+    assert!(!output.contains('\x1b'));
+}
+
+#[test]
+fn multi_line_formatter() {
+    use termgraph::NodeFormat;
+
+    struct SnippetFormatter {}
+    impl NodeFormat<usize, &str> for SnippetFormatter {
+        fn format_node(&self, id: &usize, _: &&str) -> String {
+            format!("({id})")
+        }
+
+        fn format_node_lines(&self, id: &usize, code: &&str) -> Vec<String> {
+            std::iter::once(format!("({id})"))
+                .chain(code.lines().map(String::from))
+                .collect()
+        }
+    }
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "let x = 1;\nx + 1"), (1, "x")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::new(SnippetFormatter {}, 10);
+
+    assert_eq!(
+        vec![
+            " (0)",
+            " let x = 1;",
+            " x + 1",
+            "      |",
+            "  +---+",
+            "  |",
+            "  V",
+            " (1)",
+            " x",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn context_formatter() {
+    use termgraph::{ContextNodeFormat, NodeContext};
+
+    struct ContextFormatter {}
+    impl ContextNodeFormat<usize, &str> for ContextFormatter {
+        fn format_node(&self, id: &usize, _: &&str, context: &NodeContext) -> String {
+            format!(
+                "({id} {}/{} @{})",
+                context.in_degree, context.out_degree, context.level
+            )
+        }
+    }
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 10).context_formatter(ContextFormatter {});
+
+    assert_eq!(
+        vec![
+            " (0 0/2 @0)",
+            "      |",
+            "      +------+",
+            "      |      |",
+            "      V      |",
+            " (1 1/1 @1)  |",
+            "      |      |",
+            "      +------+",
+            "      |",
+            "      V",
+            " (2 2/0 @2)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn level_hook() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (1, 2)]);
+
+    let config =
+        Config::new(termgraph::ValueFormatter::new(), 10).level_hook(|level, id, _, label| {
+            match level {
+                0 => label,
+                _ => format!("[{id}@{level}]"),
+            }
+        });
+
+    assert_eq!(
+        vec![
+            " (first)", "    |", "   ++", "   |", "   V", " [1@1]", "   |", "   |", "   V",
+            " [2@2]",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn condensed_cycles() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+    graph.add_edges([(0, 1), (1, 2), (2, 1), (2, 3)]);
+
+    let config = Config::new(IDFormatter::new(), 10);
+
+    let mut output = Vec::new();
+    termgraph::fdisplay_condensed(&graph, &config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+
+    let first = output.find("(0)").unwrap();
+    let component = output.find("[2 nodes]").unwrap();
+    let last = output.find("(3)").unwrap();
+    assert!(first < component && component < last);
+    assert!(!output.contains("(1)"));
+    assert!(!output.contains("(2)"));
+}
+
+#[test]
+fn collapsed_clusters() {
+    use termgraph::Clusters;
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([
+        (0, "main"),
+        (1, "parser::expr"),
+        (2, "parser::stmt"),
+        (3, "codegen"),
+    ]);
+    graph.add_edges([(0, 1), (0, 2), (1, 2), (2, 3), (3, 1)]);
+
+    let config = Config::new(IDFormatter::new(), 10);
+    let mut clusters =
+        Clusters::new(|_, path: &&str| path.split_once("::").map(|(module, _)| module.to_string()));
+    clusters.toggle("parser");
+
+    let render = |clusters: &Clusters<usize, &'static str>| {
+        let mut output = Vec::new();
+        termgraph::fdisplay_clustered(&graph, &config, clusters, &mut output);
+        String::from_utf8(output).unwrap()
+    };
+
+    let output = render(&clusters);
+    assert!(output.contains("(0)"));
+    assert!(output.contains("[parser: 2 nodes]"));
+    assert!(output.contains("(3)"));
+    assert!(!output.contains("(1)"));
+
+    clusters.toggle("parser");
+    let output = render(&clusters);
+    assert!(output.contains("(1)"));
+    assert!(!output.contains("[parser"));
+}
+
+#[test]
+fn edge_weight_tiers() {
+    use termgraph::{LineGlyphBuilder, WeightTiers};
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+    graph.add_edges([(0, 1), (1, 2), (2, 3)]);
+
+    let tiers = WeightTiers::new()
+        .tier(1, LineGlyphBuilder::heavy())
+        .colored_tier(2, LineGlyphBuilder::double(), Color::Red);
+    let config = Config::new(IDFormatter::new(), 10).edge_weights(|src, _| *src as u64, tiers);
+
+    assert_eq!(
+        vec![
+            " (0)", "  |", "  |", "  V", " (1)", "  ┃", "  ┃", "  ▼", " (2)", "  ║", "  ║", "  ▼",
+            " (3)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+    assert!(termgraph::render(&graph, &config).contains("\x1b[31m║\x1b[0m"));
+}
+
+#[test]
+fn highlighted_critical_path() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, 1), (1, 5), (2, 2)]);
+    graph.add_edges([(0, 1), (0, 2)]);
+
+    let path: Vec<usize> = graph
+        .critical_path(|_, w| *w, |_, _| 0)
+        .into_iter()
+        .copied()
+        .collect();
+    assert_eq!(vec![0, 1], path);
+
+    let config = Config::new(IDFormatter::new(), 10);
+    let options = DisplayOptions::new().highlight_path(path);
+    let output = termgraph::render_with(&graph, &config, &options);
+
+    assert!(output.contains("\x1b[7m(1)\x1b[0m"));
+    assert!(output.contains("\x1b[2m(2)\x1b[0m"));
+}
+
+#[test]
+fn swimlanes() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([("request", "gateway"), ("verify", "auth"), ("query", "db")]);
+    graph.add_edges([("request", "verify"), ("request", "query")]);
+
+    let config =
+        Config::new(IDFormatter::new(), 3).swimlanes(|_, service: &&str| service.to_string());
+
+    let output = termgraph::render(&graph, &config);
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(" auth     : db      : gateway", lines[0]);
+    // Only the horizontal Lines of Edges between the Lanes cross the Separators
+    assert!(lines[1..]
+        .iter()
+        .filter(|line| !line.is_empty())
+        .all(|line| matches!(line.chars().nth(10), Some(':' | '-' | '+'))));
+
+    let column = |name: &str| {
+        lines
+            .iter()
+            .find_map(|line| line.find(name))
+            .expect("Every Node is displayed")
+    };
+    assert!(column("(verify)") < 10);
+    assert!((10..20).contains(&column("(query)")));
+    assert!(column("(request)") > 20);
+}
+
+#[test]
+fn swimlanes_with_edge_gap() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([("a", "a"), ("b", "b")]);
+    graph.add_edges([("a", "b")]);
+
+    let config = Config::new(IDFormatter::new(), 3)
+        .edge_gap(1)
+        .swimlanes(|id: &&str, _| format!("lane{id}"));
+
+    let output = termgraph::render(&graph, &config);
+
+    assert!(output.contains("(a)"), "{}", output);
+    assert!(output.contains("(b)"), "{}", output);
+}
+
+#[test]
+fn explicit_ranks() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([("fetch", 0), ("lint", 1), ("build", 1), ("deploy", 5)]);
+    graph.add_edges([("fetch", "deploy"), ("deploy", "build"), ("lint", "build")]);
+
+    let config = Config::new(IDFormatter::new(), 1)
+        .rank(|_, stage| *stage)
+        .back_edge_style(BackEdgeStyle::Omit);
+
+    let output = termgraph::render(&graph, &config);
+    let row = |name: &str| {
+        output
+            .lines()
+            .position(|line| line.contains(&format!("({name})")) && !line.contains("->"))
+            .expect("Every Node is displayed")
+    };
+
+    // The Ranks are used even though the Edges and the Layer limit disagree with them
+    assert!(row("fetch") < row("lint"));
+    assert_eq!(row("lint"), row("build"));
+    assert!(row("build") < row("deploy"));
+
+    assert!(output.contains("back edge: (deploy) -> (build)"));
+    assert!(output.contains("back edge: (lint) -> (build)"));
+}
+
+#[test]
+fn explicit_ranks_exceed_glyph_width() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..9).map(|i| (i, ())));
+
+    let config = Config::new(IDFormatter::new(), 4)
+        .max_glyphs_per_layer(16)
+        .rank(|_, _| 0);
+
+    let output = termgraph::render(&graph, &config);
+    let first = output.lines().next().unwrap_or_default();
+
+    // All the Nodes share the single Rank, even though they do not fit into 16 Glyphs
+    for id in 0..9 {
+        assert!(first.contains(&format!("({id})")), "{}", output);
+    }
+}
+
+#[test]
+fn reachable_with_stubs() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([
+        (0, "first"),
+        (1, "second"),
+        (2, "third"),
+        (3, "fourth"),
+        (4, "other"),
+    ]);
+    graph.add_edges([(0, 1), (1, 2), (2, 3), (4, 1)]);
+
+    let config = Config::new(IDFormatter::new(), 3);
+    let mut output = Vec::new();
+    termgraph::fdisplay_reachable(&graph, [0], 1, &config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("(0)"));
+    assert!(output.contains("(1) → …"));
+    assert!(!output.contains("(0) →"));
+    assert!(!output.contains("(2)"));
+    assert!(!output.contains("(4)"));
+}
+
+#[test]
+fn collapsed_chains() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..12).map(|i| (i, i)));
+    graph.add_edges((0..9).map(|i| (i, i + 1)));
+    graph.add_edges([(9, 10), (9, 11)]);
+
+    let config = Config::new(IDFormatter::new(), 3).collapse_chains(4);
+    let output = termgraph::render(&graph, &config);
+
+    assert!(output.contains("(0 → … 8 nodes … → 9)"));
+    assert!(output.contains("(10)"));
+    assert!(output.contains("(11)"));
+    assert!(!output.contains("(5)"));
+}
+
+#[test]
+fn hyperlinks() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "src/main.rs"), (1, "src/lib.rs"), (2, "")]);
+    graph.add_edges([(0, 1), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 3)
+        .hyperlinks(|_, file: &&str| (!file.is_empty()).then(|| format!("file:///{file}")));
+    let output = termgraph::render(&graph, &config);
+
+    assert!(output.contains("\x1b]8;;file:///src/main.rs\x1b\\(0)"));
+    assert!(output.contains("\x1b]8;;file:///src/lib.rs\x1b\\(1)"));
+    assert_eq!(2, output.matches("\x1b]8;;\x1b\\").count());
+}
+
+#[test]
+fn levels_without_rendering() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..5).map(|i| (i, i)));
+    graph.add_edges((1..5).map(|i| (0, i)));
+
+    let config = Config::new(IDFormatter::new(), 2);
+    let levels = termgraph::Layout::compute_levels(&graph, &config);
+
+    assert_eq!(2, graph.depth());
+    assert_eq!(0, levels[&0]);
+    // Only two of the Children fit on the Level below the Root, the others spill over
+    assert_eq!(2, levels.values().copied().max().unwrap());
+}
+
+#[test]
+fn edited_levels() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+    graph.add_edges([(0, 1), (0, 2), (0, 3)]);
+
+    let config = Config::new(IDFormatter::new(), 3);
+    let mut levels = termgraph::Levels::compute(&graph, &config);
+    assert_eq!(2, levels.len());
+    assert!(levels.move_node(&3, 2));
+    // The Root is moved below all of its Children, so its Edges are drawn like Back-Edges
+    assert!(levels.move_node(&0, 5));
+    assert_eq!(Some(2), levels.level_of(&0));
+
+    let layout = termgraph::Layout::compute_with_levels(&graph, &config, &levels);
+    let mut output = Vec::new();
+    layout.fdisplay(&config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+
+    let row = |label: &str| output.lines().position(|l| l.contains(label)).unwrap();
+    assert!(row("(1)") < row("(3)"));
+    assert!(row("(3)") < row("(0)"));
+    assert_eq!(row("(1)"), row("(2)"));
+}
+
+#[test]
+fn inspect_positions() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 3).line_prefix("> ");
+    let layout = termgraph::Layout::compute(&graph, &config);
+    let mut output = Vec::new();
+    layout.fdisplay(&config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<Vec<char>> = output.lines().map(|l| l.chars().collect()).collect();
+
+    let positions = layout.node_positions(&config);
+    assert_eq!(3, positions.len());
+    for (id, position) in &positions {
+        let label: String = lines[position.row][position.column..][..position.width]
+            .iter()
+            .collect();
+        assert_eq!(format!("({id})"), label);
+        assert_eq!(
+            Some(*id),
+            layout.node_at(&config, position.column, position.row)
+        );
+    }
+    assert_eq!(None, layout.node_at(&config, 0, 0));
+
+    let edges = layout.edge_cells(&config);
+    assert!(edges.iter().any(|cell| cell.target == Some(&1)));
+    assert!(edges.iter().all(|cell| positions
+        .values()
+        .all(|p| !p.contains(cell.column, cell.row))));
+}
+
+#[test]
+fn level_separator() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 3).level_separator(Some('~'));
+
+    // One Rule directly below the Nodes of every Level except the last one
+    assert_eq!(
+        vec![
+            " (0)", "~~|~~~~~", "  +---+", "  |   |", "  V   |", " (1)  |", "~~|~~~|~", "  +---+",
+            "  |", "  V", " (2)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn ruler() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::new(IDFormatter::new(), 3).ruler(true);
+    let layout = termgraph::Layout::compute(&graph, &config);
+    let mut output = Vec::new();
+    layout.fdisplay(&config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!("  0", lines[0]);
+    assert!(lines[1].starts_with("  0123"));
+    assert!(lines[2].starts_with("0 "));
+
+    // The Positions of the Nodes account for the Ruler
+    let position = layout.node_positions(&config)[&1];
+    assert_eq!(
+        "(1)",
+        &lines[position.row][position.column..][..position.width]
+    );
+}
+
+#[test]
+fn passthrough_glyph() {
+    use termgraph::LineGlyphBuilder;
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (0, 2), (1, 2)]);
+
+    let config =
+        Config::new(IDFormatter::new(), 3).line_glyphs(LineGlyphBuilder::ascii().passthrough(':'));
+
+    // The Edge from the first to the third Node passes by the second Node
+    assert_eq!(
+        vec![
+            " (0)", "  |", "  +---+", "  |   |", "  V   |", " (1)  :", "  |   |", "  +---+", "  |",
+            "  V", " (2)",
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn layout_warnings() {
+    use std::{cell::RefCell, rc::Rc};
+    use termgraph::LayoutWarning;
+
+    let warnings = Rc::new(RefCell::new(Vec::new()));
+    let collected = warnings.clone();
+    let config = Config::new(IDFormatter::new(), 3)
+        .max_label_width(3)
+        .on_warning(move |warning| {
+            let warning = match warning {
+                LayoutWarning::TruncatedLabel { node, .. } => format!("truncated {node}"),
+                LayoutWarning::DroppedSelfLoop { node } => format!("self-loop {node}"),
+                LayoutWarning::BrokenCycle { src, target } => format!("cycle {src} {target}"),
+                LayoutWarning::ClampedEdge { .. } => "clamped".to_string(),
+                LayoutWarning::DroppedEdgeLabel { .. } => "edge label".to_string(),
+                LayoutWarning::InvalidLabel { node, .. } => format!("invalid {node}"),
+            };
+            collected.borrow_mut().push(warning);
+        });
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (10, "third")]);
+    graph.add_edges([(0, 1), (1, 1), (1, 10), (10, 0)]);
+
+    let mut output = Vec::new();
+    termgraph::fdisplay(&graph, &config, &mut output);
+
+    let mut warnings = warnings.borrow().clone();
+    warnings.sort();
+    assert_eq!(3, warnings.len(), "{warnings:?}");
+    assert!(warnings[0].starts_with("cycle"));
+    assert_eq!(["self-loop 1", "truncated 10"], warnings[1..]);
+}
+
+#[test]
+fn invalid_label_warnings() {
+    use std::{cell::RefCell, rc::Rc};
+    use termgraph::{LabelIssue, LayoutWarning, Validate, ValueFormatter};
+
+    let warnings = Rc::new(RefCell::new(Vec::new()));
+    let collected = warnings.clone();
+    let config = Config::new(Validate::new(ValueFormatter::new()), 3).on_warning(move |warning| {
+        if let LayoutWarning::InvalidLabel { node, issue } = warning {
+            collected.borrow_mut().push((*node, issue));
+        }
+    });
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "plain"), (1, "\x1b[1mbold")]);
+    graph.add_edges([(0, 1)]);
+
+    assert_eq!(
+        vec![
+            " (plain)",
+            "    |",
+            "    +-+",
+            "      |",
+            "      V",
+            " (␛[1mbold)"
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+    assert_eq!(
+        vec![(1, LabelIssue::ControlCharacter('\x1b'))],
+        *warnings.borrow()
+    );
+}
+
+#[test]
+fn sorted_siblings() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..8).map(|i| (i, i)));
+    graph.add_edges((1..8).map(|i| (0, i)));
+
+    let config = Config::new(IDFormatter::new(), 10).sort_siblings();
+    let output = termgraph::render(&graph, &config);
+
+    let children = output.lines().find(|l| l.contains("(1)")).unwrap();
+    assert_eq!(" (1)  (2)  (3)  (4)  (5)  (6)  (7)", children.trim_end());
+}
+
+#[test]
+fn from_edges() {
+    let config = Config::new(IDFormatter::new(), 3);
+    let mut output = Vec::new();
+    termgraph::fdisplay_edges([("a", "b"), ("a", "c"), ("b", "c")], &config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+
+    let row = |label: &str| output.lines().position(|l| l.contains(label)).unwrap();
+    assert!(row("(a)") < row("(b)"));
+    assert!(row("(b)") < row("(c)"));
+}
+
+#[test]
+fn reversal_cost() {
+    use std::{cell::RefCell, rc::Rc};
+    use termgraph::LayoutWarning;
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([("entry", ()), ("header", ()), ("body", ()), ("exit", ())]);
+    graph.add_edges([
+        ("entry", "header"),
+        ("header", "body"),
+        ("body", "header"),
+        ("header", "exit"),
+    ]);
+
+    for back_edge in [("body", "header"), ("header", "body")] {
+        let broken = Rc::new(RefCell::new(Vec::new()));
+        let collected = broken.clone();
+        let config = Config::new(IDFormatter::new(), 3)
+            .reversal_cost(move |src, target| if (*src, *target) == back_edge { 1 } else { 10 })
+            .on_warning(move |warning| {
+                if let LayoutWarning::BrokenCycle { src, target } = warning {
+                    collected.borrow_mut().push((*src, *target));
+                }
+            });
+
+        let mut output = Vec::new();
+        termgraph::fdisplay(&graph, &config, &mut output);
+        assert_eq!(vec![back_edge], *broken.borrow());
+    }
+}
+
+#[test]
+fn edge_styles() {
+    use termgraph::LineStyle;
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (0, 2)]);
+
+    let config = Config::new(IDFormatter::new(), 3).edge_style(|_, target| match target {
+        2 => LineStyle::Dotted,
+        _ => LineStyle::Solid,
+    });
+
+    // Only the Part of the Line leading to the third Node is dotted
+    assert_eq!(
+        vec![
+            " (0)",
+            "  |",
+            "  +----+",
+            "  |    ┊",
+            "  V    ▼",
+            " (1)  (2)"
+        ],
+        termgraph::render_to_lines(&graph, &config)
+    );
+}
+
+#[test]
+fn unicode_arrows() {
+    use termgraph::{BackEdgeStyle, LineGlyphBuilder};
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let config = Config::new(IDFormatter::new(), 3)
+        .line_glyphs(LineGlyphBuilder::unicode_arrows())
+        .back_edge_style(BackEdgeStyle::Marked);
+    let render = |graph: &DirectedGraph<i32, &'static str>| termgraph::render(graph, &config);
+
+    let output = render(&graph);
+    assert!(output.contains('▼'), "{output}");
+    assert!(output.contains('|'), "{output}");
+
+    // The marked Back-Edge ends in an upward Arrow
+    graph.add_edges([(1, 0)]);
+    let output = render(&graph);
+    assert!(output.contains('▲'), "{output}");
+}
+
+#[test]
+fn wide_labels() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([("日本語の名前", ()), ("b", ())]);
+    graph.add_edges([("日本語の名前", "b")]);
+
+    let config = Config::new(IDFormatter::new(), 3).max_label_width(6);
+
+    // The wide Characters take up two Columns each, so the Edge starts below the middle of the Label
+    assert_eq!(
+        vec![" (日本… ", "    |", "  +-+", "  |", "  V", " (b) ", ""],
+        termgraph::render(&graph, &config)
+            .lines()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn render_report() {
+    let config = Config::new(IDFormatter::new(), 3).max_label_width(3);
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (10, "second"), (2, "third")]);
+    graph.add_edges([(0, 10), (10, 2), (2, 0)]);
+
+    let mut output = Vec::new();
+    let report = termgraph::fdisplay_report(&graph, &config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(3, report.levels);
+    assert_eq!(1, report.reversed_edges);
+    assert_eq!(1, report.truncated_labels);
+    assert!(output
+        .lines()
+        .all(|l| l.chars().count() <= report.max_width));
+
+    // A simple Chain has no Crossings at all
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (10, "second"), (2, "third")]);
+    graph.add_edges([(0, 10), (10, 2)]);
+    let report = termgraph::fdisplay_report(&graph, &config, std::io::sink());
+    assert_eq!(0, report.reversed_edges);
+    assert_eq!(0, report.crossings);
+}
+
+#[test]
+fn layout_metrics() {
+    let config = Config::new(IDFormatter::new(), 3);
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second")]);
+    graph.add_edges([(0, 1)]);
+
+    let layout = termgraph::Layout::compute(&graph, &config);
+    let metrics = layout.metrics();
+    assert_eq!(0, metrics.crossings);
+    assert_eq!(layout.edge_cells(&config).len(), metrics.edge_length);
+    assert!(metrics.aspect_ratio > 0.0);
+}
+
+#[test]
+fn best_of_layouts() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..9).map(|i| (i, ())));
+    graph.add_edges([
+        (0, 5),
+        (1, 4),
+        (2, 3),
+        (0, 6),
+        (3, 7),
+        (4, 8),
+        (5, 7),
+        (1, 6),
+    ]);
+
+    let config = Config::new(IDFormatter::new(), 4).best_of(30, None);
+    let recording = termgraph::Layout::compute(&graph, &config).record(&config);
+
+    // The recorded Levels are the ones of the chosen Layout
+    let config = Config::new(IDFormatter::new(), 4);
+    assert_eq!(recording.output(), recording.replay(&config));
+
+    // Every tried Ordering gets the whole render time, so the chosen one is always complete
+    let limited = Config::new(IDFormatter::new(), 4)
+        .best_of(30, None)
+        .max_render_time(std::time::Duration::from_secs(60));
+    let output = termgraph::render(&graph, &limited);
+    assert!((0..9).all(|id| output.contains(&format!("({id})"))));
+    assert!(!output.contains("more nodes"));
+}
+
+#[test]
+fn shortened_labels() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([
+        (0, "root"),
+        (1, "a rather long label"),
+        (2, "another long label"),
+    ]);
+    graph.add_edges([(0, 1), (0, 2)]);
+
+    let render = |shorten: bool| {
+        let config = Config::new(IDValueFormatter::new(), 3)
+            .max_glyphs_per_layer(34)
+            .shorten_labels(shorten);
+        let mut output = Vec::new();
+        let levels = termgraph::fdisplay_report(&graph, &config, &mut output).levels;
+        (levels, String::from_utf8(output).unwrap())
+    };
+
+    let (levels, _) = render(false);
+    assert_eq!(3, levels);
+
+    // Both Children fit onto the same Level, once one of them is only displayed by its ID
+    let (levels, output) = render(true);
+    assert_eq!(2, levels, "{output}");
+    assert!(
+        output.contains(" (1) ") || output.contains(" (2) "),
+        "{output}"
+    );
+}
+
+#[test]
+fn padded_shortened_labels() {
+    use termgraph::PadTo;
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([
+        (0, "root"),
+        (1, "a rather long label"),
+        (2, "another long label"),
+    ]);
+    graph.add_edges([(0, 1), (0, 2)]);
+
+    let render = |shorten: bool| {
+        let config = Config::new(PadTo::new(IDValueFormatter::new(), 16), 3)
+            .max_glyphs_per_layer(34)
+            .shorten_labels(shorten);
+        termgraph::render_to_lines(&graph, &config)
+    };
+
+    // The shortened Labels are padded as well, so they do not fit onto the same Level either
+    assert_eq!(
+        vec![
+            " (0: root)",
+            "         |",
+            "         +---------+",
+            "         |         |",
+            "         V         |",
+            " (1: a rather lo…  |",
+            "                   |",
+            "         +---------+",
+            "         |",
+            "         V",
+            " (2: another lon…",
+        ],
+        render(true)
+    );
+    assert_eq!(render(false), render(true));
+}
+
+#[test]
+fn colorblind_patterns() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third"), (3, "fourth")]);
+    graph.add_edges([(0, 2), (1, 3)]);
+
+    let config = Config::new(IDFormatter::new(), 3).colorblind_patterns();
+
+    // Both Edges have their own Pattern
+    assert_eq!(
+        vec![" (0)  (1)", "  |    !", "  |    !", "  V    V", " (2)  (3)"],
+        termgraph::render_to_lines(&graph, &config)
+    );
+    assert!(!termgraph::render(&graph, &config).contains('\x1b'));
+}
+
+#[test]
+fn render_lines() {
+    let build = || {
+        let mut graph = DirectedGraph::new();
+        graph.add_nodes((0..5).map(|i| (i, ())));
+        graph.add_edges([(0, 1), (0, 2), (0, 4), (1, 3), (2, 3), (3, 4)]);
+        graph
+    };
+    let config = Config::new(IDFormatter::new(), 3).default_colors();
+
+    let lines = termgraph::render_to_lines(&build(), &config);
+    assert_eq!(" (0)", lines[0]);
+    assert!(lines.iter().any(|l| l == " (1)  (2)  |"), "{lines:#?}");
+    assert!(lines
+        .iter()
+        .all(|l| l == l.trim_end() && !l.contains('\x1b')));
+    assert!(!lines.last().unwrap().is_empty());
+
+    // The Output does not depend on the Order the Nodes are stored in
+    for _ in 0..20 {
+        assert_eq!(lines, termgraph::render_to_lines(&build(), &config));
+    }
+}
+
+#[test]
+fn render_lines_deterministic() {
+    let dag = [
+        (0, 3),
+        (0, 4),
+        (1, 4),
+        (1, 5),
+        (2, 5),
+        (2, 6),
+        (3, 7),
+        (4, 7),
+        (5, 8),
+        (6, 9),
+    ];
+    let cyclic = [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+        (4, 2),
+        (4, 5),
+        (5, 1),
+    ];
+    let config = Config::new(IDFormatter::new(), 2);
+
+    for edges in [&dag[..], &cyclic[..]] {
+        let build = || {
+            let mut graph = DirectedGraph::new();
+            graph.add_nodes((0..10).map(|i| (i, ())));
+            graph.add_edges(edges.iter().copied());
+            graph
+        };
+
+        // Every Graph is stored with a freshly seeded HashMap, so the Levels
+        // and the broken Cycles must not depend on its Iteration Order
+        let lines = termgraph::render_to_lines(&build(), &config);
+        for _ in 0..200 {
+            assert_eq!(lines, termgraph::render_to_lines(&build(), &config));
+        }
+    }
+}
+
+#[test]
+fn id_without_display() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct NodeId(u64);
+
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(NodeId(0), "first"), (NodeId(1), "second")]);
+    graph.add_edges([(NodeId(0), NodeId(1))]);
+
+    let config = Config::new(termgraph::ValueFormatter::new(), 3).max_glyphs_per_layer(40);
+    let lines = termgraph::render_to_lines(&graph, &config);
+    assert_eq!(" (first)", lines[0]);
+    assert!(lines.iter().any(|l| l.contains("(second)")), "{lines:#?}");
+}
+
+#[test]
+fn render_session() {
+    struct Counting(std::rc::Rc<std::cell::Cell<usize>>);
+    impl termgraph::NodeFormat<usize, ()> for Counting {
+        fn format_node(&self, id: &usize, _: &()) -> String {
+            self.0.set(self.0.get() + 1);
+            id.to_string()
+        }
+    }
+
+    let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+    let config = Config::new(Counting(calls.clone()), 3);
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, ()), (1, ())]);
+    graph.add_edges([(0, 1)]);
+
+    let mut session = termgraph::RenderSession::new();
+    let (mut first, mut second) = (Vec::new(), Vec::new());
+    session.fdisplay(&graph, &config, 0, &mut first);
+    let formatted = calls.get();
+    session.fdisplay(&graph, &config, 0, &mut second);
+    assert_eq!(formatted, calls.get());
+    assert_eq!(first, second);
+
+    // A new Generation formats the Labels again
+    graph.add_nodes([(2, ())]);
+    let mut third = Vec::new();
+    session.fdisplay(&graph, &config, 1, &mut third);
+    assert!(calls.get() > formatted);
+    assert_ne!(first, third);
+}
+
+#[test]
+fn render_session_reuse() {
+    struct Counting(std::rc::Rc<std::cell::Cell<usize>>);
+    impl termgraph::NodeFormat<usize, &str> for Counting {
+        fn format_node(&self, _: &usize, value: &&str) -> String {
+            self.0.set(self.0.get() + 1);
+            value.to_string()
+        }
+    }
+
+    let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "a"), (1, "b"), (2, "c")]);
+    graph.add_edges([(0, 1), (0, 2)]);
+    let mut session = termgraph::RenderSession::new();
+    let mut first = Vec::new();
+    session.fdisplay(
+        &graph,
+        &Config::new(Counting(calls.clone()), 3),
+        0,
+        &mut first,
+    );
+    let first = String::from_utf8(first).unwrap();
+
+    // Only the Values changed, so the Levels are kept and the Names are formatted again
+    graph.add_nodes([(1, "bb"), (2, "cc")]);
+    let formatted = calls.get();
+    let mut second = Vec::new();
+    session.fdisplay(
+        &graph,
+        &Config::new(Counting(calls.clone()), 3),
+        1,
+        &mut second,
+    );
+    let second = String::from_utf8(second).unwrap();
+    assert!(calls.get() > formatted);
+    assert_eq!(first.lines().count(), second.lines().count());
+    assert!(second.contains("bb") && second.contains("cc"));
+
+    // The Levels are kept for the same Shape, even though the Config only allows one Node per Level
+    let narrow = Config::new(Counting(calls.clone()), 1);
+    let mut third = Vec::new();
+    session.fdisplay(&graph, &narrow, 2, &mut third);
+    let third = String::from_utf8(third).unwrap();
+    assert_eq!(second.lines().count(), third.lines().count());
+    assert!(termgraph::render(&graph, &narrow).lines().count() > third.lines().count());
+
+    // Restyling reuses the Names of the same Generation
+    let colored = Config::new(Counting(calls.clone()), 3).default_colors();
+    let formatted = calls.get();
+    session.restyle();
+    let mut fourth = Vec::new();
+    session.fdisplay(&graph, &colored, 2, &mut fourth);
+    let fourth = String::from_utf8(fourth).unwrap();
+    assert_eq!(formatted, calls.get());
+    assert!(fourth.contains('\x1b'));
+    assert_eq!(second.lines().count(), fourth.lines().count());
+}
+
+#[test]
+fn display_multi() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (0, 2)]);
+    let config = Config::new(IDFormatter::new(), 3).default_colors();
+
+    let mut colored = Vec::new();
+    let mut plain = termgraph::StripColors::new(Vec::new());
+    termgraph::fdisplay_multi(&graph, &config, &mut [&mut colored, &mut plain]);
+    let (colored, plain) = (
+        String::from_utf8(colored).unwrap(),
+        String::from_utf8(plain.into_inner()).unwrap(),
+    );
+
+    // Both Writers received the same Picture
+    assert!(colored.contains('\x1b'), "{colored}");
+    assert!(!plain.contains('\x1b'), "{plain}");
+    assert_eq!(colored.lines().count(), plain.lines().count());
+}
+
+#[test]
+fn edge_labels() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([("idle", ()), ("running", ()), ("done", ())]);
+    graph.add_labeled_edges([("idle", "running", "start"), ("running", "done", "finish")]);
+    graph.add_edges([("idle", "done")]);
+
+    let config = Config::new(IDFormatter::new(), 3).edge_gap(6);
+    let lines = termgraph::render_to_lines(&graph, &config);
+    let output = lines.join("\n");
+    assert!(output.contains("start"), "{output}");
+    assert!(output.contains("finish"), "{output}");
+
+    // The Labels do not move the Nodes
+    let mut unlabeled = DirectedGraph::new();
+    unlabeled.add_nodes([("idle", ()), ("running", ()), ("done", ())]);
+    unlabeled.add_edges([("idle", "running"), ("running", "done"), ("idle", "done")]);
+    let plain = termgraph::render_to_lines(&unlabeled, &config);
+    assert_eq!(lines.len(), plain.len());
+    assert_eq!(lines[0], plain[0]);
+}
+
+#[test]
+fn edge_label_order() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..6).map(|i| (i, ())));
+    graph.add_labeled_edges((1..6).map(|i| (0, i, format!("to {i}"))));
+
+    let dropped = std::rc::Rc::new(std::cell::Cell::new(0));
+    let counter = dropped.clone();
+    let config = Config::new(IDFormatter::new(), 5).on_warning(move |warning| {
+        if let termgraph::LayoutWarning::DroppedEdgeLabel { src: 0, .. } = warning {
+            counter.set(counter.get() + 1);
+        }
+    });
+
+    // The Labels of the Edges sharing their Source are placed the same way every time
+    let levels = termgraph::Levels::compute(&graph, &config);
+    let render = || {
+        let mut output = Vec::new();
+        termgraph::Layout::compute_with_levels(&graph, &config, &levels)
+            .fdisplay(&config, &mut output);
+        String::from_utf8(output).unwrap()
+    };
+    let first = render();
+    for _ in 0..5 {
+        assert_eq!(first, render());
+    }
+
+    // Every Label that did not fit is reported, once for each of the 6 Renders
+    let drawn = (1..6)
+        .filter(|i| first.contains(&format!("to {i}")))
+        .count();
+    assert!(drawn > 0, "{first}");
+    assert_eq!(6 * (5 - drawn), dropped.get());
+}
+
+#[test]
+fn layout_weights() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..5).map(|i| (i, ())));
+    graph.add_edges([(0, 1), (1, 2), (2, 3), (0, 4)]);
+
+    let config = Config::new(IDFormatter::new(), 3);
+    assert_eq!(3, termgraph::Layout::compute_levels(&graph, &config)[&4]);
+
+    // The heavy Edge is kept as short as possible
+    let config = config.layout_weights(|src, target| match (*src, *target) {
+        (0, 4) => 10,
+        _ => 1,
+    });
+    let levels = termgraph::Layout::compute_levels(&graph, &config);
+    assert_eq!(1, levels[&4]);
+    assert_eq!(3, levels[&3]);
+}
+
+#[test]
+fn neighborhood() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes((0..100).map(|i| (i, ())));
+    graph.add_edges((0..99).map(|i| (i, i + 1)));
+    graph.add_edges([(70, 51)]);
+
+    let config = Config::new(IDFormatter::new(), 3);
+    let mut output = Vec::new();
+    termgraph::fdisplay_neighborhood(&graph, &50, 1, &config, &mut output);
+    let output = String::from_utf8(output).unwrap();
+
+    for id in [49, 50, 51] {
+        assert!(output.contains(&format!("({id})")), "{output}");
+    }
+    for id in [48, 52, 70] {
+        assert!(!output.contains(&format!("({id})")), "{output}");
+    }
+    // The Predecessor of the second Node is reached within two Edges
+    assert!(graph.neighborhood(&50, 2).contains(&&70));
+}
+
+#[test]
+fn edge_list() {
+    let graph = DirectedGraph::from_edge_list("a,b\n\nb , c,1.5\nc,a\n".as_bytes()).unwrap();
+    assert_eq!(3, graph.nodes().count());
+    assert_eq!(
+        vec![&"c".to_string()],
+        graph.successors(&"b".to_string()).collect::<Vec<_>>()
+    );
+
+    let error = DirectedGraph::from_edge_list("a,b\n,c\n".as_bytes()).unwrap_err();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+    assert!(error.to_string().starts_with("line 2"));
+
+    // Quoted Fields may contain Commas and the BOM is not part of the first Node
+    let csv = "\u{feff}src,dst\n\"x,y\",z\n\"say \"\"hi\"\"\",z\n";
+    let graph = DirectedGraph::from_edge_list_with_header(csv.as_bytes()).unwrap();
+    let mut edges: Vec<_> = graph.edges().collect();
+    edges.sort();
+    assert_eq!(
+        vec![
+            (&"say \"hi\"".to_string(), &"z".to_string()),
+            (&"x,y".to_string(), &"z".to_string())
+        ],
+        edges
+    );
+    let graph = DirectedGraph::from_edge_list(csv.as_bytes()).unwrap();
+    assert!(graph.get(&"src".to_string()).is_some());
+
+    let error = DirectedGraph::from_edge_list("a,b\n\"c,d\n".as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("line 2: unterminated quote"));
+}
+
+#[test]
+fn render_string() {
+    let mut graph = DirectedGraph::new();
+    graph.add_nodes([(0, "first"), (1, "second"), (2, "third")]);
+    graph.add_edges([(0, 1), (1, 2)]);
+    let config = Config::new(IDFormatter::new(), 3).default_colors();
+
+    let mut output = Vec::new();
+    termgraph::fdisplay(&graph, &config, &mut output);
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        termgraph::render(&graph, &config)
+    );
+}